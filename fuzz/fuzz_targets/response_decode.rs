@@ -0,0 +1,16 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// The decoder only depends on `std`, `thiserror` and `tracing`, it is included as is to avoid
+// building the whole backend.
+#[allow(dead_code)]
+#[path = "../../src/rcon/response.rs"]
+mod response;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(response) = response::RconResponse::try_from_rcon_bytes(data) {
+        // A decoded response always fits in the bytes it was decoded from.
+        assert!(response.response_payload.len() + 14 <= data.len());
+    }
+});
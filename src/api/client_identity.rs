@@ -0,0 +1,71 @@
+use rocket::{
+    Request,
+    request::{FromRequest, Outcome},
+};
+use std::{convert::Infallible, net::IpAddr};
+
+/// Identity of the client performing a request.
+///
+/// Clients providing an API token, either with an `Authorization: Bearer <token>` or an
+/// `X-Api-Key: <token>` header, are identified by their token. Other clients are identified by
+/// their IP address, which honors the `ROCKET_IP_HEADER` set by the reverse proxy.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClientIdentity {
+    Token(String),
+    Ip(IpAddr),
+    Unknown,
+}
+
+impl ClientIdentity {
+    /// Resolves the identity of the client performing the request.
+    pub fn from_request(request: &Request<'_>) -> Self {
+        let token = request
+            .headers()
+            .get_one("Authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .or_else(|| request.headers().get_one("X-Api-Key"))
+            .map(str::trim)
+            .filter(|token| !token.is_empty());
+
+        match token {
+            Some(token) => ClientIdentity::Token(String::from(token)),
+            None => ClientIdentity::from_ip(request),
+        }
+    }
+
+    /// Resolves the identity of the client performing the request from its IP address only,
+    /// ignoring any API token it provides.
+    pub fn from_ip(request: &Request<'_>) -> Self {
+        match request.client_ip() {
+            Some(ip) => ClientIdentity::Ip(ip),
+            None => ClientIdentity::Unknown,
+        }
+    }
+
+    /// Returns the key identifying the client, as used in the configuration.
+    pub fn key(&self) -> String {
+        match self {
+            ClientIdentity::Token(token) => token.clone(),
+            ClientIdentity::Ip(ip) => ip.to_string(),
+            ClientIdentity::Unknown => String::from("unknown"),
+        }
+    }
+
+    /// Returns a description of the client that can be shared, without its API token.
+    pub fn redacted(&self) -> String {
+        match self {
+            ClientIdentity::Token(_) => String::from("an API token"),
+            ClientIdentity::Ip(ip) => ip.to_string(),
+            ClientIdentity::Unknown => String::from("an unknown client"),
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ClientIdentity {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(ClientIdentity::from_request(request))
+    }
+}
@@ -0,0 +1,171 @@
+use crate::{
+    game_events::GameEventError, log_tail::LogTailError, query::QueryError, rcon::RconError,
+    server::ServerError, slack::SlackError, slp::SlpError, telemetry::RequestId,
+    webhooks::WebhookError,
+};
+use rocket::{
+    Request, Response,
+    http::Status,
+    response::{self, Responder},
+    serde::json::Json,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiErrorBody {
+    pub error: String,
+    /// The ID of the request, also returned in the `X-Request-Id` header.
+    pub request_id: String,
+}
+
+/// Error returned by the API handlers, responding with a status and a JSON body describing it.
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    pub status: Status,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(status: Status, message: impl Into<String>) -> Self {
+        ApiError {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<Status> for ApiError {
+    fn from(status: Status) -> Self {
+        ApiError::new(status, status.reason_lossy())
+    }
+}
+
+impl From<RconError> for ApiError {
+    fn from(error: RconError) -> Self {
+        let status = match error {
+            RconError::Configuration { .. }
+            | RconError::Connection { .. }
+            | RconError::ConnectTimeout { .. } => Status::BadGateway,
+            RconError::Authentication { .. } => Status::NetworkAuthenticationRequired,
+            RconError::Shutdown { .. } => Status::InternalServerError,
+            RconError::RequestTooLarge { .. } => Status::PayloadTooLarge,
+            RconError::InvalidCommand { .. } => Status::BadRequest,
+            RconError::Send { .. }
+            | RconError::Receive { .. }
+            | RconError::AuthTimeout { .. }
+            | RconError::WriteTimeout { .. }
+            | RconError::Timeout { .. }
+            | RconError::DeadlineExceeded { .. }
+            | RconError::Desync { .. }
+            | RconError::QueueFull { .. }
+            | RconError::QueueTimeout { .. }
+            | RconError::CircuitOpen { .. } => Status::ServiceUnavailable,
+        };
+
+        ApiError::new(status, error.to_string())
+    }
+}
+
+impl From<SlpError> for ApiError {
+    fn from(error: SlpError) -> Self {
+        let status = match error {
+            SlpError::Configuration { .. }
+            | SlpError::Connection { .. }
+            | SlpError::ConnectTimeout { .. } => Status::BadGateway,
+            SlpError::Send { .. }
+            | SlpError::Receive { .. }
+            | SlpError::InvalidResponse { .. }
+            | SlpError::Timeout { .. } => Status::ServiceUnavailable,
+        };
+
+        ApiError::new(status, error.to_string())
+    }
+}
+
+impl From<QueryError> for ApiError {
+    fn from(error: QueryError) -> Self {
+        let status = match error {
+            QueryError::Configuration { .. } | QueryError::Connection { .. } => Status::BadGateway,
+            QueryError::Send { .. }
+            | QueryError::Receive { .. }
+            | QueryError::InvalidResponse { .. }
+            | QueryError::Timeout { .. } => Status::ServiceUnavailable,
+        };
+
+        ApiError::new(status, error.to_string())
+    }
+}
+
+impl From<ServerError> for ApiError {
+    fn from(error: ServerError) -> Self {
+        let status = match error {
+            ServerError::Configuration { .. } => Status::ServiceUnavailable,
+            ServerError::Start { .. } | ServerError::Stop { .. } => Status::InternalServerError,
+            ServerError::AlreadyRunning | ServerError::NotRunning => Status::Conflict,
+        };
+
+        ApiError::new(status, error.to_string())
+    }
+}
+
+impl From<GameEventError> for ApiError {
+    fn from(error: GameEventError) -> Self {
+        let status = match error {
+            GameEventError::Configuration { .. } => Status::ServiceUnavailable,
+            GameEventError::InvalidQuery { .. } => Status::BadRequest,
+        };
+
+        ApiError::new(status, error.to_string())
+    }
+}
+
+impl From<LogTailError> for ApiError {
+    fn from(error: LogTailError) -> Self {
+        let status = match error {
+            LogTailError::Configuration { .. } => Status::ServiceUnavailable,
+            LogTailError::InvalidFilter { .. } => Status::BadRequest,
+        };
+
+        ApiError::new(status, error.to_string())
+    }
+}
+
+impl From<WebhookError> for ApiError {
+    fn from(error: WebhookError) -> Self {
+        let status = match error {
+            WebhookError::Configuration { .. } => Status::ServiceUnavailable,
+            WebhookError::Delivery { .. } => Status::InternalServerError,
+            WebhookError::UnknownTrigger { .. } => Status::NotFound,
+            WebhookError::InvalidSignature { .. } => Status::Unauthorized,
+            WebhookError::InvalidParameters { .. } => Status::BadRequest,
+        };
+
+        ApiError::new(status, error.to_string())
+    }
+}
+
+impl From<SlackError> for ApiError {
+    fn from(error: SlackError) -> Self {
+        let status = match error {
+            SlackError::Configuration { .. } => Status::ServiceUnavailable,
+            SlackError::InvalidSignature { .. } => Status::Unauthorized,
+            SlackError::InvalidCommand { .. } => Status::BadRequest,
+        };
+
+        ApiError::new(status, error.to_string())
+    }
+}
+
+impl<'r> Responder<'r, 'static> for ApiError {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'static> {
+        let body = ApiErrorBody {
+            error: self.message,
+            request_id: RequestId::of(request).to_string(),
+        };
+
+        Response::build_from(Json(body).respond_to(request)?)
+            .status(self.status)
+            .ok()
+    }
+}
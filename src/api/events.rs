@@ -0,0 +1,220 @@
+use super::{ApiError, ApiErrorBody};
+use crate::{
+    game_events::{
+        GameEvent, GameEventError, GameEventKind, GameEventQuery, GameEventStore,
+        GameEventsConfiguration,
+    },
+    log_tail::LogTailConfiguration,
+    rate_limit::RateLimited,
+    telemetry::RequestSpan,
+};
+use rocket::{State, get, serde::json::Json};
+use serde::Serialize;
+use std::time::UNIX_EPOCH;
+use utoipa::ToSchema;
+
+/// Default number of game events returned.
+pub const DEFAULT_GAME_EVENTS_LIMIT: usize = 100;
+
+#[derive(Clone)]
+pub struct GameEventsManagedState {
+    /// The store of the game events, missing when the log tailing is not enabled.
+    pub store: Option<GameEventStore>,
+}
+
+impl GameEventsManagedState {
+    /// Creates the game events state from the environment variables.
+    pub fn try_new() -> Result<Self, GameEventError> {
+        let store = if LogTailConfiguration::is_set() {
+            Some(GameEventStore::new(GameEventsConfiguration::try_new()?))
+        } else {
+            None
+        };
+
+        Ok(GameEventsManagedState { store })
+    }
+
+    /// Returns the store of the game events, if the log tailing is enabled.
+    pub fn store(&self) -> Result<&GameEventStore, GameEventError> {
+        self.store
+            .as_ref()
+            .ok_or_else(|| GameEventError::Configuration {
+                cause: String::from("Environment variable 'LOG_TAIL_PATH' is not set"),
+            })
+    }
+}
+
+/// Details of a game event, depending on its type.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApiGameEventDetails {
+    PlayerJoined {
+        player: String,
+        uuid: Option<String>,
+        /// The IP address the player connected from.
+        address: Option<String>,
+    },
+    PlayerLeft {
+        player: String,
+        /// The reason of the disconnection, e.g. `Disconnected`.
+        reason: Option<String>,
+    },
+    Chat {
+        player: String,
+        message: String,
+    },
+    Death {
+        player: String,
+        /// The death message, e.g. `Steve was slain by Zombie`.
+        message: String,
+    },
+    Advancement {
+        player: String,
+        advancement: String,
+        /// The frame of the advancement: `task`, `goal` or `challenge`.
+        frame: String,
+    },
+    ServerStarted {
+        /// The startup time reported by the server, in milliseconds.
+        startup_time_ms: Option<u64>,
+    },
+    ServerStopping,
+    Lag {
+        /// How late the server is, in milliseconds.
+        behind_ms: u64,
+        /// The number of ticks the server skips to catch up.
+        ticks: u64,
+    },
+}
+
+impl From<GameEventKind> for ApiGameEventDetails {
+    fn from(kind: GameEventKind) -> Self {
+        match kind {
+            GameEventKind::PlayerJoined {
+                player,
+                uuid,
+                address,
+            } => ApiGameEventDetails::PlayerJoined {
+                player,
+                uuid,
+                address,
+            },
+            GameEventKind::PlayerLeft { player, reason } => {
+                ApiGameEventDetails::PlayerLeft { player, reason }
+            }
+            GameEventKind::Chat { player, message } => {
+                ApiGameEventDetails::Chat { player, message }
+            }
+            GameEventKind::Death { player, message } => {
+                ApiGameEventDetails::Death { player, message }
+            }
+            GameEventKind::Advancement {
+                player,
+                advancement,
+                frame,
+            } => ApiGameEventDetails::Advancement {
+                player,
+                advancement,
+                frame,
+            },
+            GameEventKind::ServerStarted { startup_time } => ApiGameEventDetails::ServerStarted {
+                startup_time_ms: startup_time.map(|startup_time| startup_time.as_millis() as u64),
+            },
+            GameEventKind::ServerStopping => ApiGameEventDetails::ServerStopping,
+            GameEventKind::Lag { behind, ticks } => ApiGameEventDetails::Lag {
+                behind_ms: behind.as_millis() as u64,
+                ticks,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiGameEvent {
+    /// The ID of the event, to be given as `since` to only get the following events.
+    pub id: u64,
+    /// The time printed on the log line, e.g. `12:34:56`.
+    pub time: Option<String>,
+    /// When the log line was read, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub details: ApiGameEventDetails,
+}
+
+impl From<GameEvent> for ApiGameEvent {
+    fn from(event: GameEvent) -> Self {
+        ApiGameEvent {
+            id: event.id,
+            time: event.time,
+            timestamp: event
+                .timestamp
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or_default(),
+            details: ApiGameEventDetails::from(event.kind),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiGameEvents {
+    /// The events, oldest first.
+    pub events: Vec<ApiGameEvent>,
+    /// The players online according to the logs, sorted by name.
+    pub online_players: Vec<String>,
+}
+
+/// Return the game events recognized in the server logs.
+///
+/// The players joining and leaving, the chat, the deaths, the advancements, the server starting
+/// and stopping and the lag warnings are recognized as the log file is read.
+#[utoipa::path(
+    tag = "logs",
+    context_path = "/api",
+    params(
+        ("kind" = Option<String>, Query, description = "The type of the events: `player_joined`, `player_left`, `chat`, `death`, `advancement`, `server_started`, `server_stopping` or `lag`"),
+        ("player" = Option<String>, Query, description = "The player the events are about, case-insensitively"),
+        ("since" = Option<u64>, Query, description = "The ID of the last event already known, to only get the following ones"),
+        ("limit" = Option<usize>, Query, description = "The maximum number of events, the most recent ones being returned, `100` by default"),
+    ),
+    responses(
+        (status = 200, description = "Successful response", body = ApiGameEvents),
+        (status = 400, description = "The type of the events is unknown", body = ApiErrorBody),
+        (status = 429, description = "Too many requests, retry after the delay given by the `Retry-After` header", body = ApiErrorBody),
+        (status = 503, description = "The log tailing is not configured", body = ApiErrorBody),
+    )
+)]
+#[tracing::instrument(
+    name = "GET /api/events",
+    parent = request_span.span(),
+    skip(request_span, _rate_limited, events_state),
+    fields(
+        http.route = "/api/events",
+        app.handler = "handle_events",
+        app.component = "api",
+    )
+)]
+#[get("/events?<kind>&<player>&<since>&<limit>")]
+pub async fn handle_events(
+    request_span: &RequestSpan,
+    _rate_limited: RateLimited,
+    events_state: &State<GameEventsManagedState>,
+    kind: Option<&str>,
+    player: Option<&str>,
+    since: Option<u64>,
+    limit: Option<usize>,
+) -> Result<Json<ApiGameEvents>, ApiError> {
+    let store = events_state.store()?;
+
+    let events = store.query(&GameEventQuery {
+        kind: kind.filter(|kind| !kind.is_empty()),
+        player: player.filter(|player| !player.is_empty()),
+        since,
+        limit: limit.unwrap_or(DEFAULT_GAME_EVENTS_LIMIT),
+    })?;
+
+    Ok(Json(ApiGameEvents {
+        events: events.into_iter().map(ApiGameEvent::from).collect(),
+        online_players: store.online_players(),
+    }))
+}
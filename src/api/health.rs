@@ -0,0 +1,108 @@
+use super::RconManagedState;
+use crate::{rcon::RconCircuitState, telemetry::RequestSpan};
+use rocket::{State, get, http::Status, serde::json::Json};
+use serde::Serialize;
+use std::time::Instant;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiCircuitStatus {
+    pub target: String,
+    /// The state of the circuit: `closed`, `half_open` or `open`.
+    pub state: String,
+    pub consecutive_failures: u32,
+    /// The time before the next attempt to reach the target, when the circuit is not closed.
+    pub retry_in_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiLiveness {
+    /// The health of the service, always `ok`.
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiHealth {
+    /// The health of the service: `ok` or `degraded`.
+    pub status: String,
+    pub circuits: Vec<ApiCircuitStatus>,
+}
+
+/// Return the liveness of the service.
+///
+/// The service is alive as long as it answers, regardless of the RCON server reachability.
+#[utoipa::path(
+    tag = "health",
+    context_path = "/api",
+    responses(
+        (status = 200, description = "The service is alive", body = ApiLiveness),
+    )
+)]
+#[tracing::instrument(
+    name = "GET /api/health",
+    parent = request_span.span(),
+    skip(request_span),
+    fields(
+        http.route = "/api/health",
+        app.handler = "handle_health",
+        app.component = "api",
+    )
+)]
+#[get("/health")]
+pub async fn handle_health(request_span: &RequestSpan) -> Json<ApiLiveness> {
+    Json(ApiLiveness {
+        status: String::from("ok"),
+    })
+}
+
+/// Return the readiness of the service.
+///
+/// The service is not ready while the circuit of an RCON server is open, meaning that commands
+/// fail fast without being sent to it.
+#[utoipa::path(
+    tag = "health",
+    context_path = "/api",
+    responses(
+        (status = 200, description = "The service is ready", body = ApiHealth),
+        (status = 503, description = "An RCON server is unreachable", body = ApiHealth),
+    )
+)]
+#[tracing::instrument(
+    name = "GET /api/health/ready",
+    parent = request_span.span(),
+    skip(request_span, rcon_state),
+    fields(
+        http.route = "/api/health/ready",
+        app.handler = "handle_health_ready",
+        app.component = "api",
+    )
+)]
+#[get("/health/ready")]
+pub async fn handle_health_ready(
+    request_span: &RequestSpan,
+    rcon_state: &State<RconManagedState>,
+) -> (Status, Json<ApiHealth>) {
+    let circuits = rcon_state.client.circuit_breaker().status(Instant::now());
+    let ready = circuits
+        .iter()
+        .all(|circuit| circuit.state != RconCircuitState::Open);
+
+    let health = ApiHealth {
+        status: String::from(if ready { "ok" } else { "degraded" }),
+        circuits: circuits
+            .into_iter()
+            .map(|circuit| ApiCircuitStatus {
+                target: circuit.target,
+                state: String::from(circuit.state.as_str()),
+                consecutive_failures: circuit.consecutive_failures,
+                retry_in_ms: circuit.retry_in_ms,
+            })
+            .collect(),
+    };
+
+    if ready {
+        (Status::Ok, Json(health))
+    } else {
+        (Status::ServiceUnavailable, Json(health))
+    }
+}
@@ -0,0 +1,169 @@
+use super::{ApiError, ApiErrorBody, ApiRconResponse, RconManagedState};
+use crate::{
+    rate_limit::RateLimited,
+    telemetry::RequestSpan,
+    webhooks::{
+        WEBHOOK_SIGNATURE_HEADER, WEBHOOK_TIMESTAMP_HEADER, WebhookConfiguration, WebhookError,
+        WebhookTriggers,
+    },
+};
+use rocket::{
+    Request, State, post,
+    request::{FromRequest, Outcome},
+    serde::json::Json,
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::{
+    convert::Infallible,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use utoipa::ToSchema;
+
+#[derive(Clone)]
+pub struct HooksManagedState {
+    /// The triggers of the webhooks file, missing when the webhooks are not enabled.
+    pub triggers: Option<WebhookTriggers>,
+}
+
+impl HooksManagedState {
+    /// Creates the webhook triggers state from the environment variables.
+    pub fn try_new() -> Result<Self, WebhookError> {
+        let triggers = if WebhookConfiguration::is_set() {
+            Some(WebhookTriggers::new(&WebhookConfiguration::try_new()?))
+        } else {
+            None
+        };
+
+        Ok(HooksManagedState { triggers })
+    }
+
+    /// Returns the triggers of the webhooks file, if the webhooks are enabled.
+    pub fn triggers(&self) -> Result<&WebhookTriggers, WebhookError> {
+        self.triggers
+            .as_ref()
+            .ok_or_else(|| WebhookError::Configuration {
+                cause: String::from("Environment variable 'WEBHOOKS_PATH' is not set"),
+            })
+    }
+}
+
+/// Signature of a request invoking a trigger, from the `X-Webhook-Timestamp` and
+/// `X-Webhook-Signature` headers.
+pub struct WebhookSignature {
+    pub timestamp: Option<String>,
+    pub signature: Option<String>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for WebhookSignature {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let headers = request.headers();
+
+        Outcome::Success(WebhookSignature {
+            timestamp: headers.get_one(WEBHOOK_TIMESTAMP_HEADER).map(String::from),
+            signature: headers.get_one(WEBHOOK_SIGNATURE_HEADER).map(String::from),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiHookResponse {
+    /// The name of the trigger.
+    pub trigger: String,
+    /// The responses of the commands of the trigger, in order.
+    pub responses: Vec<ApiRconResponse>,
+}
+
+/// Invoke a trigger, executing its predefined commands on the RCON server.
+///
+/// The request is signed with the secret of the trigger: the `X-Webhook-Signature` header is
+/// `sha256=` followed by the hexadecimal HMAC-SHA256 of `<timestamp>.<body>`, the timestamp being
+/// given by the `X-Webhook-Timestamp` header in seconds since the Unix epoch. The body is a JSON
+/// object giving the parameters of the commands, and may be empty.
+#[utoipa::path(
+    tag = "rcon",
+    context_path = "/api",
+    params(
+        ("name" = String, Path, description = "The name of the trigger"),
+        ("X-Webhook-Timestamp" = u64, Header, description = "The time the request was signed at, in seconds since the Unix epoch"),
+        ("X-Webhook-Signature" = String, Header, description = "The signature of the request, `sha256=<hex>`"),
+    ),
+    request_body(
+        description = "The parameters of the commands of the trigger",
+        content(
+            ("application/json"),
+        ),
+        example = json!({"version": "1.4.2"})
+    ),
+    responses(
+        (status = 200, description = "Successful response", body = ApiHookResponse),
+        (status = 400, description = "A parameter of the commands is missing or invalid", body = ApiErrorBody),
+        (status = 401, description = "The signature is missing, invalid, too old or already used", body = ApiErrorBody),
+        (status = 404, description = "The trigger does not exist", body = ApiErrorBody),
+        (status = 429, description = "Too many requests, retry after the delay given by the `Retry-After` header", body = ApiErrorBody),
+        (status = 502, description = "Unable to connect to the RCON server", body = ApiErrorBody),
+        (status = 503, description = "The webhooks are not configured, or the RCON server did not respond as expected", body = ApiErrorBody),
+        (status = 511, description = "The RCON login failed", body = ApiErrorBody),
+    )
+)]
+#[tracing::instrument(
+    name = "POST /api/hooks/<name>",
+    parent = request_span.span(),
+    skip(request_span, _rate_limited, signature, hooks_state, rcon_state, body),
+    fields(
+        http.route = "/api/hooks/<name>",
+        app.handler = "handle_hook",
+        app.component = "api",
+    )
+)]
+#[post("/hooks/<name>", data = "<body>")]
+pub async fn handle_hook(
+    request_span: &RequestSpan,
+    _rate_limited: RateLimited,
+    signature: WebhookSignature,
+    hooks_state: &State<HooksManagedState>,
+    rcon_state: &State<RconManagedState>,
+    name: &str,
+    body: String,
+) -> Result<Json<ApiHookResponse>, ApiError> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let trigger = hooks_state.triggers()?.authenticate(
+        name,
+        signature.timestamp.as_deref(),
+        signature.signature.as_deref(),
+        body.as_bytes(),
+        now,
+    )?;
+
+    let parameters = if body.trim().is_empty() {
+        Map::new()
+    } else {
+        serde_json::from_str::<Map<String, Value>>(&body).map_err(|err| {
+            WebhookError::InvalidParameters {
+                cause: format!("The body is not a JSON object: {err}"),
+            }
+        })?
+    };
+    let commands = trigger.commands(&parameters)?;
+
+    // Execute the commands in order, as sent to `POST /api/rcon`
+    let client = format!("the trigger '{}'", trigger.name);
+    let mut responses = Vec::with_capacity(commands.len());
+    for command in commands {
+        let response = rcon_state
+            .execute_command(command, false, false, &client)
+            .await?;
+        responses.push(ApiRconResponse::from(response));
+    }
+
+    Ok(Json(ApiHookResponse {
+        trigger: trigger.name.clone(),
+        responses,
+    }))
+}
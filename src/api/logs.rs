@@ -0,0 +1,161 @@
+use super::{ApiError, ApiErrorBody};
+use crate::{
+    log_tail::{LogEntry, LogFilter, LogFollower, LogTailConfiguration, LogTailError},
+    rate_limit::RateLimited,
+    telemetry::RequestSpan,
+};
+use rocket::{
+    Shutdown, State,
+    futures::{StreamExt, stream::BoxStream},
+    get,
+    response::stream::{Event, EventStream, stream},
+    tokio::{select, sync::broadcast::error::RecvError},
+};
+use serde::Serialize;
+use std::time::UNIX_EPOCH;
+use utoipa::ToSchema;
+
+/// Default number of recent log entries sent to a client before the new ones.
+pub const DEFAULT_LOG_STREAM_BACKLOG: usize = 100;
+
+#[derive(Clone)]
+pub struct LogTailManagedState {
+    /// The follower of the log file, missing when `LOG_TAIL_PATH` is not set.
+    pub follower: Option<LogFollower>,
+}
+
+impl LogTailManagedState {
+    /// Creates the log tail state from the environment variables.
+    pub fn try_new() -> Result<Self, LogTailError> {
+        let follower = match LogTailConfiguration::try_new() {
+            Ok(configuration) => Some(LogFollower::new(configuration)),
+            Err(_) if !LogTailConfiguration::is_set() => None,
+            Err(error) => return Err(error),
+        };
+
+        Ok(LogTailManagedState { follower })
+    }
+
+    /// Returns the follower of the log file, if it is configured.
+    pub fn follower(&self) -> Result<&LogFollower, LogTailError> {
+        self.follower
+            .as_ref()
+            .ok_or_else(|| LogTailError::Configuration {
+                cause: String::from("Environment variable 'LOG_TAIL_PATH' is not set"),
+            })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiLogEntry {
+    /// The position of the entry, also sent as the ID of the event.
+    pub sequence: u64,
+    /// The time printed on the line, `null` for the lines continuing the previous entry.
+    pub time: Option<String>,
+    pub thread: Option<String>,
+    /// The level of the entry: `trace`, `debug`, `info`, `warn`, `error` or `fatal`.
+    pub level: String,
+    pub message: String,
+    /// The line as printed in the log file.
+    pub line: String,
+    /// When the line was read from the log file, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+impl From<LogEntry> for ApiLogEntry {
+    fn from(entry: LogEntry) -> Self {
+        ApiLogEntry {
+            sequence: entry.sequence,
+            time: entry.time,
+            thread: entry.thread,
+            level: String::from(entry.level.as_str()),
+            message: entry.message,
+            line: entry.line,
+            timestamp: entry
+                .timestamp
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Returns the server-sent event of a log entry.
+fn log_event(entry: LogEntry) -> Event {
+    let id = entry.sequence.to_string();
+
+    Event::json(&ApiLogEntry::from(entry)).event("log").id(id)
+}
+
+/// Stream the logs of the Minecraft server.
+///
+/// The lines of the log file of the server are sent as server-sent events as they are written,
+/// after the most recent ones. The chat, the players joining and leaving and the output of the
+/// plugins are part of the logs, unlike the responses to the RCON commands.
+#[utoipa::path(
+    tag = "logs",
+    context_path = "/api",
+    params(
+        ("level" = Option<String>, Query, description = "The least severe level of the entries to send: `trace`, `debug`, `info`, `warn`, `error` or `fatal`"),
+        ("filter" = Option<String>, Query, description = "A regular expression the lines of the entries to send must match"),
+        ("backlog" = Option<usize>, Query, description = "The number of recent entries to send before the new ones, `100` by default"),
+    ),
+    responses(
+        (status = 200, description = "A stream of `log` events", content_type = "text/event-stream", body = ApiLogEntry),
+        (status = 400, description = "The level or the regular expression is invalid", body = ApiErrorBody),
+        (status = 429, description = "Too many requests, retry after the delay given by the `Retry-After` header", body = ApiErrorBody),
+        (status = 503, description = "The log tailing is not configured", body = ApiErrorBody),
+    )
+)]
+#[tracing::instrument(
+    name = "GET /api/logs/stream",
+    parent = request_span.span(),
+    skip(request_span, _rate_limited, logs_state, shutdown),
+    fields(
+        http.route = "/api/logs/stream",
+        app.handler = "handle_logs_stream",
+        app.component = "api",
+    )
+)]
+#[get("/logs/stream?<level>&<filter>&<backlog>")]
+pub async fn handle_logs_stream(
+    request_span: &RequestSpan,
+    _rate_limited: RateLimited,
+    logs_state: &State<LogTailManagedState>,
+    level: Option<&str>,
+    filter: Option<&str>,
+    backlog: Option<usize>,
+    mut shutdown: Shutdown,
+) -> Result<EventStream<BoxStream<'static, Event>>, ApiError> {
+    let follower = logs_state.follower()?;
+    let filter = LogFilter::try_new(level, filter)?;
+    let (recent, mut receiver) = follower.subscribe(backlog.unwrap_or(DEFAULT_LOG_STREAM_BACKLOG));
+
+    let events = stream! {
+        for entry in recent {
+            if filter.matches(&entry) {
+                yield log_event(entry);
+            }
+        }
+
+        loop {
+            let entry = select! {
+                entry = receiver.recv() => match entry {
+                    Ok(entry) => entry,
+                    Err(RecvError::Lagged(skipped)) => {
+                        yield Event::comment(format!("{skipped} entries skipped"));
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                },
+                _ = &mut shutdown => break,
+            };
+
+            if filter.matches(&entry) {
+                yield log_event(entry);
+            }
+        }
+    };
+
+    Ok(EventStream::from(events.boxed()))
+}
@@ -1,4 +1,26 @@
+mod client_identity;
+mod error;
+mod events;
+mod health;
+mod hooks;
+mod logs;
+mod query;
+mod queue;
 mod rcon;
 mod rcon_test;
+mod server;
+mod slack;
+mod status;
 
+pub use client_identity::*;
+pub use error::*;
+pub use events::*;
+pub use health::*;
+pub use hooks::*;
+pub use logs::*;
+pub use query::*;
+pub use queue::*;
 pub use rcon::*;
+pub use server::*;
+pub use slack::*;
+pub use status::*;
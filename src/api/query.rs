@@ -0,0 +1,109 @@
+use super::{ApiError, ApiErrorBody};
+use crate::{
+    query::{QueryBasicStat, QueryClient, QueryFullStat},
+    rate_limit::RateLimited,
+    telemetry::RequestSpan,
+};
+use rocket::{get, serde::json::Json};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiQueryStatus {
+    pub motd: String,
+    pub game_type: String,
+    pub map: String,
+    pub online_players: u32,
+    pub max_players: u32,
+    pub host_port: u16,
+    pub host_ip: String,
+    /// The game ID, only returned by the full stat.
+    pub game_id: Option<String>,
+    /// The version of the server, only returned by the full stat.
+    pub version: Option<String>,
+    /// The name and version of the server software, missing on vanilla servers.
+    pub server_mod: Option<String>,
+    pub plugins: Vec<String>,
+    /// The names of all the online players, only returned by the full stat.
+    pub players: Option<Vec<String>>,
+}
+
+impl From<QueryBasicStat> for ApiQueryStatus {
+    fn from(stat: QueryBasicStat) -> Self {
+        ApiQueryStatus {
+            motd: stat.motd,
+            game_type: stat.game_type,
+            map: stat.map,
+            online_players: stat.online_players,
+            max_players: stat.max_players,
+            host_port: stat.host_port,
+            host_ip: stat.host_ip,
+            game_id: None,
+            version: None,
+            server_mod: None,
+            plugins: Vec::new(),
+            players: None,
+        }
+    }
+}
+
+impl From<QueryFullStat> for ApiQueryStatus {
+    fn from(stat: QueryFullStat) -> Self {
+        ApiQueryStatus {
+            motd: stat.motd,
+            game_type: stat.game_type,
+            map: stat.map,
+            online_players: stat.online_players,
+            max_players: stat.max_players,
+            host_port: stat.host_port,
+            host_ip: stat.host_ip,
+            game_id: Some(stat.game_id),
+            version: Some(stat.version),
+            server_mod: stat.server_mod,
+            plugins: stat.plugins,
+            players: Some(stat.players),
+        }
+    }
+}
+
+/// Return the stats of the Minecraft server from its query port.
+///
+/// The stats are read with the UDP query protocol, enabled by `enable-query=true` in the
+/// `server.properties` file, so they do not need the RCON password.
+#[utoipa::path(
+    tag = "status",
+    context_path = "/api",
+    params(
+        ("basic" = Option<bool>, Query, description = "Set to `true` to only request the basic stat, without the version, the plugins and the player names"),
+    ),
+    responses(
+        (status = 200, description = "Successful response", body = ApiQueryStatus),
+        (status = 429, description = "Too many requests, retry after the delay given by the `Retry-After` header", body = ApiErrorBody),
+        (status = 502, description = "Unable to reach the query port of the Minecraft server", body = ApiErrorBody),
+        (status = 503, description = "The query port did not respond as expected, or did not respond at all", body = ApiErrorBody),
+    )
+)]
+#[tracing::instrument(
+    name = "GET /api/query",
+    parent = request_span.span(),
+    skip(request_span, _rate_limited, basic),
+    fields(
+        http.route = "/api/query",
+        app.handler = "handle_query",
+        app.component = "api",
+    )
+)]
+#[get("/query?<basic>")]
+pub async fn handle_query(
+    request_span: &RequestSpan,
+    _rate_limited: RateLimited,
+    basic: Option<bool>,
+) -> Result<Json<ApiQueryStatus>, ApiError> {
+    let status = if basic.unwrap_or(false) {
+        ApiQueryStatus::from(QueryClient.basic_stat().await?)
+    } else {
+        ApiQueryStatus::from(QueryClient.full_stat().await?)
+    };
+
+    Ok(Json(status))
+}
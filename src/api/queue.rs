@@ -0,0 +1,62 @@
+use super::RconManagedState;
+use crate::{rcon::RconQueueStatus, telemetry::RequestSpan};
+use rocket::{State, get, serde::json::Json};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiQueueStatus {
+    pub target: String,
+    pub running: usize,
+    pub waiting: usize,
+    pub concurrency: usize,
+    pub max_depth: usize,
+}
+
+impl From<RconQueueStatus> for ApiQueueStatus {
+    fn from(status: RconQueueStatus) -> Self {
+        ApiQueueStatus {
+            target: status.target,
+            running: status.running,
+            waiting: status.waiting,
+            concurrency: status.concurrency,
+            max_depth: status.max_depth,
+        }
+    }
+}
+
+/// Return the state of the RCON command queues.
+///
+/// A queue is returned for each RCON server a command was sent to, with the number of commands
+/// being executed and waiting for their turn.
+#[utoipa::path(
+    tag = "rcon",
+    context_path = "/api",
+    responses(
+        (status = 200, description = "Successful response", body = Vec<ApiQueueStatus>),
+    )
+)]
+#[tracing::instrument(
+    name = "GET /api/queue",
+    parent = request_span.span(),
+    skip(request_span, rcon_state),
+    fields(
+        http.route = "/api/queue",
+        app.handler = "handle_queue",
+        app.component = "api",
+    )
+)]
+#[get("/queue")]
+pub async fn handle_queue(
+    request_span: &RequestSpan,
+    rcon_state: &State<RconManagedState>,
+) -> Json<Vec<ApiQueueStatus>> {
+    Json(
+        rcon_state
+            .queue
+            .status()
+            .into_iter()
+            .map(ApiQueueStatus::from)
+            .collect(),
+    )
+}
@@ -1,39 +1,198 @@
+use super::{ApiError, ApiErrorBody, ClientIdentity};
 use crate::{
-    rcon::{RconClient, RconRequest, RconRequestType, RconResponse, RconResponseType},
+    rate_limit::RateLimited,
+    rcon::{
+        RconClient, RconCommandQueue, RconConfiguration, RconError, RconRequest, RconRequestType,
+        RconResponse, split_command,
+    },
     telemetry::RequestSpan,
+    webhooks::{WebhookDispatcher, WebhookEvent, WebhookEventKind},
+};
+use base64::{Engine, prelude::BASE64_STANDARD};
+use rocket::{
+    Request, State,
+    http::Status,
+    post,
+    request::{FromRequest, Outcome},
+    serde::json::Json,
 };
-use rocket::{State, http::Status, post, serde::json::Json};
 use serde::Serialize;
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use std::convert::Infallible;
 use utoipa::ToSchema;
 
-#[derive(Default, Clone)]
+#[derive(Clone)]
 pub struct RconManagedState {
-    pub client: Arc<Mutex<RconClient>>,
+    pub client: RconClient,
+    pub queue: RconCommandQueue,
+}
+
+impl RconManagedState {
+    /// Creates the RCON state from the environment variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `webhooks` - The dispatcher notified of the failures and of the dangerous commands.
+    pub fn try_new(webhooks: WebhookDispatcher) -> Result<Self, RconError> {
+        Ok(RconManagedState {
+            client: RconClient::try_new()?.with_webhooks(webhooks),
+            queue: RconCommandQueue::try_new()?,
+        })
+    }
+
+    /// Executes a command on the RCON server, as sent to `POST /api/rcon`.
+    ///
+    /// The command is split if needed and allowed, then executed once a command slot is free on
+    /// the target. The webhooks are notified of each dangerous command once executed, including
+    /// the ones resulting from the split.
+    ///
+    /// # Arguments
+    ///
+    /// * `rcon_command` - The command to execute.
+    /// * `idempotent` - Whether the command can be retried even if the server may have received it.
+    /// * `priority` - Whether the command skips the commands waiting for a slot.
+    /// * `client` - The description of the client, as sent to the webhooks.
+    ///
+    /// # Returns
+    ///
+    /// The response of the server, joined if the command was split.
+    pub async fn execute_command(
+        &self,
+        rcon_command: String,
+        idempotent: bool,
+        priority: bool,
+        client: &str,
+    ) -> Result<RconResponse, ApiError> {
+        let configuration = RconConfiguration::try_new().map_err(|_| Status::BadGateway)?;
+        let target = configuration.address();
+        let commands = split_command(
+            &rcon_command,
+            configuration.max_payload_size,
+            configuration.split_long_commands,
+        )?;
+
+        // Wait for a free command slot on the target
+        let permit = self.queue.acquire(&target, priority).await?;
+
+        // Execute the commands, the client takes care of the connection lifecycle and retries
+        let responses = async {
+            let mut responses = Vec::with_capacity(commands.len());
+            for command in commands {
+                let request = RconRequest::new(RconRequestType::ExecCommand, command)
+                    .with_idempotent(idempotent);
+                responses.push(self.client.execute(&request).await?);
+                self.notify_dangerous_command(&request, &configuration, client);
+            }
+
+            Ok::<_, RconError>(responses)
+        }
+        .await;
+
+        // Free the command slot faster
+        drop(permit);
+
+        let response = RconResponse::join(responses?).ok_or(Status::InternalServerError)?;
+
+        Ok(response)
+    }
+
+    /// Notifies the webhooks of an executed command, if it is a dangerous one.
+    fn notify_dangerous_command(
+        &self,
+        request: &RconRequest,
+        configuration: &RconConfiguration,
+        client: &str,
+    ) {
+        let webhooks = self.client.webhooks();
+        if !webhooks.is_dangerous_command(&request.request_payload) {
+            return;
+        }
+
+        let target = configuration.address();
+        let command = request.traced_payload(configuration.redact_command_arguments);
+        webhooks.dispatch(
+            WebhookEvent::new(
+                WebhookEventKind::DangerousCommand,
+                format!("The command '{command}' was executed on '{target}' by {client}"),
+            )
+            .with_data("target", target.as_str())
+            .with_data("command", command.as_str())
+            .with_data("verb", request.command_verb())
+            .with_data("client", client),
+        );
+    }
 }
 
 #[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct ApiRconResponse {
     pub id: i32,
     pub payload: String,
+    /// The payload as received from the RCON server, encoded in base64, only returned on demand.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw: Option<String>,
 }
 
-impl From<RconResponse> for ApiRconResponse {
-    fn from(response: RconResponse) -> Self {
+impl ApiRconResponse {
+    /// Creates the API response from an RCON response.
+    ///
+    /// # Arguments
+    ///
+    /// * `response` - The response of the RCON server.
+    /// * `raw` - Whether to include the raw payload, encoded in base64.
+    pub fn new(response: RconResponse, raw: bool) -> Self {
         ApiRconResponse {
             id: response.response_id,
+            raw: raw.then(|| BASE64_STANDARD.encode(&response.response_bytes)),
             payload: response.response_payload,
         }
     }
 }
 
+impl From<RconResponse> for ApiRconResponse {
+    fn from(response: RconResponse) -> Self {
+        ApiRconResponse::new(response, false)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiRconValidation {
+    pub valid: bool,
+    pub size: usize,
+    pub max_size: usize,
+    /// The commands the command would be sent as, split if needed and allowed.
+    pub commands: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Idempotency of the command, as declared by the client with the `X-Rcon-Idempotent` header.
+///
+/// Idempotent commands are retried after a failure even if the server may have received them.
+pub struct RconIdempotency(pub bool);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RconIdempotency {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let idempotent = request
+            .headers()
+            .get_one("X-Rcon-Idempotent")
+            .is_some_and(|value| value.trim().eq_ignore_ascii_case("true"));
+
+        Outcome::Success(RconIdempotency(idempotent))
+    }
+}
+
 /// Execute a command on the RCON server and return the response.
 ///
 /// The command is sent to the RCON server and the response is returned.
 #[utoipa::path(
     tag = "rcon",
     context_path = "/api", 
+    params(
+        ("X-Rcon-Idempotent" = Option<bool>, Header, description = "Set to `true` to allow the command to be retried after a failure even if the server may have received it"),
+        ("raw" = Option<bool>, Query, description = "Set to `true` to also return the payload as received from the RCON server, encoded in base64"),
+    ),
     request_body(
         description = "The command to execute on the RCON server",
         content(
@@ -43,78 +202,108 @@ impl From<RconResponse> for ApiRconResponse {
     ),
     responses(
         (status = 200, description = "Successful response", description = "Successful response", body = ApiRconResponse),
-        (status = 500, description = "Internal error"),
-        (status = 502, description = "Unable to connect to the RCON server"),
-        (status = 503, description = "The RCON server did not respond as expected"),
-        (status = 511, description = "The RCON login failed"),
+        (status = 429, description = "Too many requests, retry after the delay given by the `Retry-After` header", body = ApiErrorBody),
+        (status = 400, description = "The command cannot be sent to the server", body = ApiErrorBody),
+        (status = 413, description = "The command is too long for a single request and cannot be split", body = ApiErrorBody),
+        (status = 500, description = "Internal error", body = ApiErrorBody),
+        (status = 502, description = "Unable to connect to the RCON server", body = ApiErrorBody),
+        (status = 503, description = "The RCON server did not respond as expected, or the command queue is full", body = ApiErrorBody),
+        (status = 511, description = "The RCON login failed", body = ApiErrorBody),
     )
 )]
 #[tracing::instrument(
     name = "POST /api/rcon",
     parent = request_span.span(),
-    skip(request_span, rcon_state, rcon_command),
+    skip(request_span, _rate_limited, client_identity, idempotency, rcon_state, rcon_command, raw),
     fields(
         http.route = "/api/rcon",
         app.handler = "handle_rcon",
         app.component = "api",
     )
 )]
-#[post("/rcon", data = "<rcon_command>")]
+#[post("/rcon?<raw>", data = "<rcon_command>")]
 pub async fn handle_rcon(
     request_span: &RequestSpan,
+    _rate_limited: RateLimited,
+    client_identity: ClientIdentity,
+    idempotency: RconIdempotency,
     rcon_state: &State<RconManagedState>,
     rcon_command: String,
-) -> Result<Json<ApiRconResponse>, Status> {
-    let rcon_client = rcon_state.client.lock().await;
-
-    // Get a new connection
-    let mut connection = rcon_client
-        .get_connection()
-        .await
-        .map_err(|_| Status::BadGateway)?;
-
-    let request = RconRequest::new(RconRequestType::ExecCommand, rcon_command);
-
-    // Wrap all calls in a closure to ensure the connection is disconnected regardless of the result.
-    let response = {
-        connection
-            .login()
-            .await
-            .map(|login_result| {
-                if !login_result {
-                    // The login is unsuccessful.
-                    Err(Status::NetworkAuthenticationRequired)
-                } else {
-                    Ok(())
-                }
-            })
-            .map_err(|_| Status::ServiceUnavailable)??;
-
-        let response = connection
-            .request(&request)
-            .await
-            .map(|response| {
-                if response.response_type == RconResponseType::AuthResponse {
-                    // This would happen only if the auth changes between the login and the command.
-                    Err(Status::NetworkAuthenticationRequired)
-                } else {
-                    Ok(response)
-                }
-            })
-            .map_err(|_| Status::ServiceUnavailable)??;
+    raw: Option<bool>,
+) -> Result<Json<ApiRconResponse>, ApiError> {
+    let priority = matches!(
+        &client_identity,
+        ClientIdentity::Token(token) if rcon_state.queue.is_priority_token(token)
+    );
 
-        Ok(response)
-    };
+    let response = rcon_state
+        .execute_command(
+            rcon_command,
+            idempotency.0,
+            priority,
+            &client_identity.redacted(),
+        )
+        .await?;
 
-    // Disconnect
-    connection
-        .disconnect()
-        .await
-        .map_err(|_| Status::InternalServerError)?;
+    Ok(Json(ApiRconResponse::new(response, raw.unwrap_or(false))))
+}
+
+/// Check whether a command can be sent to the RCON server, without sending it.
+///
+/// The command is checked against the maximum size of a request, and split in several commands
+/// if needed and allowed.
+#[utoipa::path(
+    tag = "rcon",
+    context_path = "/api",
+    request_body(
+        description = "The command to check",
+        content(
+            ("text/plain"),
+        ),
+        example = "say Hello"
+    ),
+    responses(
+        (status = 200, description = "Successful response", body = ApiRconValidation),
+        (status = 429, description = "Too many requests, retry after the delay given by the `Retry-After` header", body = ApiErrorBody),
+        (status = 502, description = "Invalid RCON configuration", body = ApiErrorBody),
+    )
+)]
+#[tracing::instrument(
+    name = "POST /api/rcon/validate",
+    parent = request_span.span(),
+    skip(request_span, _rate_limited, rcon_command),
+    fields(
+        http.route = "/api/rcon/validate",
+        app.handler = "handle_rcon_validate",
+        app.component = "api",
+    )
+)]
+#[post("/rcon/validate", data = "<rcon_command>")]
+pub async fn handle_rcon_validate(
+    request_span: &RequestSpan,
+    _rate_limited: RateLimited,
+    rcon_command: String,
+) -> Result<Json<ApiRconValidation>, ApiError> {
+    let configuration = RconConfiguration::try_new().map_err(|_| Status::BadGateway)?;
+    let max_size = configuration.max_payload_size;
 
-    // Drop the lock faster
-    drop(rcon_client);
+    let validation = match split_command(&rcon_command, max_size, configuration.split_long_commands)
+    {
+        Ok(commands) => ApiRconValidation {
+            valid: true,
+            size: rcon_command.len(),
+            max_size,
+            commands,
+            error: None,
+        },
+        Err(error) => ApiRconValidation {
+            valid: false,
+            size: rcon_command.len(),
+            max_size,
+            commands: Vec::new(),
+            error: Some(error.to_string()),
+        },
+    };
 
-    // Return the response
-    response.map(|response| Json(response.into()))
+    Ok(Json(validation))
 }
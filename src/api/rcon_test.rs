@@ -1,7 +1,57 @@
 #[cfg(test)]
 mod tests {
-    use crate::api::ApiRconResponse;
+    use crate::api::{ApiRconResponse, RconManagedState};
     use crate::rcon::{RconResponse, RconResponseType};
+    use crate::webhooks::{WebhookConfiguration, WebhookDispatcher, WebhookEndpoint};
+    use serde_json::Value;
+    use serial_test::serial;
+    use std::{
+        collections::{BTreeMap, HashSet},
+        time::Duration,
+    };
+    use temp_env::async_with_vars;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        sync::mpsc::{UnboundedReceiver, unbounded_channel},
+        time::timeout,
+    };
+
+    /// Starts an HTTP endpoint receiving the webhook events.
+    ///
+    /// # Returns
+    ///
+    /// The URL of the endpoint and the receiver of the events it got.
+    async fn webhook_endpoint() -> (String, UnboundedReceiver<Value>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let (sender, receiver) = unbounded_channel();
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buffer = [0; 4096];
+                // The body is a JSON object, complete once its braces are balanced.
+                let body = loop {
+                    let read = stream.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..read]);
+                    let text = String::from_utf8_lossy(&request);
+                    if let Some((_, body)) = text.split_once("\r\n\r\n")
+                        && let Ok(body) = serde_json::from_str::<Value>(body)
+                    {
+                        break body;
+                    }
+                };
+                stream
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                    .await
+                    .ok();
+                sender.send(body).ok();
+            }
+        });
+
+        (url, receiver)
+    }
 
     #[test]
     fn try_from_rcon_response_to_api_rcon_response() {
@@ -9,11 +59,80 @@ mod tests {
             response_id: 1,
             response_type: RconResponseType::ResponseValue,
             response_payload: String::from("Herobrine"),
+            response_bytes: b"Herobrine".to_vec(),
         };
 
         let response: ApiRconResponse = response_mock.into();
 
         assert_eq!(response.id, 1);
         assert_eq!(response.payload, "Herobrine");
+        assert_eq!(response.raw, None);
+    }
+
+    #[test]
+    fn new_api_rcon_response_with_raw_payload() {
+        let response_mock = RconResponse {
+            response_id: 1,
+            response_type: RconResponseType::ResponseValue,
+            response_payload: String::from("Caf\u{fffd}"),
+            response_bytes: vec![b'C', b'a', b'f', 0xe9],
+        };
+
+        let response = ApiRconResponse::new(response_mock, true);
+
+        assert_eq!(response.payload, "Caf\u{fffd}");
+        assert_eq!(response.raw.as_deref(), Some("Q2Fm6Q=="));
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn execute_split_dangerous_command() {
+        let (url, mut received) = webhook_endpoint().await;
+
+        async_with_vars(
+            [
+                ("RCON_TRANSPORT", Some("process")),
+                ("RCON_PROCESS_COMMAND", Some("cat")),
+                ("RCON_PROCESS_RESPONSE_IDLE", Some("100")),
+                ("RCON_SPLIT_LONG_COMMANDS", Some("true")),
+                ("RCON_REDACT_COMMAND_ARGUMENTS", Some("false")),
+            ],
+            async {
+                let webhooks = WebhookDispatcher::new(WebhookConfiguration {
+                    endpoints: vec![WebhookEndpoint {
+                        name: String::from("audit"),
+                        url: url.clone(),
+                        secret: None,
+                        events: vec![String::from("rcon.dangerous_command")],
+                        template: None,
+                        headers: BTreeMap::new(),
+                    }],
+                    timeout: 1000,
+                    max_attempts: 1,
+                    base_delay: 10,
+                    max_delay: 10,
+                    dead_letter_path: None,
+                    dangerous_commands: HashSet::from([String::from("op")]),
+                    triggers: Vec::new(),
+                    trigger_tolerance: 300,
+                })
+                .unwrap();
+                let rcon_state = RconManagedState::try_new(webhooks).unwrap();
+
+                // The dangerous command is hidden behind a harmless one.
+                rcon_state
+                    .execute_command(String::from("list\nop Mallory"), false, false, "test")
+                    .await
+                    .unwrap();
+
+                let event = timeout(Duration::from_secs(5), received.recv())
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(event["data"]["command"], "op Mallory");
+                assert_eq!(event["data"]["verb"], "op");
+            },
+        )
+        .await;
     }
 }
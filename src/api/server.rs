@@ -0,0 +1,258 @@
+use super::{ApiError, ApiErrorBody};
+use crate::{
+    rate_limit::RateLimited,
+    rcon::RconClient,
+    server::{ServerConfiguration, ServerError, ServerLogLine, ServerStatus, ServerSupervisor},
+    telemetry::RequestSpan,
+};
+use rocket::{State, get, post, serde::json::Json};
+use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
+use utoipa::ToSchema;
+
+#[derive(Clone)]
+pub struct ServerManagedState {
+    /// The supervisor of the Minecraft server, missing when `SERVER_COMMAND` is not set.
+    pub supervisor: Option<ServerSupervisor>,
+}
+
+impl ServerManagedState {
+    /// Creates the server state from the environment variables.
+    ///
+    /// # Parameters
+    ///
+    /// - `rcon`: The RCON client used to stop the server.
+    pub fn try_new(rcon: RconClient) -> Result<Self, ServerError> {
+        let supervisor = match ServerConfiguration::try_new() {
+            Ok(configuration) => Some(ServerSupervisor::new(configuration, rcon)),
+            Err(_) if !ServerConfiguration::is_set() => None,
+            Err(error) => return Err(error),
+        };
+
+        Ok(ServerManagedState { supervisor })
+    }
+
+    /// Returns the supervisor of the Minecraft server, if it is configured.
+    pub fn supervisor(&self) -> Result<&ServerSupervisor, ServerError> {
+        self.supervisor
+            .as_ref()
+            .ok_or_else(|| ServerError::Configuration {
+                cause: String::from("Environment variable 'SERVER_COMMAND' is not set"),
+            })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiServerLogLine {
+    /// The stream the line was printed on: `stdout` or `stderr`.
+    pub stream: String,
+    pub line: String,
+    /// When the line was printed, in milliseconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+impl From<ServerLogLine> for ApiServerLogLine {
+    fn from(line: ServerLogLine) -> Self {
+        ApiServerLogLine {
+            stream: String::from(line.stream.as_str()),
+            line: line.line,
+            timestamp: unix_millis(line.timestamp),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiServerState {
+    /// The state of the server: `stopped`, `starting`, `running`, `stopping` or `crashed`.
+    pub state: String,
+    pub pid: Option<u32>,
+    /// When the server process was started, in milliseconds since the Unix epoch.
+    pub started_at: Option<u64>,
+    /// The startup time reported by the server once ready, in milliseconds.
+    pub startup_time_ms: Option<u64>,
+    /// The number of restarts after a crash since the server was started.
+    pub restarts: u32,
+    /// How the server process last exited, e.g. `exit status: 1`.
+    pub last_exit: Option<String>,
+    /// When the crashed server is restarted, in milliseconds since the Unix epoch.
+    pub next_restart_at: Option<u64>,
+    /// The last lines printed by the server, only returned on demand.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logs: Option<Vec<ApiServerLogLine>>,
+}
+
+impl From<ServerStatus> for ApiServerState {
+    fn from(status: ServerStatus) -> Self {
+        ApiServerState {
+            state: String::from(status.state.as_str()),
+            pid: status.pid,
+            started_at: status.started_at.map(unix_millis),
+            startup_time_ms: status
+                .startup_time
+                .map(|startup_time| startup_time.as_millis() as u64),
+            restarts: status.restarts,
+            last_exit: status.last_exit,
+            next_restart_at: status.next_restart_at.map(unix_millis),
+            logs: None,
+        }
+    }
+}
+
+/// Returns the number of milliseconds elapsed since the Unix epoch.
+fn unix_millis(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Start the Minecraft server.
+///
+/// The server is starting once the request returns, it is running once it prints its `Done`
+/// line.
+#[utoipa::path(
+    tag = "server",
+    context_path = "/api",
+    responses(
+        (status = 200, description = "The server is starting", body = ApiServerState),
+        (status = 409, description = "The server is already running", body = ApiErrorBody),
+        (status = 429, description = "Too many requests, retry after the delay given by the `Retry-After` header", body = ApiErrorBody),
+        (status = 500, description = "The server process could not be started", body = ApiErrorBody),
+        (status = 503, description = "The server supervisor is not configured", body = ApiErrorBody),
+    )
+)]
+#[tracing::instrument(
+    name = "POST /api/server/start",
+    parent = request_span.span(),
+    skip(request_span, _rate_limited, server_state),
+    fields(
+        http.route = "/api/server/start",
+        app.handler = "handle_server_start",
+        app.component = "api",
+    )
+)]
+#[post("/server/start")]
+pub async fn handle_server_start(
+    request_span: &RequestSpan,
+    _rate_limited: RateLimited,
+    server_state: &State<ServerManagedState>,
+) -> Result<Json<ApiServerState>, ApiError> {
+    let status = server_state.supervisor()?.start().await?;
+
+    Ok(Json(ApiServerState::from(status)))
+}
+
+/// Stop the Minecraft server.
+///
+/// The `stop` command is sent with the RCON client so the server saves the worlds, then the
+/// process is terminated if it does not stop in time. The request returns once the server is
+/// stopped.
+#[utoipa::path(
+    tag = "server",
+    context_path = "/api",
+    responses(
+        (status = 200, description = "The server is stopped", body = ApiServerState),
+        (status = 409, description = "The server is not running", body = ApiErrorBody),
+        (status = 429, description = "Too many requests, retry after the delay given by the `Retry-After` header", body = ApiErrorBody),
+        (status = 500, description = "The server process did not exit", body = ApiErrorBody),
+        (status = 503, description = "The server supervisor is not configured", body = ApiErrorBody),
+    )
+)]
+#[tracing::instrument(
+    name = "POST /api/server/stop",
+    parent = request_span.span(),
+    skip(request_span, _rate_limited, server_state),
+    fields(
+        http.route = "/api/server/stop",
+        app.handler = "handle_server_stop",
+        app.component = "api",
+    )
+)]
+#[post("/server/stop")]
+pub async fn handle_server_stop(
+    request_span: &RequestSpan,
+    _rate_limited: RateLimited,
+    server_state: &State<ServerManagedState>,
+) -> Result<Json<ApiServerState>, ApiError> {
+    let status = server_state.supervisor()?.stop().await?;
+
+    Ok(Json(ApiServerState::from(status)))
+}
+
+/// Restart the Minecraft server.
+///
+/// The server is stopped as with `POST /api/server/stop` if it is running, then started again.
+#[utoipa::path(
+    tag = "server",
+    context_path = "/api",
+    responses(
+        (status = 200, description = "The server is starting", body = ApiServerState),
+        (status = 429, description = "Too many requests, retry after the delay given by the `Retry-After` header", body = ApiErrorBody),
+        (status = 500, description = "The server process could not be stopped or started", body = ApiErrorBody),
+        (status = 503, description = "The server supervisor is not configured", body = ApiErrorBody),
+    )
+)]
+#[tracing::instrument(
+    name = "POST /api/server/restart",
+    parent = request_span.span(),
+    skip(request_span, _rate_limited, server_state),
+    fields(
+        http.route = "/api/server/restart",
+        app.handler = "handle_server_restart",
+        app.component = "api",
+    )
+)]
+#[post("/server/restart")]
+pub async fn handle_server_restart(
+    request_span: &RequestSpan,
+    _rate_limited: RateLimited,
+    server_state: &State<ServerManagedState>,
+) -> Result<Json<ApiServerState>, ApiError> {
+    let status = server_state.supervisor()?.restart().await?;
+
+    Ok(Json(ApiServerState::from(status)))
+}
+
+/// Return the state of the Minecraft server.
+#[utoipa::path(
+    tag = "server",
+    context_path = "/api",
+    params(
+        ("logs" = Option<usize>, Query, description = "The number of lines of the server output to return, none by default"),
+    ),
+    responses(
+        (status = 200, description = "Successful response", body = ApiServerState),
+        (status = 429, description = "Too many requests, retry after the delay given by the `Retry-After` header", body = ApiErrorBody),
+        (status = 503, description = "The server supervisor is not configured", body = ApiErrorBody),
+    )
+)]
+#[tracing::instrument(
+    name = "GET /api/server/state",
+    parent = request_span.span(),
+    skip(request_span, _rate_limited, server_state, logs),
+    fields(
+        http.route = "/api/server/state",
+        app.handler = "handle_server_state",
+        app.component = "api",
+    )
+)]
+#[get("/server/state?<logs>")]
+pub async fn handle_server_state(
+    request_span: &RequestSpan,
+    _rate_limited: RateLimited,
+    server_state: &State<ServerManagedState>,
+    logs: Option<usize>,
+) -> Result<Json<ApiServerState>, ApiError> {
+    let supervisor = server_state.supervisor()?;
+
+    let mut state = ApiServerState::from(supervisor.status());
+    state.logs = logs.map(|count| {
+        supervisor
+            .logs()
+            .tail(count)
+            .into_iter()
+            .map(ApiServerLogLine::from)
+            .collect()
+    });
+
+    Ok(Json(state))
+}
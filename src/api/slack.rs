@@ -0,0 +1,186 @@
+use super::{ApiError, ApiErrorBody, RconManagedState};
+use crate::{
+    rate_limit::RateLimited,
+    slack::{
+        SLACK_SIGNATURE_HEADER, SLACK_TIMESTAMP_HEADER, SlackCommand, SlackConfiguration,
+        SlackError, SlackResponseType, format_slack_text, verify_slack_request,
+    },
+    telemetry::RequestSpan,
+};
+use rocket::{
+    Request, State, post,
+    request::{FromRequest, Outcome},
+    serde::json::Json,
+};
+use serde::Serialize;
+use std::{
+    convert::Infallible,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tracing::warn;
+use utoipa::ToSchema;
+
+#[derive(Clone)]
+pub struct SlackManagedState {
+    /// The configuration of the Slack app, missing when the Slack command is not enabled.
+    pub configuration: Option<SlackConfiguration>,
+}
+
+impl SlackManagedState {
+    /// Creates the Slack state from the environment variables.
+    pub fn try_new() -> Result<Self, SlackError> {
+        let configuration = if SlackConfiguration::is_set() {
+            Some(SlackConfiguration::try_new()?)
+        } else {
+            None
+        };
+
+        Ok(SlackManagedState { configuration })
+    }
+
+    /// Returns the configuration of the Slack app, if the Slack command is enabled.
+    pub fn configuration(&self) -> Result<&SlackConfiguration, SlackError> {
+        self.configuration
+            .as_ref()
+            .ok_or_else(|| SlackError::Configuration {
+                cause: String::from("Environment variable 'SLACK_SIGNING_SECRET' is not set"),
+            })
+    }
+}
+
+/// Signature of a request sent by Slack, from the `X-Slack-Request-Timestamp` and
+/// `X-Slack-Signature` headers.
+pub struct SlackSignature {
+    pub timestamp: Option<String>,
+    pub signature: Option<String>,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for SlackSignature {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let headers = request.headers();
+
+        Outcome::Success(SlackSignature {
+            timestamp: headers.get_one(SLACK_TIMESTAMP_HEADER).map(String::from),
+            signature: headers.get_one(SLACK_SIGNATURE_HEADER).map(String::from),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiSlackReply {
+    /// Either `ephemeral`, only shown to the user who typed the command, or `in_channel`.
+    pub response_type: String,
+    /// The reply, in the Slack formatting.
+    pub text: String,
+}
+
+impl ApiSlackReply {
+    fn new(response_type: SlackResponseType, text: String) -> Self {
+        ApiSlackReply {
+            response_type: String::from(response_type.as_str()),
+            text,
+        }
+    }
+}
+
+/// Execute a command sent by a Slack slash command, e.g. `/mc kick Steve griefing`.
+///
+/// The request is signed with the signing secret of the Slack app: the `X-Slack-Signature` header
+/// is `v0=` followed by the hexadecimal HMAC-SHA256 of `v0:<timestamp>:<body>`, the timestamp
+/// being given by the `X-Slack-Request-Timestamp` header in seconds since the Unix epoch. Only
+/// the commands allowed by the `SLACK_ALLOWED_COMMANDS` environment variable are executed.
+///
+/// The errors of the command are replied to the user who typed it, as Slack only shows the
+/// replies of successful requests.
+#[utoipa::path(
+    tag = "rcon",
+    context_path = "/api",
+    params(
+        ("X-Slack-Request-Timestamp" = u64, Header, description = "The time the request was signed at, in seconds since the Unix epoch"),
+        ("X-Slack-Signature" = String, Header, description = "The signature of the request, `v0=<hex>`"),
+    ),
+    request_body(
+        description = "The slash command, as sent by Slack",
+        content(
+            ("application/x-www-form-urlencoded"),
+        ),
+        example = "command=%2Fmc&text=kick+Steve+griefing&user_id=U2147483697&user_name=alex"
+    ),
+    responses(
+        (status = 200, description = "Successful response, also replying the errors of the command", body = ApiSlackReply),
+        (status = 400, description = "The body is not a slash command", body = ApiErrorBody),
+        (status = 401, description = "The signature is missing, invalid or too old", body = ApiErrorBody),
+        (status = 429, description = "Too many requests, retry after the delay given by the `Retry-After` header", body = ApiErrorBody),
+        (status = 503, description = "The Slack command is not configured", body = ApiErrorBody),
+    )
+)]
+#[tracing::instrument(
+    name = "POST /api/slack/command",
+    parent = request_span.span(),
+    skip(request_span, _rate_limited, signature, slack_state, rcon_state, body),
+    fields(
+        http.route = "/api/slack/command",
+        app.handler = "handle_slack_command",
+        app.component = "api",
+    )
+)]
+#[post("/slack/command", data = "<body>")]
+pub async fn handle_slack_command(
+    request_span: &RequestSpan,
+    _rate_limited: RateLimited,
+    signature: SlackSignature,
+    slack_state: &State<SlackManagedState>,
+    rcon_state: &State<RconManagedState>,
+    body: String,
+) -> Result<Json<ApiSlackReply>, ApiError> {
+    let configuration = slack_state.configuration()?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    verify_slack_request(
+        configuration.signing_secret.expose(),
+        signature.timestamp.as_deref(),
+        signature.signature.as_deref(),
+        body.as_bytes(),
+        now,
+        configuration.timestamp_tolerance,
+    )?;
+
+    let command = SlackCommand::parse(&body)?;
+    let rcon_command = match command.rcon_command(&configuration.allowed_commands) {
+        Ok(rcon_command) => rcon_command,
+        Err(SlackError::InvalidCommand { cause }) => {
+            return Ok(Json(ApiSlackReply::new(
+                SlackResponseType::Ephemeral,
+                cause,
+            )));
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let client = format!(
+        "the Slack user '{}' ({})",
+        command.user_name, command.user_id
+    );
+    match rcon_state
+        .execute_command(rcon_command, false, false, &client)
+        .await
+    {
+        Ok(response) => Ok(Json(ApiSlackReply::new(
+            configuration.response_type,
+            format_slack_text(&response.response_payload),
+        ))),
+        Err(err) => {
+            warn!("Unable to execute the Slack command: {}", err.message);
+
+            Ok(Json(ApiSlackReply::new(
+                SlackResponseType::Ephemeral,
+                format!("The command failed: {}", err.message),
+            )))
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use super::{ApiError, ApiErrorBody};
+use crate::{
+    rate_limit::RateLimited,
+    slp::{Motd, MotdSegment, SlpClient, SlpPlayer, SlpStatus},
+    telemetry::RequestSpan,
+};
+use rocket::{get, serde::json::Json};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiServerVersion {
+    pub name: String,
+    pub protocol: i32,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiServerPlayer {
+    pub name: String,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiServerPlayers {
+    pub max: i64,
+    pub online: i64,
+    /// Some of the online players, as chosen by the server.
+    pub sample: Vec<ApiServerPlayer>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiMotdSegment {
+    pub text: String,
+    /// Either a color name, e.g. `dark_red`, or a `#RRGGBB` hex color.
+    pub color: Option<String>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underlined: bool,
+    pub strikethrough: bool,
+    pub obfuscated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiMotd {
+    /// The MOTD without formatting.
+    pub text: String,
+    pub segments: Vec<ApiMotdSegment>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiServerStatus {
+    pub version: ApiServerVersion,
+    pub players: ApiServerPlayers,
+    pub motd: ApiMotd,
+    /// The icon of the server, as a `data:image/png;base64,` URL.
+    pub favicon: Option<String>,
+    /// The round trip time of a ping, missing if the server did not answer it.
+    pub latency_ms: Option<u64>,
+}
+
+impl From<SlpPlayer> for ApiServerPlayer {
+    fn from(player: SlpPlayer) -> Self {
+        ApiServerPlayer {
+            name: player.name,
+            id: player.id,
+        }
+    }
+}
+
+impl From<MotdSegment> for ApiMotdSegment {
+    fn from(segment: MotdSegment) -> Self {
+        ApiMotdSegment {
+            text: segment.text,
+            color: segment.color,
+            bold: segment.bold,
+            italic: segment.italic,
+            underlined: segment.underlined,
+            strikethrough: segment.strikethrough,
+            obfuscated: segment.obfuscated,
+        }
+    }
+}
+
+impl From<Motd> for ApiMotd {
+    fn from(motd: Motd) -> Self {
+        ApiMotd {
+            text: motd.text,
+            segments: motd
+                .segments
+                .into_iter()
+                .map(ApiMotdSegment::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<SlpStatus> for ApiServerStatus {
+    fn from(status: SlpStatus) -> Self {
+        ApiServerStatus {
+            version: ApiServerVersion {
+                name: status.version.name,
+                protocol: status.version.protocol,
+            },
+            players: ApiServerPlayers {
+                max: status.players.max,
+                online: status.players.online,
+                sample: status
+                    .players
+                    .sample
+                    .into_iter()
+                    .map(ApiServerPlayer::from)
+                    .collect(),
+            },
+            motd: ApiMotd::from(status.motd),
+            favicon: status.favicon,
+            latency_ms: status.latency_ms,
+        }
+    }
+}
+
+/// Return the status of the Minecraft server.
+///
+/// The status is read with the Server List Ping protocol, as done by the multiplayer screen of the
+/// game, so it does not need RCON to be enabled.
+#[utoipa::path(
+    tag = "status",
+    context_path = "/api",
+    responses(
+        (status = 200, description = "Successful response", body = ApiServerStatus),
+        (status = 429, description = "Too many requests, retry after the delay given by the `Retry-After` header", body = ApiErrorBody),
+        (status = 502, description = "Unable to connect to the Minecraft server", body = ApiErrorBody),
+        (status = 503, description = "The Minecraft server did not respond as expected", body = ApiErrorBody),
+    )
+)]
+#[tracing::instrument(
+    name = "GET /api/status",
+    parent = request_span.span(),
+    skip(request_span, _rate_limited),
+    fields(
+        http.route = "/api/status",
+        app.handler = "handle_status",
+        app.component = "api",
+    )
+)]
+#[get("/status")]
+pub async fn handle_status(
+    request_span: &RequestSpan,
+    _rate_limited: RateLimited,
+) -> Result<Json<ApiServerStatus>, ApiError> {
+    let status = SlpClient.status().await?;
+
+    Ok(Json(ApiServerStatus::from(status)))
+}
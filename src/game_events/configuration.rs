@@ -0,0 +1,38 @@
+use super::GameEventError;
+use std::env;
+use tracing::debug;
+
+/// Default number of game events kept in memory.
+pub const DEFAULT_GAME_EVENTS_BUFFER_SIZE: usize = 1000;
+
+#[derive(Debug, Clone)]
+pub struct GameEventsConfiguration {
+    /// Number of game events kept in memory, the oldest ones being dropped.
+    pub buffer_size: usize,
+}
+
+impl GameEventsConfiguration {
+    /// Returns the configuration from environment variables.
+    ///
+    /// # Returns:
+    ///
+    /// The configuration.
+    pub fn try_new() -> Result<GameEventsConfiguration, GameEventError> {
+        debug!("Attempting to create game events configuration from environment variables...");
+
+        let buffer_size = env::var("GAME_EVENTS_BUFFER_SIZE")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<usize>())
+            .unwrap_or(Ok(DEFAULT_GAME_EVENTS_BUFFER_SIZE))
+            .ok()
+            .filter(|buffer_size| *buffer_size > 0)
+            .ok_or_else(|| GameEventError::Configuration {
+                cause: String::from(
+                    "Environment variable 'GAME_EVENTS_BUFFER_SIZE' is not a valid positive number",
+                ),
+            })?;
+
+        Ok(GameEventsConfiguration { buffer_size })
+    }
+}
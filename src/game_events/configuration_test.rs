@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod tests {
+    use crate::game_events::{
+        DEFAULT_GAME_EVENTS_BUFFER_SIZE, GameEventError, GameEventsConfiguration,
+    };
+    use serial_test::serial;
+    use temp_env::with_vars;
+    use test_case::test_case;
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration() {
+        with_vars([("GAME_EVENTS_BUFFER_SIZE", None::<&str>)], || {
+            let configuration = GameEventsConfiguration::try_new().unwrap();
+            assert_eq!(configuration.buffer_size, DEFAULT_GAME_EVENTS_BUFFER_SIZE);
+        });
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_custom() {
+        with_vars([("GAME_EVENTS_BUFFER_SIZE", Some("50"))], || {
+            let configuration = GameEventsConfiguration::try_new().unwrap();
+            assert_eq!(configuration.buffer_size, 50);
+        });
+    }
+
+    #[test_case("0"; "empty buffer")]
+    #[test_case("many"; "invalid buffer")]
+    #[serial(rcon)]
+    fn test_get_configuration_invalid(value: &str) {
+        with_vars([("GAME_EVENTS_BUFFER_SIZE", Some(value))], || {
+            let result = GameEventsConfiguration::try_new();
+            assert!(matches!(result, Err(GameEventError::Configuration { .. })));
+        });
+    }
+}
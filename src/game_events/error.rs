@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum GameEventError {
+    #[error("Invalid game events configuration: {cause}")]
+    Configuration { cause: String },
+    #[error("Invalid game events query: {cause}")]
+    InvalidQuery { cause: String },
+}
@@ -0,0 +1,98 @@
+use std::time::{Duration, SystemTime};
+
+/// Event of the game recognized in the server logs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameEventKind {
+    PlayerJoined {
+        player: String,
+        /// The UUID of the player, from the line of the authenticator.
+        uuid: Option<String>,
+        /// The address the player connected from, from the login line.
+        address: Option<String>,
+    },
+    PlayerLeft {
+        player: String,
+        /// The reason of the disconnection, from the lost connection line.
+        reason: Option<String>,
+    },
+    Chat {
+        player: String,
+        message: String,
+    },
+    Death {
+        player: String,
+        /// The death message, e.g. `Steve was slain by Zombie`.
+        message: String,
+    },
+    Advancement {
+        player: String,
+        advancement: String,
+        /// The frame of the advancement: `task`, `goal` or `challenge`.
+        frame: String,
+    },
+    ServerStarted {
+        /// The startup time reported by the server.
+        startup_time: Option<Duration>,
+    },
+    ServerStopping,
+    Lag {
+        /// How late the server is.
+        behind: Duration,
+        /// The number of ticks the server skips to catch up.
+        ticks: u64,
+    },
+}
+
+/// Names of the kinds of game events, as used in the API and the metrics.
+pub const GAME_EVENT_KINDS: [&str; 8] = [
+    "player_joined",
+    "player_left",
+    "chat",
+    "death",
+    "advancement",
+    "server_started",
+    "server_stopping",
+    "lag",
+];
+
+impl GameEventKind {
+    /// Returns the name of the kind of event, as used in the API and the metrics.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GameEventKind::PlayerJoined { .. } => "player_joined",
+            GameEventKind::PlayerLeft { .. } => "player_left",
+            GameEventKind::Chat { .. } => "chat",
+            GameEventKind::Death { .. } => "death",
+            GameEventKind::Advancement { .. } => "advancement",
+            GameEventKind::ServerStarted { .. } => "server_started",
+            GameEventKind::ServerStopping => "server_stopping",
+            GameEventKind::Lag { .. } => "lag",
+        }
+    }
+
+    /// Returns the player the event is about, if any.
+    pub fn player(&self) -> Option<&str> {
+        match self {
+            GameEventKind::PlayerJoined { player, .. }
+            | GameEventKind::PlayerLeft { player, .. }
+            | GameEventKind::Chat { player, .. }
+            | GameEventKind::Death { player, .. }
+            | GameEventKind::Advancement { player, .. } => Some(player),
+            GameEventKind::ServerStarted { .. }
+            | GameEventKind::ServerStopping
+            | GameEventKind::Lag { .. } => None,
+        }
+    }
+}
+
+/// Game event, as stored and returned by the API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameEvent {
+    /// Position of the event since the backend started, increasing by one per event.
+    pub id: u64,
+    pub kind: GameEventKind,
+    /// The time printed on the log line, e.g. `12:34:56`.
+    pub time: Option<String>,
+    /// When the log line was read.
+    pub timestamp: SystemTime,
+}
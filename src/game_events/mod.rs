@@ -0,0 +1,14 @@
+pub mod configuration;
+pub mod configuration_test;
+pub mod error;
+pub mod event;
+pub mod parser;
+pub mod parser_test;
+pub mod store;
+pub mod store_test;
+
+pub use configuration::*;
+pub use error::*;
+pub use event::*;
+pub use parser::*;
+pub use store::*;
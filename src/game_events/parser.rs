@@ -0,0 +1,170 @@
+use super::GameEventKind;
+use crate::{log_tail::LogEntry, server::parse_startup_time};
+use std::{collections::HashMap, time::Duration};
+
+/// Beginnings of the death messages of the game, following the name of the player.
+///
+/// - [Death messages](https://minecraft.wiki/w/Death_messages)
+const DEATH_MESSAGES: [&str; 19] = [
+    "was ",
+    "drowned",
+    "died",
+    "blew up",
+    "fell ",
+    "hit the ground too hard",
+    "burned to death",
+    "went up in flames",
+    "went off with a bang",
+    "walked into",
+    "tried to swim in lava",
+    "discovered the floor was lava",
+    "suffocated in a wall",
+    "starved to death",
+    "froze to death",
+    "experienced kinetic energy",
+    "withered away",
+    "didn't want to live",
+    "left the confines of this world",
+];
+
+/// Parser of the game events from the entries of the server logs.
+///
+/// The parser is fed every entry in order, as some events are spread over several lines: the UUID
+/// and the address of a player are printed before they join, and the reason of a disconnection
+/// before they leave.
+#[derive(Debug, Default)]
+pub struct GameEventParser {
+    /// UUIDs of the players authenticated but not joined yet.
+    uuids: HashMap<String, String>,
+    /// Addresses of the players logged in but not joined yet.
+    addresses: HashMap<String, String>,
+    /// Reasons of the disconnections of the players not left yet.
+    reasons: HashMap<String, String>,
+}
+
+impl GameEventParser {
+    /// Parses the next entry of the server logs.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - The entry, following the previous one given to the parser.
+    ///
+    /// # Returns
+    ///
+    /// The event of the entry, if any.
+    pub fn parse(&mut self, entry: &LogEntry) -> Option<GameEventKind> {
+        // The lines continuing the previous entry, e.g. the lines of a stack trace, are not events.
+        entry.time.as_ref()?;
+        let message = entry.message.as_str();
+
+        if let Some(lag) = message.strip_prefix("Can't keep up! ") {
+            return parse_lag(lag);
+        }
+        if message.starts_with("Done (") {
+            return Some(GameEventKind::ServerStarted {
+                startup_time: parse_startup_time(&entry.line),
+            });
+        }
+        if message == "Stopping the server" || message == "Stopping server" {
+            self.uuids.clear();
+            self.addresses.clear();
+            self.reasons.clear();
+            return Some(GameEventKind::ServerStopping);
+        }
+        if let Some(authenticated) = message.strip_prefix("UUID of player ") {
+            let (player, uuid) = authenticated.split_once(" is ")?;
+            self.uuids.insert(String::from(player), String::from(uuid));
+            return None;
+        }
+        if let Some(chat) = message
+            .strip_prefix("[Not Secure] ")
+            .unwrap_or(message)
+            .strip_prefix('<')
+        {
+            let (player, text) = chat.split_once("> ")?;
+            return is_player_name(player).then(|| GameEventKind::Chat {
+                player: String::from(player),
+                message: String::from(text),
+            });
+        }
+
+        let (player, rest) = message.split_once(' ')?;
+
+        // `Steve[/127.0.0.1:53422] logged in with entity id 123 at (0.5, 64.0, 0.5)`
+        if rest.starts_with("logged in with entity id ") {
+            let (player, address) = player.split_once("[/")?;
+            let address = address.strip_suffix(']')?;
+            let address = address.rsplit_once(':').map_or(address, |(ip, _)| ip);
+            self.addresses
+                .insert(String::from(player), String::from(address));
+            return None;
+        }
+
+        if !is_player_name(player) {
+            return None;
+        }
+
+        if rest.ends_with("joined the game") {
+            return Some(GameEventKind::PlayerJoined {
+                player: String::from(player),
+                uuid: self.uuids.remove(player),
+                address: self.addresses.remove(player),
+            });
+        }
+        if rest == "left the game" {
+            return Some(GameEventKind::PlayerLeft {
+                player: String::from(player),
+                reason: self.reasons.remove(player),
+            });
+        }
+        if let Some(reason) = rest.strip_prefix("lost connection: ") {
+            self.uuids.remove(player);
+            self.addresses.remove(player);
+            self.reasons
+                .insert(String::from(player), String::from(reason));
+            return None;
+        }
+
+        for (prefix, frame) in [
+            ("has made the advancement [", "task"),
+            ("has reached the goal [", "goal"),
+            ("has completed the challenge [", "challenge"),
+        ] {
+            if let Some(advancement) = rest.strip_prefix(prefix) {
+                return Some(GameEventKind::Advancement {
+                    player: String::from(player),
+                    advancement: String::from(advancement.strip_suffix(']')?),
+                    frame: String::from(frame),
+                });
+            }
+        }
+
+        DEATH_MESSAGES
+            .iter()
+            .any(|death| rest.starts_with(death))
+            .then(|| GameEventKind::Death {
+                player: String::from(player),
+                message: String::from(message),
+            })
+    }
+}
+
+/// Returns whether a name is a valid name of a Java Edition player.
+fn is_player_name(name: &str) -> bool {
+    (1..=16).contains(&name.len())
+        && name
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || character == '_')
+}
+
+/// Parses a lag warning, e.g. `Is the server overloaded? Running 2034ms or 40 ticks behind`.
+fn parse_lag(warning: &str) -> Option<GameEventKind> {
+    let (_, running) = warning.split_once("Running ")?;
+    let (behind, rest) = running.split_once("ms or ")?;
+    let (ticks, _) = rest.split_once(" ticks behind")?;
+
+    Some(GameEventKind::Lag {
+        behind: Duration::from_millis(behind.parse().ok()?),
+        ticks: ticks.parse().ok()?,
+    })
+}
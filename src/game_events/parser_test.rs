@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        game_events::{GameEventKind, GameEventParser},
+        log_tail::{LogEntry, LogLevel},
+    };
+    use std::time::Duration;
+    use test_case::test_case;
+
+    /// Parses lines in order, returning the events of each one.
+    fn parse(lines: &[&str]) -> Vec<Option<GameEventKind>> {
+        let mut parser = GameEventParser::default();
+
+        lines
+            .iter()
+            .enumerate()
+            .map(|(index, line)| {
+                parser.parse(&LogEntry::new(
+                    index as u64,
+                    String::from(*line),
+                    LogLevel::Info,
+                ))
+            })
+            .collect()
+    }
+
+    fn parse_one(line: &str) -> Option<GameEventKind> {
+        parse(&[line]).remove(0)
+    }
+
+    #[test]
+    fn test_player_joined() {
+        let events = parse(&[
+            "[12:00:00] [User Authenticator #1/INFO]: UUID of player Steve is 069a79f4-44e9-4726-a5be-fca90e38aaf5",
+            "[12:00:00] [Server thread/INFO]: Steve[/127.0.0.1:53422] logged in with entity id 123 at (0.5, 64.0, 0.5)",
+            "[12:00:00] [Server thread/INFO]: Steve joined the game",
+            "[12:00:05] [Server thread/INFO]: Alex joined the game",
+        ]);
+
+        assert_eq!(events[0], None);
+        assert_eq!(events[1], None);
+        assert_eq!(
+            events[2],
+            Some(GameEventKind::PlayerJoined {
+                player: String::from("Steve"),
+                uuid: Some(String::from("069a79f4-44e9-4726-a5be-fca90e38aaf5")),
+                address: Some(String::from("127.0.0.1")),
+            })
+        );
+        assert_eq!(
+            events[3],
+            Some(GameEventKind::PlayerJoined {
+                player: String::from("Alex"),
+                uuid: None,
+                address: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_player_left() {
+        let events = parse(&[
+            "[12:00:00] [Server thread/INFO]: Steve lost connection: Disconnected",
+            "[12:00:00] [Server thread/INFO]: Steve left the game",
+            "[12:00:05] [Server thread/INFO]: Steve left the game",
+        ]);
+
+        assert_eq!(events[0], None);
+        assert_eq!(
+            events[1],
+            Some(GameEventKind::PlayerLeft {
+                player: String::from("Steve"),
+                reason: Some(String::from("Disconnected")),
+            })
+        );
+        assert_eq!(
+            events[2],
+            Some(GameEventKind::PlayerLeft {
+                player: String::from("Steve"),
+                reason: None,
+            })
+        );
+    }
+
+    #[test_case("[12:00:00] [Server thread/INFO]: <Steve> Hello there", "Steve", "Hello there"; "vanilla")]
+    #[test_case("[12:00:00] [Server thread/INFO]: [Not Secure] <Alex> gg", "Alex", "gg"; "not secure")]
+    #[test_case("[12:00:00 INFO]: <Steve> I joined the game", "Steve", "I joined the game"; "paper")]
+    fn test_chat(line: &str, player: &str, message: &str) {
+        assert_eq!(
+            parse_one(line),
+            Some(GameEventKind::Chat {
+                player: String::from(player),
+                message: String::from(message),
+            })
+        );
+    }
+
+    #[test_case("Steve was slain by Zombie"; "slain")]
+    #[test_case("Steve drowned"; "drowned")]
+    #[test_case("Steve fell from a high place"; "fell")]
+    #[test_case("Steve hit the ground too hard whilst trying to escape Creeper"; "hit the ground")]
+    #[test_case("Steve tried to swim in lava"; "lava")]
+    #[test_case("Steve_2 blew up"; "blew up")]
+    fn test_death(message: &str) {
+        let line = format!("[12:00:00] [Server thread/INFO]: {message}");
+        let player = message.split_once(' ').unwrap().0;
+
+        assert_eq!(
+            parse_one(&line),
+            Some(GameEventKind::Death {
+                player: String::from(player),
+                message: String::from(message),
+            })
+        );
+    }
+
+    #[test_case("has made the advancement [Stone Age]", "Stone Age", "task"; "task")]
+    #[test_case("has reached the goal [Sky's the Limit]", "Sky's the Limit", "goal"; "goal")]
+    #[test_case("has completed the challenge [How Did We Get Here?]", "How Did We Get Here?", "challenge"; "challenge")]
+    fn test_advancement(text: &str, advancement: &str, frame: &str) {
+        let line = format!("[12:00:00] [Server thread/INFO]: Steve {text}");
+
+        assert_eq!(
+            parse_one(&line),
+            Some(GameEventKind::Advancement {
+                player: String::from("Steve"),
+                advancement: String::from(advancement),
+                frame: String::from(frame),
+            })
+        );
+    }
+
+    #[test_case(
+        "[12:00:00] [Server thread/INFO]: Done (3.245s)! For help, type \"help\""
+        => Some(GameEventKind::ServerStarted { startup_time: Some(Duration::from_millis(3245)) });
+        "started"
+    )]
+    #[test_case("[12:00:00] [Server thread/INFO]: Stopping the server" => Some(GameEventKind::ServerStopping); "stopping")]
+    #[test_case("[12:00:00 INFO]: Stopping server" => Some(GameEventKind::ServerStopping); "stopping paper")]
+    #[test_case(
+        "[12:00:00] [Server thread/WARN]: Can't keep up! Is the server overloaded? Running 2034ms or 40 ticks behind"
+        => Some(GameEventKind::Lag { behind: Duration::from_millis(2034), ticks: 40 });
+        "lag"
+    )]
+    #[test_case("[12:00:00] [Server thread/WARN]: Can't keep up! Did the system time change?" => None; "time change")]
+    #[test_case("[12:00:00] [Server thread/INFO]: Preparing spawn area: 83%" => None; "other line")]
+    #[test_case("[12:00:00] [Server thread/INFO]: There are 0 of a max of 20 players online: " => None; "list")]
+    #[test_case("[12:00:00] [Server thread/INFO]: <Steve> " => Some(GameEventKind::Chat { player: String::from("Steve"), message: String::new() }); "empty chat")]
+    #[test_case("[12:00:00] [Server thread/INFO]: [Steve: Gave 1 [Diamond] to Steve]" => None; "command feedback")]
+    #[test_case("java.lang.IllegalStateException: Steve was slain" => None; "stack trace")]
+    fn test_server_events(line: &str) -> Option<GameEventKind> {
+        parse_one(line)
+    }
+
+    #[test]
+    fn test_pending_details_cleared() {
+        let events = parse(&[
+            "[12:00:00] [User Authenticator #1/INFO]: UUID of player Steve is 069a79f4-44e9-4726-a5be-fca90e38aaf5",
+            "[12:00:00] [Server thread/INFO]: Steve lost connection: Failed to verify username!",
+            "[12:00:05] [Server thread/INFO]: Steve joined the game",
+        ]);
+
+        assert_eq!(
+            events[2],
+            Some(GameEventKind::PlayerJoined {
+                player: String::from("Steve"),
+                uuid: None,
+                address: None,
+            })
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use super::{
+    GAME_EVENT_KINDS, GameEvent, GameEventError, GameEventKind, GameEventParser,
+    GameEventsConfiguration,
+};
+use crate::log_tail::LogEntry;
+use std::{
+    collections::{HashSet, VecDeque},
+    sync::{Arc, Mutex, MutexGuard},
+    time::SystemTime,
+};
+use tokio::{sync::mpsc::Receiver, task::JoinHandle};
+
+/// Criteria of the game events to return.
+#[derive(Debug, Clone, Default)]
+pub struct GameEventQuery<'a> {
+    /// The kind of the events, e.g. `player_joined`.
+    pub kind: Option<&'a str>,
+    /// The player the events are about, case-insensitively.
+    pub player: Option<&'a str>,
+    /// The ID after which the events were recorded, to only get the new ones.
+    pub since: Option<u64>,
+    /// The maximum number of events to return, the most recent ones being kept.
+    pub limit: usize,
+}
+
+/// Ring buffer of the last game events, also recorded in the metrics.
+#[derive(Debug, Clone)]
+pub struct GameEventStore {
+    configuration: GameEventsConfiguration,
+    state: Arc<Mutex<GameEventState>>,
+}
+
+#[derive(Debug, Default)]
+struct GameEventState {
+    events: VecDeque<GameEvent>,
+    /// The ID of the next event.
+    next_id: u64,
+    /// The players online according to the events.
+    online: HashSet<String>,
+}
+
+impl GameEventStore {
+    /// Creates an empty store.
+    pub fn new(configuration: GameEventsConfiguration) -> Self {
+        GameEventStore {
+            configuration,
+            state: Arc::new(Mutex::new(GameEventState::default())),
+        }
+    }
+
+    /// Parses the game events from the log entries in the background.
+    ///
+    /// The entries replayed from the log file at startup rebuild the events and the online
+    /// players without being counted in the metrics again.
+    ///
+    /// # Arguments
+    ///
+    /// * `receiver` - The receiver of every log entry, obtained before the log file is read so
+    ///   that no event is missed.
+    ///
+    /// # Returns
+    ///
+    /// The handle of the parse task, completing once the log entries are no longer sent.
+    pub fn follow(&self, mut receiver: Receiver<LogEntry>) -> JoinHandle<()> {
+        let store = self.clone();
+
+        tokio::spawn(async move {
+            let mut parser = GameEventParser::default();
+            while let Some(entry) = receiver.recv().await {
+                if let Some(kind) = parser.parse(&entry) {
+                    store.push(kind, entry.time, entry.timestamp, !entry.replayed);
+                }
+            }
+        })
+    }
+
+    /// Records an event, dropping the oldest one if the store is full.
+    ///
+    /// # Arguments
+    ///
+    /// * `counted` - Whether to increment the counters of the metrics, false for the events
+    ///   already counted before the backend started.
+    ///
+    /// # Returns
+    ///
+    /// The recorded event.
+    pub fn push(
+        &self,
+        kind: GameEventKind,
+        time: Option<String>,
+        timestamp: SystemTime,
+        counted: bool,
+    ) -> GameEvent {
+        let mut state = self.lock_state();
+
+        match &kind {
+            GameEventKind::PlayerJoined { player, .. } => {
+                state.online.insert(player.clone());
+            }
+            GameEventKind::PlayerLeft { player, .. } => {
+                state.online.remove(player);
+            }
+            GameEventKind::ServerStarted { .. } | GameEventKind::ServerStopping => {
+                state.online.clear();
+            }
+            _ => {}
+        }
+        Self::record_metrics(&kind, state.online.len(), counted);
+
+        let event = GameEvent {
+            id: state.next_id,
+            kind,
+            time,
+            timestamp,
+        };
+        state.next_id += 1;
+
+        if state.events.len() >= self.configuration.buffer_size {
+            state.events.pop_front();
+        }
+        state.events.push_back(event.clone());
+
+        event
+    }
+
+    /// Returns the recorded events matching a query, oldest first.
+    ///
+    /// # Returns
+    ///
+    /// The events, or a `GameEventError::InvalidQuery` if the kind is unknown.
+    pub fn query(&self, query: &GameEventQuery) -> Result<Vec<GameEvent>, GameEventError> {
+        if let Some(kind) = query.kind.filter(|kind| !GAME_EVENT_KINDS.contains(kind)) {
+            return Err(GameEventError::InvalidQuery {
+                cause: format!(
+                    "Unknown event kind '{kind}', expected one of {}",
+                    GAME_EVENT_KINDS.join(", ")
+                ),
+            });
+        }
+
+        let state = self.lock_state();
+        let mut events: Vec<GameEvent> = state
+            .events
+            .iter()
+            .rev()
+            .take_while(|event| query.since.is_none_or(|since| event.id > since))
+            .filter(|event| query.kind.is_none_or(|kind| event.kind.as_str() == kind))
+            .filter(|event| {
+                query.player.is_none_or(|player| {
+                    event
+                        .kind
+                        .player()
+                        .is_some_and(|name| name.eq_ignore_ascii_case(player))
+                })
+            })
+            .take(query.limit)
+            .cloned()
+            .collect();
+        events.reverse();
+
+        Ok(events)
+    }
+
+    /// Returns the players online according to the events, sorted by name.
+    pub fn online_players(&self) -> Vec<String> {
+        let mut players: Vec<String> = self.lock_state().online.iter().cloned().collect();
+        players.sort();
+
+        players
+    }
+
+    #[allow(unused_variables)]
+    fn record_metrics(kind: &GameEventKind, online: usize, counted: bool) {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "metrics")] {
+                crate::metrics::GAME_PLAYERS_ONLINE.set(online as i64);
+                if !counted {
+                    return;
+                }
+                crate::metrics::GAME_EVENTS_TOTAL
+                    .with_label_values(&[kind.as_str()])
+                    .inc();
+                if let GameEventKind::Lag { behind, .. } = kind {
+                    crate::metrics::GAME_LAG_BEHIND_MILLISECONDS_TOTAL.inc_by(behind.as_millis() as u64);
+                }
+            }
+        }
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, GameEventState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
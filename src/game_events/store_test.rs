@@ -0,0 +1,298 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        game_events::{
+            GameEventError, GameEventKind, GameEventQuery, GameEventStore, GameEventsConfiguration,
+        },
+        log_tail::{LOG_TAIL_CHANNEL_CAPACITY, LogFollower, LogTailConfiguration},
+    };
+    use std::{fs, time::Duration, time::SystemTime};
+    use tokio::time::sleep;
+
+    fn store(buffer_size: usize) -> GameEventStore {
+        GameEventStore::new(GameEventsConfiguration { buffer_size })
+    }
+
+    fn joined(player: &str) -> GameEventKind {
+        GameEventKind::PlayerJoined {
+            player: String::from(player),
+            uuid: None,
+            address: None,
+        }
+    }
+
+    fn left(player: &str) -> GameEventKind {
+        GameEventKind::PlayerLeft {
+            player: String::from(player),
+            reason: None,
+        }
+    }
+
+    fn query(store: &GameEventStore, query: GameEventQuery) -> Vec<u64> {
+        store
+            .query(&query)
+            .unwrap()
+            .into_iter()
+            .map(|event| event.id)
+            .collect()
+    }
+
+    #[test]
+    fn test_query() {
+        let store = store(10);
+        store.push(joined("Steve"), None, SystemTime::now(), true);
+        store.push(joined("Alex"), None, SystemTime::now(), true);
+        store.push(left("steve"), None, SystemTime::now(), true);
+        store.push(GameEventKind::ServerStopping, None, SystemTime::now(), true);
+
+        let all = GameEventQuery {
+            limit: 10,
+            ..Default::default()
+        };
+        assert_eq!(query(&store, all.clone()), vec![0, 1, 2, 3]);
+        assert_eq!(
+            query(
+                &store,
+                GameEventQuery {
+                    limit: 2,
+                    ..all.clone()
+                }
+            ),
+            vec![2, 3]
+        );
+        assert_eq!(
+            query(
+                &store,
+                GameEventQuery {
+                    kind: Some("player_joined"),
+                    ..all.clone()
+                }
+            ),
+            vec![0, 1]
+        );
+        assert_eq!(
+            query(
+                &store,
+                GameEventQuery {
+                    player: Some("STEVE"),
+                    ..all.clone()
+                }
+            ),
+            vec![0, 2]
+        );
+        assert_eq!(
+            query(
+                &store,
+                GameEventQuery {
+                    since: Some(1),
+                    ..all.clone()
+                }
+            ),
+            vec![2, 3]
+        );
+        assert!(
+            query(
+                &store,
+                GameEventQuery {
+                    since: Some(3),
+                    ..all
+                }
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_query_unknown_kind() {
+        let result = store(10).query(&GameEventQuery {
+            kind: Some("explosion"),
+            limit: 10,
+            ..Default::default()
+        });
+        assert!(matches!(result, Err(GameEventError::InvalidQuery { .. })));
+    }
+
+    #[test]
+    fn test_oldest_events_dropped() {
+        let store = store(2);
+        for player in ["Steve", "Alex", "Notch"] {
+            store.push(joined(player), None, SystemTime::now(), true);
+        }
+
+        let events = store
+            .query(&GameEventQuery {
+                limit: 10,
+                ..Default::default()
+            })
+            .unwrap();
+        let ids: Vec<u64> = events.iter().map(|event| event.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_online_players() {
+        let store = store(10);
+        store.push(joined("Steve"), None, SystemTime::now(), true);
+        store.push(joined("Alex"), None, SystemTime::now(), true);
+        assert_eq!(store.online_players(), vec!["Alex", "Steve"]);
+
+        store.push(left("Steve"), None, SystemTime::now(), true);
+        assert_eq!(store.online_players(), vec!["Alex"]);
+
+        store.push(GameEventKind::ServerStopping, None, SystemTime::now(), true);
+        assert!(store.online_players().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_follow_log_file() {
+        let dir = std::env::temp_dir().join(format!("game-events-follow-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("latest.log");
+        fs::write(
+            &path,
+            "[12:00:00] [Server thread/INFO]: Done (1.5s)! For help, type \"help\"\n\
+             [12:00:01] [Server thread/INFO]: Steve joined the game\n\
+             [12:00:02] [Server thread/INFO]: <Steve> Hello\n\
+             [12:00:03] [Server thread/INFO]: Preparing level \"world\"\n",
+        )
+        .unwrap();
+
+        let follower = LogFollower::new(LogTailConfiguration {
+            path: path.clone(),
+            poll_interval: 20,
+            backlog_size: 10,
+        });
+        let store = store(10);
+        let parse_task = store.follow(follower.consume());
+        let follow_task = follower.start();
+        sleep(Duration::from_millis(200)).await;
+
+        let events = store
+            .query(&GameEventQuery {
+                limit: 10,
+                ..Default::default()
+            })
+            .unwrap();
+        let kinds: Vec<&str> = events.iter().map(|event| event.kind.as_str()).collect();
+        assert_eq!(kinds, vec!["server_started", "player_joined", "chat"]);
+        assert_eq!(events[1].time.as_deref(), Some("12:00:01"));
+        assert_eq!(store.online_players(), vec!["Steve"]);
+
+        follow_task.abort();
+        parse_task.abort();
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_follow_large_log_file() {
+        let dir = std::env::temp_dir().join(format!("game-events-large-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("latest.log");
+        // More lines than the log tail channel holds, the first players joining at the start.
+        let mut content = String::from(
+            "[12:00:00] [Server thread/INFO]: Steve joined the game\n\
+             [12:00:00] [Server thread/INFO]: Alex joined the game\n",
+        );
+        for index in 0..LOG_TAIL_CHANNEL_CAPACITY * 3 {
+            content.push_str(&format!(
+                "[12:00:01] [Server thread/INFO]: <Steve> {index}\n"
+            ));
+        }
+        content.push_str("[12:00:02] [Server thread/INFO]: Alex left the game\n");
+        fs::write(&path, content).unwrap();
+
+        let follower = LogFollower::new(LogTailConfiguration {
+            path: path.clone(),
+            poll_interval: 20,
+            backlog_size: 10,
+        });
+        let store = store(LOG_TAIL_CHANNEL_CAPACITY * 4);
+        let parse_task = store.follow(follower.consume());
+        let follow_task = follower.start();
+        sleep(Duration::from_millis(500)).await;
+
+        let events = store
+            .query(&GameEventQuery {
+                limit: LOG_TAIL_CHANNEL_CAPACITY * 4,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(events.len(), LOG_TAIL_CHANNEL_CAPACITY * 3 + 3);
+        assert_eq!(store.online_players(), vec!["Steve"]);
+
+        follow_task.abort();
+        parse_task.abort();
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_follow_replayed_log_file() {
+        let dir = std::env::temp_dir().join(format!("game-events-replay-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("latest.log");
+        fs::write(
+            &path,
+            "[12:00:00] [Server thread/INFO]: Steve joined the game\n\
+             [12:00:01] [Server thread/WARN]: Can't keep up! Is the server overloaded? Running 2034ms or 40 ticks behind\n",
+        )
+        .unwrap();
+        #[cfg(feature = "metrics")]
+        let lag_before = crate::metrics::GAME_LAG_BEHIND_MILLISECONDS_TOTAL.get();
+
+        let follower = LogFollower::new(LogTailConfiguration {
+            path: path.clone(),
+            poll_interval: 20,
+            backlog_size: 10,
+        });
+        let store = store(10);
+        let parse_task = store.follow(follower.consume());
+        let follow_task = follower.start();
+        sleep(Duration::from_millis(200)).await;
+
+        // The replayed lines rebuild the events without being counted again.
+        assert_eq!(
+            store
+                .query(&GameEventQuery {
+                    limit: 10,
+                    ..Default::default()
+                })
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(store.online_players(), vec!["Steve"]);
+        #[cfg(feature = "metrics")]
+        assert_eq!(
+            crate::metrics::GAME_LAG_BEHIND_MILLISECONDS_TOTAL.get(),
+            lag_before
+        );
+
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            b"[12:00:02] [Server thread/WARN]: Can't keep up! Is the server overloaded? Running 1000ms or 20 ticks behind\n",
+        )
+        .unwrap();
+        sleep(Duration::from_millis(200)).await;
+
+        assert_eq!(
+            store
+                .query(&GameEventQuery {
+                    limit: 10,
+                    ..Default::default()
+                })
+                .unwrap()
+                .len(),
+            3
+        );
+        #[cfg(feature = "metrics")]
+        assert_eq!(
+            crate::metrics::GAME_LAG_BEHIND_MILLISECONDS_TOTAL.get(),
+            lag_before + 1000
+        );
+
+        follow_task.abort();
+        parse_task.abort();
+        fs::remove_dir_all(dir).ok();
+    }
+}
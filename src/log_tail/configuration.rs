@@ -0,0 +1,87 @@
+use super::LogTailError;
+use std::{env, path::PathBuf};
+use tracing::debug;
+
+/// Default interval between two checks of the log file, in milliseconds.
+pub const DEFAULT_LOG_TAIL_POLL_INTERVAL: u64 = 250;
+
+/// Default number of recent log entries kept in memory for the new clients.
+pub const DEFAULT_LOG_TAIL_BACKLOG_SIZE: usize = 500;
+
+#[derive(Debug, Clone)]
+pub struct LogTailConfiguration {
+    /// Path of the log file of the Minecraft server, usually `logs/latest.log`.
+    pub path: PathBuf,
+    /// Interval between two checks of the log file, in milliseconds.
+    pub poll_interval: u64,
+    /// Number of recent log entries kept in memory for the new clients.
+    pub backlog_size: usize,
+}
+
+impl LogTailConfiguration {
+    /// Returns the configuration from environment variables.
+    ///
+    /// The path defaults to the `logs/latest.log` file of the working directory of the supervised
+    /// server, if any.
+    ///
+    /// # Returns:
+    ///
+    /// The configuration.
+    pub fn try_new() -> Result<LogTailConfiguration, LogTailError> {
+        debug!("Attempting to create log tail configuration from environment variables...");
+
+        let path = Self::path().ok_or_else(|| LogTailError::Configuration {
+            cause: String::from("Environment variable 'LOG_TAIL_PATH' is not set"),
+        })?;
+        let poll_interval = env::var("LOG_TAIL_POLL_INTERVAL")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u64>())
+            .unwrap_or(Ok(DEFAULT_LOG_TAIL_POLL_INTERVAL))
+            .ok()
+            .filter(|poll_interval| *poll_interval > 0)
+            .ok_or_else(|| LogTailError::Configuration {
+                cause: String::from(
+                    "Environment variable 'LOG_TAIL_POLL_INTERVAL' is not a valid positive number",
+                ),
+            })?;
+        let backlog_size = env::var("LOG_TAIL_BACKLOG_SIZE")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<usize>())
+            .unwrap_or(Ok(DEFAULT_LOG_TAIL_BACKLOG_SIZE))
+            .ok()
+            .filter(|backlog_size| *backlog_size > 0)
+            .ok_or_else(|| LogTailError::Configuration {
+                cause: String::from(
+                    "Environment variable 'LOG_TAIL_BACKLOG_SIZE' is not a valid positive number",
+                ),
+            })?;
+
+        Ok(LogTailConfiguration {
+            path,
+            poll_interval,
+            backlog_size,
+        })
+    }
+
+    /// Returns whether the log tailing is enabled, with the `LOG_TAIL_PATH` or
+    /// `SERVER_WORKING_DIR` environment variables.
+    pub fn is_set() -> bool {
+        Self::path().is_some()
+    }
+
+    /// Returns the path of the log file from the environment variables.
+    fn path() -> Option<PathBuf> {
+        env::var("LOG_TAIL_PATH")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .map(PathBuf::from)
+            .or_else(|| {
+                env::var("SERVER_WORKING_DIR")
+                    .ok()
+                    .filter(|value| !value.trim().is_empty())
+                    .map(|working_dir| PathBuf::from(working_dir).join("logs").join("latest.log"))
+            })
+    }
+}
@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use crate::log_tail::{
+        DEFAULT_LOG_TAIL_BACKLOG_SIZE, DEFAULT_LOG_TAIL_POLL_INTERVAL, LogTailConfiguration,
+        LogTailError,
+    };
+    use serial_test::serial;
+    use std::path::PathBuf;
+    use temp_env::with_vars;
+    use test_case::test_case;
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration() {
+        with_vars(
+            [
+                ("LOG_TAIL_PATH", Some("/srv/minecraft/logs/latest.log")),
+                ("LOG_TAIL_POLL_INTERVAL", None),
+                ("LOG_TAIL_BACKLOG_SIZE", None),
+            ],
+            || {
+                let configuration = LogTailConfiguration::try_new().unwrap();
+                assert_eq!(
+                    configuration.path,
+                    PathBuf::from("/srv/minecraft/logs/latest.log")
+                );
+                assert_eq!(configuration.poll_interval, DEFAULT_LOG_TAIL_POLL_INTERVAL);
+                assert_eq!(configuration.backlog_size, DEFAULT_LOG_TAIL_BACKLOG_SIZE);
+                assert!(LogTailConfiguration::is_set());
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_custom() {
+        with_vars(
+            [
+                ("LOG_TAIL_PATH", Some("latest.log")),
+                ("LOG_TAIL_POLL_INTERVAL", Some("1000")),
+                ("LOG_TAIL_BACKLOG_SIZE", Some("50")),
+            ],
+            || {
+                let configuration = LogTailConfiguration::try_new().unwrap();
+                assert_eq!(configuration.path, PathBuf::from("latest.log"));
+                assert_eq!(configuration.poll_interval, 1000);
+                assert_eq!(configuration.backlog_size, 50);
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_server_working_dir() {
+        with_vars(
+            [
+                ("LOG_TAIL_PATH", None),
+                ("SERVER_WORKING_DIR", Some("/srv/minecraft")),
+            ],
+            || {
+                let configuration = LogTailConfiguration::try_new().unwrap();
+                assert_eq!(
+                    configuration.path,
+                    PathBuf::from("/srv/minecraft/logs/latest.log")
+                );
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_missing_path() {
+        with_vars(
+            [
+                ("LOG_TAIL_PATH", None::<&str>),
+                ("SERVER_WORKING_DIR", None),
+            ],
+            || {
+                let result = LogTailConfiguration::try_new();
+                assert_eq!(
+                    result.unwrap_err(),
+                    LogTailError::Configuration {
+                        cause: String::from("Environment variable 'LOG_TAIL_PATH' is not set"),
+                    }
+                );
+                assert!(!LogTailConfiguration::is_set());
+            },
+        );
+    }
+
+    #[test_case("LOG_TAIL_POLL_INTERVAL", "0"; "zero poll interval")]
+    #[test_case("LOG_TAIL_POLL_INTERVAL", "soon"; "invalid poll interval")]
+    #[test_case("LOG_TAIL_BACKLOG_SIZE", "0"; "empty backlog")]
+    #[test_case("LOG_TAIL_BACKLOG_SIZE", "-1"; "negative backlog")]
+    #[serial(rcon)]
+    fn test_get_configuration_invalid(variable: &str, value: &str) {
+        with_vars(
+            [
+                ("LOG_TAIL_PATH", Some("latest.log")),
+                (variable, Some(value)),
+            ],
+            || {
+                let result = LogTailConfiguration::try_new();
+                assert!(matches!(result, Err(LogTailError::Configuration { .. })));
+            },
+        );
+    }
+}
@@ -0,0 +1,148 @@
+use std::time::SystemTime;
+
+/// Level of a line of the server logs, from the least to the most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl LogLevel {
+    /// Returns the name of the level, as used in the API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "trace",
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+            LogLevel::Fatal => "fatal",
+        }
+    }
+}
+
+impl TryFrom<&str> for LogLevel {
+    type Error = String;
+
+    /// Parses a level, case-insensitively, including the `WARNING` and `SEVERE` names of the
+    /// `java.util.logging` levels printed by some plugins.
+    fn try_from(value: &str) -> Result<Self, String> {
+        match value.to_ascii_lowercase().as_str() {
+            "trace" => Ok(LogLevel::Trace),
+            "debug" => Ok(LogLevel::Debug),
+            "info" => Ok(LogLevel::Info),
+            "warn" | "warning" => Ok(LogLevel::Warn),
+            "error" | "severe" => Ok(LogLevel::Error),
+            "fatal" => Ok(LogLevel::Fatal),
+            _ => Err(format!("Unknown log level '{value}'")),
+        }
+    }
+}
+
+/// Line of the server logs split in its parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogLine<'a> {
+    pub time: &'a str,
+    pub thread: Option<&'a str>,
+    pub level: LogLevel,
+    pub message: &'a str,
+}
+
+impl<'a> LogLine<'a> {
+    /// Parses a line of the server logs.
+    ///
+    /// The `[12:34:56] [Server thread/INFO]: message` format of the vanilla server is supported,
+    /// along with the `[12:34:56 INFO]: message` one of Paper and the extra `[logger]` blocks of
+    /// Forge.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The line, without its line break.
+    ///
+    /// # Returns
+    ///
+    /// The parts of the line, or `None` if it is not in a known format, e.g. a line of a stack
+    /// trace.
+    pub fn parse(line: &'a str) -> Option<Self> {
+        let (header, rest) = line.strip_prefix('[')?.split_once(']')?;
+
+        if let Some(message) = rest.strip_prefix(": ") {
+            let (time, level) = header.rsplit_once(' ')?;
+
+            return Some(LogLine {
+                time,
+                thread: None,
+                level: LogLevel::try_from(level).ok()?,
+                message,
+            });
+        }
+
+        let (source, mut rest) = rest.strip_prefix(" [")?.split_once(']')?;
+        let (thread, level) = source.rsplit_once('/')?;
+        while let Some(block) = rest.strip_prefix(" [") {
+            rest = block.split_once(']')?.1;
+        }
+        let message = rest.strip_prefix(':')?;
+
+        Some(LogLine {
+            time: header,
+            thread: Some(thread),
+            level: LogLevel::try_from(level).ok()?,
+            message: message.strip_prefix(' ').unwrap_or(message),
+        })
+    }
+}
+
+/// Entry of the server logs, as streamed to the clients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogEntry {
+    /// Position of the entry since the backend started, increasing by one per line.
+    pub sequence: u64,
+    pub time: Option<String>,
+    pub thread: Option<String>,
+    pub level: LogLevel,
+    pub message: String,
+    /// The line as printed in the log file.
+    pub line: String,
+    /// When the line was read from the log file.
+    pub timestamp: SystemTime,
+    /// Whether the line was already in the log file when the backend started following it.
+    pub replayed: bool,
+}
+
+impl LogEntry {
+    /// Creates an entry from a line of the log file.
+    ///
+    /// # Arguments
+    ///
+    /// * `sequence` - The position of the entry.
+    /// * `line` - The line, without its line break.
+    /// * `previous_level` - The level of the previous entry, given to the lines not in a known
+    ///   format as they usually continue it, e.g. the lines of a stack trace.
+    pub fn new(sequence: u64, line: String, previous_level: LogLevel) -> Self {
+        let (time, thread, level, message) = match LogLine::parse(&line) {
+            Some(parsed) => (
+                Some(String::from(parsed.time)),
+                parsed.thread.map(String::from),
+                parsed.level,
+                String::from(parsed.message),
+            ),
+            None => (None, None, previous_level, line.clone()),
+        };
+
+        LogEntry {
+            sequence,
+            time,
+            thread,
+            level,
+            message,
+            line,
+            timestamp: SystemTime::now(),
+            replayed: false,
+        }
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use crate::log_tail::{LogEntry, LogLevel, LogLine};
+    use test_case::test_case;
+
+    #[test_case(
+        "[12:34:56] [Server thread/INFO]: Steve joined the game"
+        => Some(LogLine { time: "12:34:56", thread: Some("Server thread"), level: LogLevel::Info, message: "Steve joined the game" });
+        "vanilla"
+    )]
+    #[test_case(
+        "[12:34:56] [Server thread/INFO]: <Steve> [hello]: world"
+        => Some(LogLine { time: "12:34:56", thread: Some("Server thread"), level: LogLevel::Info, message: "<Steve> [hello]: world" });
+        "chat"
+    )]
+    #[test_case(
+        "[12:34:56] [Worker-Main-1/WARN]: Can't keep up!"
+        => Some(LogLine { time: "12:34:56", thread: Some("Worker-Main-1"), level: LogLevel::Warn, message: "Can't keep up!" });
+        "worker thread"
+    )]
+    #[test_case(
+        "[12:34:56 ERROR]: Could not pass event PlayerJoinEvent"
+        => Some(LogLine { time: "12:34:56", thread: None, level: LogLevel::Error, message: "Could not pass event PlayerJoinEvent" });
+        "paper"
+    )]
+    #[test_case(
+        "[18Oct2026 12:34:56.789] [Server thread/INFO] [minecraft/DedicatedServer]: Done (3.2s)!"
+        => Some(LogLine { time: "18Oct2026 12:34:56.789", thread: Some("Server thread"), level: LogLevel::Info, message: "Done (3.2s)!" });
+        "forge"
+    )]
+    #[test_case(
+        "[12:34:56] [Server thread/INFO]:"
+        => Some(LogLine { time: "12:34:56", thread: Some("Server thread"), level: LogLevel::Info, message: "" });
+        "empty message"
+    )]
+    #[test_case("\tat net.minecraft.server.MinecraftServer.run(MinecraftServer.java:1)" => None; "stack trace")]
+    #[test_case("[12:34:56] [Server thread/NOTICE]: message" => None; "unknown level")]
+    #[test_case("[12:34:56] Server thread/INFO: message" => None; "no thread block")]
+    #[test_case("" => None; "empty")]
+    fn test_parse(line: &str) -> Option<LogLine<'_>> {
+        LogLine::parse(line)
+    }
+
+    #[test_case("info" => Ok(LogLevel::Info); "lowercase")]
+    #[test_case("WARN" => Ok(LogLevel::Warn); "uppercase")]
+    #[test_case("WARNING" => Ok(LogLevel::Warn); "warning")]
+    #[test_case("SEVERE" => Ok(LogLevel::Error); "severe")]
+    #[test_case("loud" => Err(String::from("Unknown log level 'loud'")); "unknown")]
+    fn test_level(value: &str) -> Result<LogLevel, String> {
+        LogLevel::try_from(value)
+    }
+
+    #[test]
+    fn test_level_order() {
+        assert!(LogLevel::Trace < LogLevel::Debug);
+        assert!(LogLevel::Info < LogLevel::Warn);
+        assert!(LogLevel::Error < LogLevel::Fatal);
+    }
+
+    #[test]
+    fn test_entry_continuation() {
+        let entry = LogEntry::new(
+            3,
+            String::from("[12:34:56] [Server thread/ERROR]: Encountered an unexpected exception"),
+            LogLevel::Info,
+        );
+        assert_eq!(entry.sequence, 3);
+        assert_eq!(entry.time.as_deref(), Some("12:34:56"));
+        assert_eq!(entry.thread.as_deref(), Some("Server thread"));
+        assert_eq!(entry.level, LogLevel::Error);
+        assert_eq!(entry.message, "Encountered an unexpected exception");
+
+        let line = String::from("java.lang.NullPointerException: null");
+        let continuation = LogEntry::new(4, line.clone(), entry.level);
+        assert_eq!(continuation.time, None);
+        assert_eq!(continuation.thread, None);
+        assert_eq!(continuation.level, LogLevel::Error);
+        assert_eq!(continuation.message, line);
+        assert_eq!(continuation.line, line);
+    }
+}
@@ -0,0 +1,9 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum LogTailError {
+    #[error("Invalid log tail configuration: {cause}")]
+    Configuration { cause: String },
+    #[error("Invalid log filter: {cause}")]
+    InvalidFilter { cause: String },
+}
@@ -0,0 +1,56 @@
+use super::{LogEntry, LogLevel, LogTailError};
+use regex::{Regex, RegexBuilder};
+
+/// Maximum size of a compiled filter pattern, in bytes, as the patterns are given by the clients.
+pub const LOG_FILTER_SIZE_LIMIT: usize = 1 << 20;
+
+/// Filter of the log entries streamed to a client.
+#[derive(Debug, Clone, Default)]
+pub struct LogFilter {
+    /// The least severe level of the entries kept.
+    pub level: Option<LogLevel>,
+    /// The pattern the lines of the entries kept must match.
+    pub pattern: Option<Regex>,
+}
+
+impl LogFilter {
+    /// Creates a filter from the parameters of a client.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The least severe level of the entries kept, e.g. `warn`.
+    /// * `pattern` - The regular expression the lines of the entries kept must match.
+    ///
+    /// # Returns
+    ///
+    /// The filter, or a `LogTailError::InvalidFilter` if the level or the pattern is invalid.
+    pub fn try_new(level: Option<&str>, pattern: Option<&str>) -> Result<Self, LogTailError> {
+        let level = level
+            .filter(|level| !level.is_empty())
+            .map(LogLevel::try_from)
+            .transpose()
+            .map_err(|cause| LogTailError::InvalidFilter { cause })?;
+        let pattern = pattern
+            .filter(|pattern| !pattern.is_empty())
+            .map(|pattern| {
+                RegexBuilder::new(pattern)
+                    .size_limit(LOG_FILTER_SIZE_LIMIT)
+                    .build()
+            })
+            .transpose()
+            .map_err(|err| LogTailError::InvalidFilter {
+                cause: err.to_string(),
+            })?;
+
+        Ok(LogFilter { level, pattern })
+    }
+
+    /// Returns whether an entry is kept by the filter.
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        self.level.is_none_or(|level| entry.level >= level)
+            && self
+                .pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(&entry.line))
+    }
+}
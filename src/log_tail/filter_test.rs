@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use crate::log_tail::{LogEntry, LogFilter, LogLevel, LogTailError};
+    use test_case::test_case;
+
+    fn entry(line: &str) -> LogEntry {
+        LogEntry::new(0, String::from(line), LogLevel::Info)
+    }
+
+    #[test_case(None, None, "[12:34:56] [Server thread/INFO]: Steve joined the game" => true; "no filter")]
+    #[test_case(Some("warn"), None, "[12:34:56] [Server thread/INFO]: Steve joined the game" => false; "less severe")]
+    #[test_case(Some("warn"), None, "[12:34:56] [Server thread/WARN]: Can't keep up!" => true; "same level")]
+    #[test_case(Some("warn"), None, "[12:34:56] [Server thread/ERROR]: Exception" => true; "more severe")]
+    #[test_case(None, Some("joined|left"), "[12:34:56] [Server thread/INFO]: Steve left the game" => true; "matching pattern")]
+    #[test_case(None, Some("^<Steve>"), "[12:34:56] [Server thread/INFO]: <Steve> Hello" => false; "pattern on the line")]
+    #[test_case(Some("info"), Some("Steve"), "[12:34:56] [Server thread/DEBUG]: Steve moved" => false; "both")]
+    #[test_case(Some(""), Some(""), "[12:34:56] [Server thread/DEBUG]: Steve moved" => true; "empty parameters")]
+    fn test_matches(level: Option<&str>, pattern: Option<&str>, line: &str) -> bool {
+        LogFilter::try_new(level, pattern)
+            .unwrap()
+            .matches(&entry(line))
+    }
+
+    #[test]
+    fn test_invalid_level() {
+        let result = LogFilter::try_new(Some("loud"), None);
+        assert_eq!(
+            result.unwrap_err(),
+            LogTailError::InvalidFilter {
+                cause: String::from("Unknown log level 'loud'"),
+            }
+        );
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let result = LogFilter::try_new(None, Some("joined("));
+        assert!(matches!(result, Err(LogTailError::InvalidFilter { .. })));
+    }
+
+    #[test]
+    fn test_pattern_too_large() {
+        let result = LogFilter::try_new(None, Some(r"\w{1000}\w{1000}\w{1000}"));
+        assert!(matches!(result, Err(LogTailError::InvalidFilter { .. })));
+    }
+}
@@ -0,0 +1,353 @@
+use super::{LogEntry, LogLevel, LogTailConfiguration};
+use std::{
+    collections::VecDeque,
+    fs::Metadata,
+    io,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+use tokio::{
+    fs::{self, File},
+    io::{AsyncReadExt, AsyncSeekExt},
+    sync::{broadcast, mpsc},
+    task::JoinHandle,
+    time::sleep,
+};
+use tracing::{debug, info, warn};
+
+/// Number of entries buffered for each client before the slowest ones miss some.
+pub const LOG_TAIL_CHANNEL_CAPACITY: usize = 1024;
+
+/// Number of bytes read from the log file at once.
+pub const LOG_TAIL_READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Maximum size of a line, in bytes, longer lines being split.
+pub const LOG_TAIL_MAX_LINE_SIZE: usize = 64 * 1024;
+
+/// Follower of the log file of the Minecraft server, publishing its new lines as they are written.
+///
+/// The file is polled rather than watched, which also works on the network and container file
+/// systems. It is read again from its start when it is truncated, and when the server rotates it,
+/// the previous file being compressed to a `.log.gz` one and a new `latest.log` file being created.
+#[derive(Debug, Clone)]
+pub struct LogFollower {
+    configuration: LogTailConfiguration,
+    backlog: Arc<Mutex<VecDeque<LogEntry>>>,
+    sender: broadcast::Sender<LogEntry>,
+    /// Consumers of every entry, waited for when they fall behind.
+    consumers: Arc<Mutex<Vec<mpsc::Sender<LogEntry>>>>,
+}
+
+/// State of the follow task.
+#[derive(Debug, Default)]
+struct FollowState {
+    /// The log file being read, `None` until it exists.
+    file: Option<FollowedFile>,
+    lines: LineState,
+    /// Whether the last poll failed, to only warn once about a lasting error.
+    failing: bool,
+}
+
+/// Log file being read.
+#[derive(Debug)]
+struct FollowedFile {
+    file: File,
+    /// Identifier of the file on the file system, telling it apart from the one replacing it.
+    id: Option<u64>,
+    /// Number of bytes read from the file.
+    position: u64,
+}
+
+/// State of the lines read from the log file.
+#[derive(Debug)]
+struct LineState {
+    /// The end of the file not followed by a line break yet, up to `LOG_TAIL_MAX_LINE_SIZE`
+    /// bytes.
+    partial: Vec<u8>,
+    /// The sequence of the next entry.
+    sequence: u64,
+    /// The level of the last entry.
+    level: LogLevel,
+    /// Whether the lines present when the backend started were read.
+    caught_up: bool,
+}
+
+impl Default for LineState {
+    fn default() -> Self {
+        LineState {
+            partial: Vec::new(),
+            sequence: 0,
+            level: LogLevel::Info,
+            caught_up: false,
+        }
+    }
+}
+
+impl LogFollower {
+    /// Creates a follower, not reading the log file until it is started.
+    pub fn new(configuration: LogTailConfiguration) -> Self {
+        let (sender, _) = broadcast::channel(LOG_TAIL_CHANNEL_CAPACITY);
+
+        LogFollower {
+            backlog: Arc::new(Mutex::new(VecDeque::with_capacity(
+                configuration.backlog_size,
+            ))),
+            configuration,
+            sender,
+            consumers: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Starts following the log file in the background, from its start so that the backlog holds
+    /// its last lines. The entries of the lines already in the file are marked as replayed.
+    ///
+    /// # Returns
+    ///
+    /// The handle of the follow task, which never completes by itself.
+    pub fn start(&self) -> JoinHandle<()> {
+        let follower = self.clone();
+        info!(
+            "Following the log file {}",
+            follower.configuration.path.display()
+        );
+
+        tokio::spawn(async move { follower.follow().await })
+    }
+
+    /// Subscribes to the new log entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `backlog` - The maximum number of recent entries to return.
+    ///
+    /// # Returns
+    ///
+    /// The recent entries, oldest first, and the receiver of the following ones, without gap nor
+    /// duplicate between them.
+    pub fn subscribe(&self, backlog: usize) -> (Vec<LogEntry>, broadcast::Receiver<LogEntry>) {
+        let entries = self.lock_backlog();
+        let receiver = self.sender.subscribe();
+
+        let recent = entries
+            .iter()
+            .skip(entries.len().saturating_sub(backlog))
+            .cloned()
+            .collect();
+
+        (recent, receiver)
+    }
+
+    /// Consumes every new log entry, the follower waiting for the consumer when it falls behind
+    /// rather than skipping entries.
+    ///
+    /// # Returns
+    ///
+    /// The receiver of the entries, including the lines already in the log file when called
+    /// before the follower is started.
+    pub fn consume(&self) -> mpsc::Receiver<LogEntry> {
+        let (sender, receiver) = mpsc::channel(LOG_TAIL_CHANNEL_CAPACITY);
+        self.lock_consumers().push(sender);
+
+        receiver
+    }
+
+    /// Polls the log file until the task is aborted.
+    async fn follow(&self) {
+        let poll_interval = Duration::from_millis(self.configuration.poll_interval);
+        let mut state = FollowState::default();
+
+        loop {
+            match self.poll(&mut state).await {
+                Ok(()) => state.failing = false,
+                Err(err) => {
+                    if !state.failing {
+                        warn!(
+                            "Failed to read the log file {}: {err}",
+                            self.configuration.path.display()
+                        );
+                    }
+                    state.failing = true;
+                    state.file = None;
+                }
+            }
+            // The first poll reads the file up to its end, the next lines are written afterwards.
+            state.lines.caught_up = true;
+
+            sleep(poll_interval).await;
+        }
+    }
+
+    /// Reads the lines written to the log file since the last poll, then checks whether the file
+    /// was rotated or truncated.
+    async fn poll(&self, state: &mut FollowState) -> io::Result<()> {
+        let FollowState { file, lines, .. } = state;
+
+        if file.is_none() {
+            *file = match File::open(&self.configuration.path).await {
+                Ok(opened) => {
+                    let metadata = opened.metadata().await?;
+                    debug!("Opened the log file {}", self.configuration.path.display());
+                    Some(FollowedFile {
+                        file: opened,
+                        id: file_id(&metadata),
+                        position: 0,
+                    })
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+                Err(err) => return Err(err),
+            };
+        }
+        let Some(followed) = file.as_mut() else {
+            return Ok(());
+        };
+
+        self.read(followed, lines).await?;
+
+        match fs::metadata(&self.configuration.path).await {
+            Ok(metadata) if file_id(&metadata) != followed.id => {
+                debug!("The log file was rotated");
+                // Lines may have been written to the previous file since it was read.
+                self.read(followed, lines).await?;
+                self.flush(lines).await;
+                *file = None;
+            }
+            Ok(metadata) if metadata.len() < followed.position => {
+                debug!("The log file was truncated");
+                followed.file.rewind().await?;
+                followed.position = 0;
+                lines.partial.clear();
+            }
+            Ok(_) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                debug!("The log file was rotated");
+                self.flush(lines).await;
+                *file = None;
+            }
+            Err(err) => return Err(err),
+        }
+
+        Ok(())
+    }
+
+    /// Reads the file until its end by chunks, publishing the complete lines.
+    async fn read(&self, followed: &mut FollowedFile, lines: &mut LineState) -> io::Result<()> {
+        let mut buffer = vec![0; LOG_TAIL_READ_CHUNK_SIZE];
+
+        loop {
+            let read = followed.file.read(&mut buffer).await?;
+            if read == 0 {
+                return Ok(());
+            }
+            followed.position += read as u64;
+
+            // The lines are published from the chunk, which is only drained once.
+            let mut partial = std::mem::take(&mut lines.partial);
+            partial.extend_from_slice(&buffer[..read]);
+            let mut start = 0;
+            while let Some(offset) = partial[start..].iter().position(|byte| *byte == b'\n') {
+                let end = start + offset + 1;
+                self.publish(lines, &partial[start..end]).await;
+                start = end;
+            }
+            while partial.len() - start >= LOG_TAIL_MAX_LINE_SIZE {
+                let end = line_cut(&partial[start..], LOG_TAIL_MAX_LINE_SIZE) + start;
+                self.publish(lines, &partial[start..end]).await;
+                start = end;
+            }
+            partial.drain(..start);
+            lines.partial = partial;
+        }
+    }
+
+    /// Publishes the end of a file not followed by a line break, as the file is left.
+    async fn flush(&self, lines: &mut LineState) {
+        let line = std::mem::take(&mut lines.partial);
+        self.publish(lines, &line).await;
+    }
+
+    /// Publishes a line to the subscribers and the consumers, and keeps it in the backlog.
+    async fn publish(&self, lines: &mut LineState, line: &[u8]) {
+        let line = String::from_utf8_lossy(line);
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            return;
+        }
+
+        let mut entry = LogEntry::new(lines.sequence, String::from(line), lines.level);
+        entry.replayed = !lines.caught_up;
+        lines.sequence += 1;
+        lines.level = entry.level;
+
+        {
+            let mut backlog = self.lock_backlog();
+            if backlog.len() >= self.configuration.backlog_size {
+                backlog.pop_front();
+            }
+            backlog.push_back(entry.clone());
+        }
+        // Sending fails when no client is subscribed, the entry is then only kept in the backlog.
+        self.sender.send(entry.clone()).ok();
+
+        let consumers = self.lock_consumers().clone();
+        for consumer in consumers {
+            if consumer.send(entry.clone()).await.is_err() {
+                self.lock_consumers()
+                    .retain(|consumer| !consumer.is_closed());
+            }
+        }
+    }
+
+    fn lock_backlog(&self) -> MutexGuard<'_, VecDeque<LogEntry>> {
+        self.backlog
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn lock_consumers(&self) -> MutexGuard<'_, Vec<mpsc::Sender<LogEntry>>> {
+        self.consumers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Returns where to cut a line too long, at most `max_size` bytes from its start and not in the
+/// middle of a UTF-8 character.
+fn line_cut(line: &[u8], max_size: usize) -> usize {
+    let is_continuation = |byte: u8| byte & 0b1100_0000 == 0b1000_0000;
+
+    // The last character starting before the cut, a UTF-8 character having at most 4 bytes.
+    let Some(start) = (max_size.saturating_sub(4)..max_size)
+        .rev()
+        .find(|index| !is_continuation(line[*index]))
+    else {
+        return max_size;
+    };
+    let width = match line[start] {
+        0xf0.. => 4,
+        0xe0.. => 3,
+        0xc0.. => 2,
+        _ => 1,
+    };
+
+    if start == 0 || start + width <= max_size {
+        max_size
+    } else {
+        start
+    }
+}
+
+/// Returns the identifier of a file on the file system, if available on the platform.
+#[cfg(unix)]
+fn file_id(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(metadata.ino())
+}
+
+/// Returns the identifier of a file on the file system, if available on the platform.
+///
+/// Only the truncation and the removal of the log file are detected without it.
+#[cfg(not(unix))]
+fn file_id(_metadata: &Metadata) -> Option<u64> {
+    None
+}
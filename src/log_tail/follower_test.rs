@@ -0,0 +1,271 @@
+#[cfg(test)]
+mod tests {
+    use crate::log_tail::{
+        LOG_TAIL_MAX_LINE_SIZE, LOG_TAIL_READ_CHUNK_SIZE, LogEntry, LogFollower, LogLevel,
+        LogTailConfiguration,
+    };
+    use std::{
+        fs::{self, OpenOptions},
+        io::Write,
+        path::{Path, PathBuf},
+        time::Duration,
+    };
+    use tokio::{
+        sync::broadcast::Receiver,
+        time::{sleep, timeout},
+    };
+
+    /// Creates an empty directory for the log file of a test.
+    fn log_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("{name}-{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    fn follower(path: &Path, backlog_size: usize) -> LogFollower {
+        LogFollower::new(LogTailConfiguration {
+            path: path.to_path_buf(),
+            poll_interval: 20,
+            backlog_size,
+        })
+    }
+
+    fn append(path: &Path, content: &str) {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+    }
+
+    async fn next_line(receiver: &mut Receiver<LogEntry>) -> String {
+        timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap()
+            .line
+    }
+
+    #[tokio::test]
+    async fn test_replayed() {
+        let dir = log_dir("log-tail-replayed");
+        let path = dir.join("latest.log");
+        append(&path, "[12:00:00] [Server thread/INFO]: old\n");
+
+        let follower = follower(&path, 10);
+        let mut receiver = follower.consume();
+        let task = follower.start();
+        let entry = timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.message, "old");
+        assert!(entry.replayed);
+
+        sleep(Duration::from_millis(100)).await;
+        append(&path, "[12:00:01] [Server thread/INFO]: new\n");
+        let entry = timeout(Duration::from_secs(5), receiver.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.message, "new");
+        assert!(!entry.replayed);
+
+        task.abort();
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_backlog() {
+        let dir = log_dir("log-tail-backlog");
+        let path = dir.join("latest.log");
+        append(
+            &path,
+            "[12:00:00] [Server thread/INFO]: Line 0\n\
+             [12:00:01] [Server thread/INFO]: Line 1\n\
+             [12:00:02] [Server thread/ERROR]: Line 2\n\
+             \tat net.minecraft.server.Main.main(Main.java:1)\n",
+        );
+
+        let follower = follower(&path, 3);
+        let task = follower.start();
+        sleep(Duration::from_millis(200)).await;
+
+        let (recent, _) = follower.subscribe(10);
+        let lines: Vec<&str> = recent.iter().map(|entry| entry.message.as_str()).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "Line 1",
+                "Line 2",
+                "\tat net.minecraft.server.Main.main(Main.java:1)"
+            ]
+        );
+        assert_eq!(recent[0].sequence, 1);
+        assert_eq!(recent[2].level, LogLevel::Error);
+
+        let (recent, _) = follower.subscribe(1);
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].sequence, 3);
+
+        task.abort();
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_follow_new_lines() {
+        let dir = log_dir("log-tail-follow");
+        let path = dir.join("latest.log");
+
+        // The file does not exist until the server starts.
+        let follower = follower(&path, 10);
+        let task = follower.start();
+        let (recent, mut receiver) = follower.subscribe(10);
+        assert!(recent.is_empty());
+
+        append(
+            &path,
+            "[12:00:00] [Server thread/INFO]: Steve joined the game\n",
+        );
+        assert_eq!(
+            next_line(&mut receiver).await,
+            "[12:00:00] [Server thread/INFO]: Steve joined the game"
+        );
+
+        // A line is only published once complete.
+        append(&path, "[12:00:01] [Server thread/INFO]: <Steve> Hel");
+        sleep(Duration::from_millis(100)).await;
+        assert!(receiver.is_empty());
+        append(&path, "lo\r\n");
+        assert_eq!(
+            next_line(&mut receiver).await,
+            "[12:00:01] [Server thread/INFO]: <Steve> Hello"
+        );
+
+        task.abort();
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_truncation() {
+        let dir = log_dir("log-tail-truncation");
+        let path = dir.join("latest.log");
+        append(
+            &path,
+            "[12:00:00] [Server thread/INFO]: A long line before the truncation\n",
+        );
+
+        let follower = follower(&path, 10);
+        let (_, mut receiver) = follower.subscribe(0);
+        let task = follower.start();
+        next_line(&mut receiver).await;
+
+        fs::write(&path, "").unwrap();
+        sleep(Duration::from_millis(100)).await;
+        append(&path, "[12:00:01] [Server thread/INFO]: After\n");
+        assert_eq!(
+            next_line(&mut receiver).await,
+            "[12:00:01] [Server thread/INFO]: After"
+        );
+
+        task.abort();
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_rotation() {
+        let dir = log_dir("log-tail-rotation");
+        let path = dir.join("latest.log");
+        append(&path, "[12:00:00] [Server thread/INFO]: Before\n");
+
+        let follower = follower(&path, 10);
+        let (_, mut receiver) = follower.subscribe(0);
+        let task = follower.start();
+        assert_eq!(
+            next_line(&mut receiver).await,
+            "[12:00:00] [Server thread/INFO]: Before"
+        );
+
+        // The server renames the file before compressing it, then creates a new one with more
+        // lines than the previous one had.
+        append(
+            &path,
+            "[12:00:01] [Server thread/INFO]: Stopping the server\n",
+        );
+        fs::rename(&path, dir.join("2026-10-18-1.log")).unwrap();
+        append(
+            &path,
+            "[12:00:02] [main/INFO]: Loaded 7 recipes\n\
+             [12:00:03] [Server thread/INFO]: Starting minecraft server version 1.21.4\n",
+        );
+
+        assert_eq!(
+            next_line(&mut receiver).await,
+            "[12:00:01] [Server thread/INFO]: Stopping the server"
+        );
+        assert_eq!(
+            next_line(&mut receiver).await,
+            "[12:00:02] [main/INFO]: Loaded 7 recipes"
+        );
+        assert_eq!(
+            next_line(&mut receiver).await,
+            "[12:00:03] [Server thread/INFO]: Starting minecraft server version 1.21.4"
+        );
+
+        task.abort();
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_large_file() {
+        let dir = log_dir("log-tail-large");
+        let path = dir.join("latest.log");
+        let line = "[12:00:00] [Server thread/INFO]: Some line of the log file\n";
+        let count = 4 * LOG_TAIL_READ_CHUNK_SIZE / line.len();
+        append(&path, &line.repeat(count));
+        // The end of the file has no line break.
+        append(&path, &"x".repeat(LOG_TAIL_MAX_LINE_SIZE + 10));
+
+        let follower = follower(&path, 2);
+        let task = follower.start();
+        sleep(Duration::from_millis(200)).await;
+
+        // The lines are read by chunks, the one without line break being split once too long.
+        let (recent, _) = follower.subscribe(2);
+        assert_eq!(recent[0].sequence, count as u64 - 1);
+        assert_eq!(recent[1].sequence, count as u64);
+        assert_eq!(recent[1].line.len(), LOG_TAIL_MAX_LINE_SIZE);
+
+        task.abort();
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_long_line_char_boundary() {
+        let dir = log_dir("log-tail-char-boundary");
+        let path = dir.join("latest.log");
+        // The maximum size falls in the middle of the 'é', the line not being complete.
+        append(
+            &path,
+            &format!("{}éb", "a".repeat(LOG_TAIL_MAX_LINE_SIZE - 1)),
+        );
+
+        let follower = follower(&path, 2);
+        let task = follower.start();
+        sleep(Duration::from_millis(200)).await;
+
+        let (recent, mut receiver) = follower.subscribe(2);
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].line.len(), LOG_TAIL_MAX_LINE_SIZE - 1);
+        assert!(recent[0].line.bytes().all(|byte| byte == b'a'));
+
+        append(&path, "\n");
+        assert_eq!(next_line(&mut receiver).await, "éb");
+
+        task.abort();
+        fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,15 @@
+pub mod configuration;
+pub mod configuration_test;
+pub mod entry;
+pub mod entry_test;
+pub mod error;
+pub mod filter;
+pub mod filter_test;
+pub mod follower;
+pub mod follower_test;
+
+pub use configuration::*;
+pub use entry::*;
+pub use error::*;
+pub use filter::*;
+pub use follower::*;
@@ -1,12 +1,30 @@
 mod api;
 mod app;
+mod game_events;
+mod log_tail;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod query;
+mod rate_limit;
 mod rcon;
+mod server;
+mod slack;
+mod slp;
 mod telemetry;
+mod webhooks;
 
-use api::RconManagedState;
+use api::{
+    GameEventsManagedState, HooksManagedState, LogTailManagedState, RconManagedState,
+    ServerManagedState, SlackManagedState,
+};
 use app::ui;
 use dotenvy::dotenv;
-use rocket::{Build, Rocket, launch, routes};
+use rate_limit::{RateLimitFairing, RateLimiter};
+use rocket::{Build, Rocket, catchers, fairing::AdHoc, launch, routes};
+use std::time::Duration;
+use telemetry::RequestIdFairing;
+use tracing::error;
+use webhooks::WebhookDispatcher;
 
 #[launch]
 /// Sets up a web server using the Rocket framework and mounts routes for serving
@@ -19,49 +37,206 @@ async fn rocket() -> Rocket<Build> {
     // Load environment
     dotenv().ok();
 
-    // Initialize telemetry
-    cfg_if::cfg_if! {
-        if #[cfg(feature = "opentelemetry")] {
-            use opentelemetry_sdk::trace::SdkTracerProvider;
-
-            let tracer_provider: Option<SdkTracerProvider> = {
-                cfg_if::cfg_if! {
-                    if #[cfg(not(test))] {
-                        telemetry::init_telemetry()
-                    } else {
-                        // Do not enable telemetry in tests.
-                        None
-                    }
-                }
-            };
+    // Initialize telemetry, logs are enabled whatever the features
+    #[allow(unused_variables)]
+    let telemetry = {
+        cfg_if::cfg_if! {
+            if #[cfg(not(test))] {
+                telemetry::init_telemetry()
+            } else {
+                // Do not enable telemetry in tests.
+                telemetry::Telemetry::default()
+            }
         }
-    }
-
-    // Rcon client
-    let rcon = RconManagedState::default();
+    };
 
     // Prepare the webserver
     #[allow(unused_mut)]
     let mut rocket = rocket::build()
-        .manage(rcon)
-        .mount("/api", routes![api::handle_rcon])
-        .mount("/", routes![ui::files]);
+        .attach(AdHoc::try_on_ignite("Webhooks", |rocket| async {
+            match WebhookDispatcher::try_new() {
+                Ok(webhooks) => Ok(rocket.manage(webhooks)),
+                Err(err) => {
+                    error!("Failed to initialize the webhooks: {err}");
+                    Err(rocket)
+                }
+            }
+        }))
+        .attach(AdHoc::try_on_ignite("Webhook triggers", |rocket| async {
+            match HooksManagedState::try_new() {
+                Ok(hooks) => Ok(rocket.manage(hooks)),
+                Err(err) => {
+                    error!("Failed to initialize the webhook triggers: {err}");
+                    Err(rocket)
+                }
+            }
+        }))
+        .attach(AdHoc::try_on_ignite("Slack", |rocket| async {
+            match SlackManagedState::try_new() {
+                Ok(slack) => Ok(rocket.manage(slack)),
+                Err(err) => {
+                    error!("Failed to initialize the Slack command: {err}");
+                    Err(rocket)
+                }
+            }
+        }))
+        .attach(AdHoc::try_on_ignite("RCON state", |rocket| async {
+            let Some(webhooks) = rocket.state::<WebhookDispatcher>() else {
+                return Err(rocket);
+            };
+            match RconManagedState::try_new(webhooks.clone()) {
+                Ok(rcon) => Ok(rocket.manage(rcon)),
+                Err(err) => {
+                    error!("Failed to initialize the RCON state: {err}");
+                    Err(rocket)
+                }
+            }
+        }))
+        .attach(AdHoc::on_liftoff("RCON console", |rocket| {
+            Box::pin(async move {
+                let Some(rcon) = rocket.state::<RconManagedState>() else {
+                    return;
+                };
+                if let Err(err) = rcon.client.start_console().await {
+                    error!("Failed to start the server process: {err}");
+                }
+            })
+        }))
+        .attach(AdHoc::on_liftoff("RCON circuit breaker probes", |rocket| {
+            Box::pin(async move {
+                let Some(rcon) = rocket.state::<RconManagedState>() else {
+                    return;
+                };
+                let client = rcon.client.clone();
+                let probe_interval =
+                    Duration::from_millis(client.circuit_breaker().configuration().probe_interval);
+
+                tokio::spawn(async move {
+                    loop {
+                        tokio::time::sleep(probe_interval).await;
+                        client.probe_circuits().await;
+                    }
+                });
+            })
+        }))
+        .attach(AdHoc::try_on_ignite("Server supervisor", |rocket| async {
+            let Some(rcon) = rocket.state::<RconManagedState>() else {
+                return Err(rocket);
+            };
+            match ServerManagedState::try_new(rcon.client.clone()) {
+                Ok(server) => Ok(rocket.manage(server)),
+                Err(err) => {
+                    error!("Failed to initialize the server supervisor: {err}");
+                    Err(rocket)
+                }
+            }
+        }))
+        .attach(AdHoc::on_liftoff("Server autostart", |rocket| {
+            Box::pin(async move {
+                let Some(supervisor) = rocket
+                    .state::<ServerManagedState>()
+                    .and_then(|server| server.supervisor.as_ref())
+                    .filter(|supervisor| supervisor.configuration().autostart)
+                else {
+                    return;
+                };
+                if let Err(err) = supervisor.start().await {
+                    error!("Failed to start the Minecraft server: {err}");
+                }
+            })
+        }))
+        .attach(AdHoc::on_shutdown("Server shutdown", |rocket| {
+            Box::pin(async move {
+                let Some(supervisor) = rocket
+                    .state::<ServerManagedState>()
+                    .and_then(|server| server.supervisor.as_ref())
+                    .filter(|supervisor| supervisor.status().is_active())
+                else {
+                    return;
+                };
+                if let Err(err) = supervisor.stop().await {
+                    error!("Failed to stop the Minecraft server: {err}");
+                }
+            })
+        }))
+        .attach(AdHoc::try_on_ignite("Log tail", |rocket| async {
+            match LogTailManagedState::try_new() {
+                Ok(logs) => Ok(rocket.manage(logs)),
+                Err(err) => {
+                    error!("Failed to initialize the log tail: {err}");
+                    Err(rocket)
+                }
+            }
+        }))
+        .attach(AdHoc::try_on_ignite("Game events", |rocket| async {
+            match GameEventsManagedState::try_new() {
+                Ok(events) => Ok(rocket.manage(events)),
+                Err(err) => {
+                    error!("Failed to initialize the game events: {err}");
+                    Err(rocket)
+                }
+            }
+        }))
+        .attach(AdHoc::on_liftoff("Log tail follower", |rocket| {
+            Box::pin(async move {
+                let Some(follower) = rocket
+                    .state::<LogTailManagedState>()
+                    .and_then(|logs| logs.follower.as_ref())
+                else {
+                    return;
+                };
+                // Consume the entries before the log file is read so that no game event is missed.
+                if let Some(store) = rocket
+                    .state::<GameEventsManagedState>()
+                    .and_then(|events| events.store.as_ref())
+                {
+                    store.follow(follower.consume());
+                }
+                follower.start();
+            })
+        }))
+        .attach(AdHoc::try_on_ignite("Rate limiter", |rocket| async {
+            match RateLimiter::try_new() {
+                Ok(rate_limiter) => Ok(rocket.manage(rate_limiter)),
+                Err(err) => {
+                    error!("Failed to initialize the rate limiter: {err}");
+                    Err(rocket)
+                }
+            }
+        }))
+        .attach(RateLimitFairing)
+        .attach(RequestIdFairing)
+        .mount(
+            "/api",
+            routes![
+                api::handle_rcon,
+                api::handle_rcon_validate,
+                api::handle_queue,
+                api::handle_health,
+                api::handle_health_ready,
+                api::handle_status,
+                api::handle_query,
+                api::handle_server_start,
+                api::handle_server_stop,
+                api::handle_server_restart,
+                api::handle_server_state,
+                api::handle_logs_stream,
+                api::handle_events,
+                api::handle_hook,
+                api::handle_slack_command
+            ],
+        )
+        .mount("/", routes![ui::files])
+        .register("/api", catchers![rate_limit::too_many_requests]);
 
     // Attach telemetry request and shutdown fairings if telemetry is enabled
     cfg_if::cfg_if! {
         if #[cfg(feature = "opentelemetry")] {
             use crate::telemetry::TelemetryRequestFairing;
-            use rocket::fairing::AdHoc;
-
-            if let Some(provider) = tracer_provider {
-                rocket = rocket.attach(AdHoc::on_shutdown("Telemetry provider shutdown", |_| {
-                    Box::pin(async move {
-                        if let Err(err) = provider.shutdown() {
-                            eprintln!("Failed to shutdown telemetry provider: {err:?}");
-                        }
-                    })
-                }))
-            }
+
+            rocket = rocket.attach(AdHoc::on_shutdown("Telemetry provider shutdown", |_| {
+                Box::pin(async move { telemetry.shutdown() })
+            }));
             rocket = rocket.attach(TelemetryRequestFairing);
         }
     }
@@ -73,6 +248,9 @@ async fn rocket() -> Rocket<Build> {
 
             // Metrics
             let prometheus = PrometheusMetrics::new();
+            if let Err(err) = metrics::register(prometheus.registry()) {
+                error!("Failed to register the application metrics: {err}");
+            }
 
             // Set the metrics endpoint
             rocket = rocket
@@ -84,7 +262,12 @@ async fn rocket() -> Rocket<Build> {
     // Attach OpenAPI documentation ans Swagger UI if the feature is enabled
     cfg_if::cfg_if! {
         if #[cfg(feature = "swagger")] {
-            use api::ApiRconResponse;
+            use api::{
+                ApiCircuitStatus, ApiErrorBody, ApiGameEvent, ApiGameEventDetails, ApiGameEvents, ApiHealth, ApiHookResponse, ApiLiveness, ApiLogEntry, ApiMotd, ApiMotdSegment, ApiQueryStatus,
+                ApiQueueStatus, ApiRconResponse, ApiRconValidation, ApiServerLogLine,
+                ApiServerPlayer, ApiServerPlayers, ApiServerState, ApiServerStatus,
+                ApiServerVersion, ApiSlackReply,
+            };
             use utoipa::OpenApi;
             use utoipa_swagger_ui::{Config as SwaggerConfig, SwaggerUi};
 
@@ -96,10 +279,45 @@ async fn rocket() -> Rocket<Build> {
                 ),
                 paths(
                     api::handle_rcon,
+                    api::handle_rcon_validate,
+                    api::handle_queue,
+                    api::handle_health,
+                    api::handle_health_ready,
+                    api::handle_status,
+                    api::handle_query,
+                    api::handle_server_start,
+                    api::handle_server_stop,
+                    api::handle_server_restart,
+                    api::handle_server_state,
+                    api::handle_logs_stream,
+                    api::handle_events,
+                    api::handle_hook,
+                    api::handle_slack_command,
                 ),
                 components(
                     schemas(
+                        ApiCircuitStatus,
+                        ApiErrorBody,
+                        ApiGameEvent,
+                        ApiGameEventDetails,
+                        ApiGameEvents,
+                        ApiHealth,
+                        ApiHookResponse,
+                        ApiLiveness,
+                        ApiLogEntry,
+                        ApiMotd,
+                        ApiMotdSegment,
+                        ApiQueryStatus,
+                        ApiQueueStatus,
                         ApiRconResponse,
+                        ApiRconValidation,
+                        ApiServerLogLine,
+                        ApiServerPlayer,
+                        ApiServerPlayers,
+                        ApiServerState,
+                        ApiServerStatus,
+                        ApiServerVersion,
+                        ApiSlackReply,
                     )
                 )
             )]
@@ -131,7 +349,7 @@ async fn rocket() -> Rocket<Build> {
 
 #[cfg(test)]
 mod tests {
-    use rocket::http::Status;
+    use rocket::http::{ContentType, Header, Status};
     use rocket::local::asynchronous::Client;
     use serial_test::serial;
 
@@ -145,4 +363,415 @@ mod tests {
 
         assert_eq!(response.status(), Status::Ok);
     }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn api_rcon_too_large() {
+        let rocket = crate::rocket().await;
+        let client = Client::tracked(rocket).await.unwrap();
+
+        let command = format!("give @p minecraft:stone{}", "{}".repeat(1000));
+        let response = client.post("/api/rcon").body(command).dispatch().await;
+
+        assert_eq!(response.status(), Status::PayloadTooLarge);
+
+        let request_id = response.headers().get_one("X-Request-Id").map(String::from);
+        let body: serde_json::Value = response.into_json().await.unwrap();
+        assert!(request_id.is_some());
+        assert_eq!(body["request_id"].as_str(), request_id.as_deref());
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn api_rcon_request_id() {
+        let rocket = crate::rocket().await;
+        let client = Client::tracked(rocket).await.unwrap();
+
+        let response = client
+            .post("/api/rcon")
+            .header(Header::new("X-Request-Id", "console-42"))
+            .body("help")
+            .dispatch()
+            .await;
+
+        assert_eq!(
+            response.headers().get_one("X-Request-Id"),
+            Some("console-42")
+        );
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn api_rcon_validate() {
+        let rocket = crate::rocket().await;
+        let client = Client::tracked(rocket).await.unwrap();
+
+        let response = client
+            .post("/api/rcon/validate")
+            .body("say Hello")
+            .dispatch()
+            .await;
+
+        assert_eq!(response.status(), Status::Ok);
+
+        let validation: serde_json::Value = response.into_json().await.unwrap();
+        assert_eq!(validation["valid"], true);
+        assert_eq!(validation["size"], 9);
+        assert_eq!(validation["commands"][0], "say Hello");
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn api_status_unreachable() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port().to_string();
+        drop(listener);
+
+        temp_env::async_with_vars(
+            [
+                ("SLP_HOST", Some("127.0.0.1")),
+                ("SLP_PORT", Some(port.as_str())),
+            ],
+            async {
+                let rocket = crate::rocket().await;
+                let client = Client::tracked(rocket).await.unwrap();
+
+                let response = client.get("/api/status").dispatch().await;
+
+                assert_eq!(response.status(), Status::BadGateway);
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn api_server_state_not_configured() {
+        temp_env::async_with_vars([("SERVER_COMMAND", None::<&str>)], async {
+            let rocket = crate::rocket().await;
+            let client = Client::tracked(rocket).await.unwrap();
+
+            let response = client.get("/api/server/state").dispatch().await;
+
+            assert_eq!(response.status(), Status::ServiceUnavailable);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn api_logs_stream_not_configured() {
+        temp_env::async_with_vars(
+            [
+                ("LOG_TAIL_PATH", None::<&str>),
+                ("SERVER_WORKING_DIR", None),
+            ],
+            async {
+                let rocket = crate::rocket().await;
+                let client = Client::tracked(rocket).await.unwrap();
+
+                let response = client.get("/api/logs/stream").dispatch().await;
+
+                assert_eq!(response.status(), Status::ServiceUnavailable);
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn api_logs_stream_invalid_filter() {
+        let path = std::env::temp_dir().join("api-logs-stream-latest.log");
+
+        temp_env::async_with_vars([("LOG_TAIL_PATH", path.to_str())], async {
+            let rocket = crate::rocket().await;
+            let client = Client::tracked(rocket).await.unwrap();
+
+            let response = client
+                .get("/api/logs/stream?filter=joined(")
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::BadRequest);
+
+            let response = client.get("/api/logs/stream?level=loud").dispatch().await;
+            assert_eq!(response.status(), Status::BadRequest);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn api_events() {
+        let path = std::env::temp_dir().join("api-events-latest.log");
+        std::fs::write(
+            &path,
+            "[12:00:00] [Server thread/INFO]: Steve joined the game\n",
+        )
+        .unwrap();
+
+        temp_env::async_with_vars([("LOG_TAIL_PATH", path.to_str())], async {
+            let rocket = crate::rocket().await;
+            let client = Client::tracked(rocket).await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+            let response = client
+                .get("/api/events?kind=player_joined")
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Ok);
+            let body: serde_json::Value = response.into_json().await.unwrap();
+            assert_eq!(body["events"][0]["type"], "player_joined");
+            assert_eq!(body["events"][0]["player"], "Steve");
+            assert_eq!(body["online_players"][0], "Steve");
+
+            let response = client.get("/api/events?kind=explosion").dispatch().await;
+            assert_eq!(response.status(), Status::BadRequest);
+        })
+        .await;
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn api_hooks() {
+        let path = std::env::temp_dir().join(format!("api-hooks-{}.json", std::process::id()));
+        std::fs::write(
+            &path,
+            r#"{"triggers": [{"name": "deploy", "secret": "s3cr3t", "commands": ["say Deploying {{version}}", "list"]}]}"#,
+        )
+        .unwrap();
+
+        temp_env::async_with_vars([("WEBHOOKS_PATH", path.to_str())], async {
+            let rocket = crate::rocket().await;
+            let client = Client::tracked(rocket).await.unwrap();
+            let body = r#"{"version": "1.4.2"}"#;
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs();
+            let signature = crate::webhooks::sign_webhook("s3cr3t", timestamp, body.as_bytes());
+
+            let request = || {
+                client
+                    .post("/api/hooks/deploy")
+                    .header(Header::new("X-Webhook-Timestamp", timestamp.to_string()))
+                    .header(Header::new("X-Webhook-Signature", signature.clone()))
+                    .body(body)
+            };
+
+            let response = request().dispatch().await;
+            assert_eq!(response.status(), Status::Ok);
+            let hook: serde_json::Value = response.into_json().await.unwrap();
+            assert_eq!(hook["trigger"], "deploy");
+            assert_eq!(hook["responses"].as_array().unwrap().len(), 2);
+            assert_eq!(hook["responses"][1]["payload"], "Output of list");
+
+            // The same request cannot be replayed.
+            let response = request().dispatch().await;
+            assert_eq!(response.status(), Status::Unauthorized);
+
+            let response = client
+                .post("/api/hooks/deploy")
+                .header(Header::new("X-Webhook-Timestamp", timestamp.to_string()))
+                .header(Header::new("X-Webhook-Signature", "sha256=00"))
+                .body(body)
+                .dispatch()
+                .await;
+            assert_eq!(response.status(), Status::Unauthorized);
+
+            let response = client.post("/api/hooks/backup").body(body).dispatch().await;
+            assert_eq!(response.status(), Status::NotFound);
+        })
+        .await;
+
+        std::fs::remove_file(path).ok();
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn api_hooks_not_configured() {
+        temp_env::async_with_vars([("WEBHOOKS_PATH", None::<&str>)], async {
+            let rocket = crate::rocket().await;
+            let client = Client::tracked(rocket).await.unwrap();
+
+            let response = client.post("/api/hooks/deploy").dispatch().await;
+
+            assert_eq!(response.status(), Status::ServiceUnavailable);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn api_slack_command() {
+        temp_env::async_with_vars(
+            [
+                ("SLACK_SIGNING_SECRET", Some("s3cr3t")),
+                ("SLACK_ALLOWED_COMMANDS", Some("list,kick")),
+                ("SLACK_RESPONSE_TYPE", Some("in_channel")),
+            ],
+            async {
+                let rocket = crate::rocket().await;
+                let client = Client::tracked(rocket).await.unwrap();
+                let timestamp = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                let request = |body: &'static str, signature: String| {
+                    client
+                        .post("/api/slack/command")
+                        .header(ContentType::Form)
+                        .header(Header::new(
+                            "X-Slack-Request-Timestamp",
+                            timestamp.to_string(),
+                        ))
+                        .header(Header::new("X-Slack-Signature", signature))
+                        .body(body)
+                };
+                let sign = |body: &str| {
+                    crate::slack::sign_slack_request("s3cr3t", timestamp, body.as_bytes())
+                };
+
+                let body = "command=%2Fmc&text=list&user_id=U2147483697&user_name=alex";
+                let response = request(body, sign(body)).dispatch().await;
+                assert_eq!(response.status(), Status::Ok);
+                let reply: serde_json::Value = response.into_json().await.unwrap();
+                assert_eq!(reply["response_type"], "in_channel");
+                assert_eq!(reply["text"], "```\nOutput of list\n```");
+
+                let body = "command=%2Fmc&text=stop&user_id=U2147483697&user_name=alex";
+                let response = request(body, sign(body)).dispatch().await;
+                assert_eq!(response.status(), Status::Ok);
+                let reply: serde_json::Value = response.into_json().await.unwrap();
+                assert_eq!(reply["response_type"], "ephemeral");
+                assert!(
+                    reply["text"]
+                        .as_str()
+                        .unwrap()
+                        .contains("'stop' is not allowed")
+                );
+
+                let response = request(body, String::from("v0=00")).dispatch().await;
+                assert_eq!(response.status(), Status::Unauthorized);
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn api_slack_command_multiline() {
+        temp_env::async_with_vars(
+            [
+                ("SLACK_SIGNING_SECRET", Some("s3cr3t")),
+                ("SLACK_ALLOWED_COMMANDS", Some("list")),
+                ("RCON_SPLIT_LONG_COMMANDS", Some("true")),
+            ],
+            async {
+                let rocket = crate::rocket().await;
+                let client = Client::tracked(rocket).await.unwrap();
+                let timestamp = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+
+                // An allowed command followed by another one on a new line.
+                let body = "command=%2Fmc&text=list%0Aop%20Mallory&user_id=U2147483697";
+                let response = client
+                    .post("/api/slack/command")
+                    .header(ContentType::Form)
+                    .header(Header::new(
+                        "X-Slack-Request-Timestamp",
+                        timestamp.to_string(),
+                    ))
+                    .header(Header::new(
+                        "X-Slack-Signature",
+                        crate::slack::sign_slack_request("s3cr3t", timestamp, body.as_bytes()),
+                    ))
+                    .body(body)
+                    .dispatch()
+                    .await;
+                assert_eq!(response.status(), Status::Ok);
+                let reply: serde_json::Value = response.into_json().await.unwrap();
+                assert_eq!(reply["response_type"], "ephemeral");
+                assert!(reply["text"].as_str().unwrap().contains("single line"));
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn api_slack_command_not_configured() {
+        temp_env::async_with_vars([("SLACK_SIGNING_SECRET", None::<&str>)], async {
+            let rocket = crate::rocket().await;
+            let client = Client::tracked(rocket).await.unwrap();
+
+            let response = client.post("/api/slack/command").dispatch().await;
+
+            assert_eq!(response.status(), Status::ServiceUnavailable);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn api_events_not_configured() {
+        temp_env::async_with_vars(
+            [
+                ("LOG_TAIL_PATH", None::<&str>),
+                ("SERVER_WORKING_DIR", None),
+            ],
+            async {
+                let rocket = crate::rocket().await;
+                let client = Client::tracked(rocket).await.unwrap();
+
+                let response = client.get("/api/events").dispatch().await;
+
+                assert_eq!(response.status(), Status::ServiceUnavailable);
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn api_rate_limited() {
+        temp_env::async_with_vars(
+            [
+                ("RATE_LIMIT_CAPACITY", Some("1")),
+                ("RATE_LIMIT_REFILL_RATE", Some("0")),
+                ("RATE_LIMIT_CLIENTS", None),
+                ("SERVER_COMMAND", None),
+            ],
+            async {
+                let rocket = crate::rocket().await;
+                let client = Client::tracked(rocket).await.unwrap();
+
+                let response = client.get("/api/server/state").dispatch().await;
+                assert_eq!(response.status(), Status::ServiceUnavailable);
+
+                // An unknown API token does not give the client a fresh bucket.
+                let response = client
+                    .get("/api/server/state")
+                    .header(Header::new("X-Api-Key", "random-token"))
+                    .dispatch()
+                    .await;
+                assert_eq!(response.status(), Status::TooManyRequests);
+
+                let request_id = response.headers().get_one("X-Request-Id").map(String::from);
+                let body: serde_json::Value = response.into_json().await.unwrap();
+                assert!(request_id.is_some());
+                assert_eq!(body["request_id"].as_str(), request_id.as_deref());
+                assert!(
+                    body["error"]
+                        .as_str()
+                        .unwrap()
+                        .starts_with("Too many requests")
+                );
+            },
+        )
+        .await;
+    }
 }
@@ -0,0 +1,125 @@
+use rocket_prometheus::prometheus::{
+    IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+};
+use std::sync::LazyLock;
+
+/// Number of requests checked by the rate limiter, by route and outcome (`allowed` or `limited`).
+pub static RATE_LIMIT_REQUESTS_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "rate_limit_requests_total",
+            "Number of requests checked by the rate limiter",
+        ),
+        &["route", "outcome"],
+    )
+    .expect("Invalid rate limit metric definition")
+});
+
+/// Number of RCON commands being executed, by target.
+pub static RCON_QUEUE_RUNNING: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    IntGaugeVec::new(
+        Opts::new(
+            "rcon_queue_running",
+            "Number of RCON commands being executed",
+        ),
+        &["target"],
+    )
+    .expect("Invalid RCON queue metric definition")
+});
+
+/// Number of RCON commands waiting for their turn, by target.
+pub static RCON_QUEUE_WAITING: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    IntGaugeVec::new(
+        Opts::new(
+            "rcon_queue_waiting",
+            "Number of RCON commands waiting for their turn",
+        ),
+        &["target"],
+    )
+    .expect("Invalid RCON queue metric definition")
+});
+
+/// State of the RCON circuit of a target: 0 when closed, 1 when half-open, 2 when open.
+pub static RCON_CIRCUIT_STATE: LazyLock<IntGaugeVec> = LazyLock::new(|| {
+    IntGaugeVec::new(
+        Opts::new(
+            "rcon_circuit_state",
+            "State of the RCON circuit: 0 when closed, 1 when half-open, 2 when open",
+        ),
+        &["target"],
+    )
+    .expect("Invalid RCON circuit metric definition")
+});
+
+/// Number of times the RCON circuit of a target opened.
+pub static RCON_CIRCUIT_OPENED_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "rcon_circuit_opened_total",
+            "Number of times the RCON circuit opened",
+        ),
+        &["target"],
+    )
+    .expect("Invalid RCON circuit metric definition")
+});
+
+/// Number of game events recognized in the server logs, by kind.
+pub static GAME_EVENTS_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "game_events_total",
+            "Number of game events recognized in the server logs",
+        ),
+        &["kind"],
+    )
+    .expect("Invalid game events metric definition")
+});
+
+/// Total lateness reported by the lag warnings of the server, in milliseconds.
+pub static GAME_LAG_BEHIND_MILLISECONDS_TOTAL: LazyLock<IntCounter> = LazyLock::new(|| {
+    IntCounter::new(
+        "game_lag_behind_milliseconds_total",
+        "Total lateness reported by the lag warnings of the server, in milliseconds",
+    )
+    .expect("Invalid game events metric definition")
+});
+
+/// Number of players online according to the server logs.
+pub static GAME_PLAYERS_ONLINE: LazyLock<IntGauge> = LazyLock::new(|| {
+    IntGauge::new(
+        "game_players_online",
+        "Number of players online according to the server logs",
+    )
+    .expect("Invalid game events metric definition")
+});
+
+/// Number of webhook deliveries, by endpoint and outcome.
+pub static WEBHOOK_DELIVERIES_TOTAL: LazyLock<IntCounterVec> = LazyLock::new(|| {
+    IntCounterVec::new(
+        Opts::new(
+            "webhook_deliveries_total",
+            "Number of webhook deliveries, once delivered or given up",
+        ),
+        &["endpoint", "outcome"],
+    )
+    .expect("Invalid webhook metric definition")
+});
+
+/// Registers the application metrics in the Prometheus registry.
+///
+/// # Arguments
+///
+/// * `registry` - The registry exposed by the `/metrics` endpoint.
+pub fn register(registry: &Registry) -> Result<(), rocket_prometheus::prometheus::Error> {
+    registry.register(Box::new(RATE_LIMIT_REQUESTS_TOTAL.clone()))?;
+    registry.register(Box::new(RCON_QUEUE_RUNNING.clone()))?;
+    registry.register(Box::new(RCON_QUEUE_WAITING.clone()))?;
+    registry.register(Box::new(RCON_CIRCUIT_STATE.clone()))?;
+    registry.register(Box::new(RCON_CIRCUIT_OPENED_TOTAL.clone()))?;
+    registry.register(Box::new(GAME_EVENTS_TOTAL.clone()))?;
+    registry.register(Box::new(GAME_LAG_BEHIND_MILLISECONDS_TOTAL.clone()))?;
+    registry.register(Box::new(GAME_PLAYERS_ONLINE.clone()))?;
+    registry.register(Box::new(WEBHOOK_DELIVERIES_TOTAL.clone()))?;
+
+    Ok(())
+}
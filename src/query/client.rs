@@ -0,0 +1,214 @@
+use super::{
+    QueryBasicStat, QueryConfiguration, QueryError, QueryFullStat, QueryRequest, QueryResponse,
+};
+use rand::RngExt;
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+use tokio::{
+    net::{UdpSocket, lookup_host},
+    time::timeout,
+};
+use tracing::{Span, debug, info, warn};
+
+/// Maximum size of a datagram, large enough for the full stat of a crowded server.
+const MAX_QUERY_DATAGRAM_SIZE: usize = 65535;
+
+#[derive(Debug, Clone)]
+pub struct QueryClient;
+
+impl QueryClient {
+    /// Gets the basic stat of the server: the MOTD, the game type, the map and the number of
+    /// players.
+    ///
+    /// # Returns:
+    ///
+    /// The basic stat of the server.
+    #[tracing::instrument(
+        name = "Query.BasicStat",
+        skip(self),
+        fields(
+            app.component = "query",
+            server.address = tracing::field::Empty,
+            server.port = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+        )
+    )]
+    pub async fn basic_stat(&self) -> Result<QueryBasicStat, QueryError> {
+        let payload = Self::stat(false).await?;
+
+        QueryBasicStat::try_from_payload(&payload).inspect_err(Self::record_error)
+    }
+
+    /// Gets the full stat of the server: the basic stat plus the version, the plugins and the
+    /// names of the online players.
+    ///
+    /// # Returns:
+    ///
+    /// The full stat of the server.
+    #[tracing::instrument(
+        name = "Query.FullStat",
+        skip(self),
+        fields(
+            app.component = "query",
+            server.address = tracing::field::Empty,
+            server.port = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+        )
+    )]
+    pub async fn full_stat(&self) -> Result<QueryFullStat, QueryError> {
+        let payload = Self::stat(true).await?;
+
+        QueryFullStat::try_from_payload(&payload).inspect_err(Self::record_error)
+    }
+
+    /// Gets the challenge token of a new session, then requests the stat with it.
+    ///
+    /// # Returns:
+    ///
+    /// The payload of the stat response.
+    async fn stat(full: bool) -> Result<Vec<u8>, QueryError> {
+        let configuration = QueryConfiguration::try_new().inspect_err(Self::record_error)?;
+
+        let span = Span::current();
+        span.record("server.address", configuration.host.as_str());
+        span.record("server.port", configuration.port);
+
+        async {
+            info!("Querying '{}'...", configuration.address());
+            let socket = Self::connect(&configuration).await?;
+            let session_id = rand::rng().random::<i32>();
+
+            let handshake = QueryRequest::handshake(session_id);
+            let challenge_token = Self::request(&socket, &handshake, &configuration)
+                .await?
+                .challenge_token()?;
+
+            let request = if full {
+                QueryRequest::full_stat(session_id, challenge_token)
+            } else {
+                QueryRequest::basic_stat(session_id, challenge_token)
+            };
+            let response = Self::request(&socket, &request, &configuration).await?;
+
+            Ok(response.payload)
+        }
+        .await
+        .inspect_err(Self::record_error)
+    }
+
+    /// Binds a UDP socket connected to the query port, so only its datagrams are received.
+    async fn connect(configuration: &QueryConfiguration) -> Result<UdpSocket, QueryError> {
+        let address = lookup_host(configuration.address())
+            .await
+            .map_err(|err| QueryError::Connection {
+                cause: err.to_string(),
+            })?
+            .next()
+            .ok_or_else(|| QueryError::Connection {
+                cause: format!("No address found for '{}'", configuration.host),
+            })?;
+
+        let local_address = if address.is_ipv4() {
+            "0.0.0.0:0"
+        } else {
+            "[::]:0"
+        };
+        let socket =
+            UdpSocket::bind(local_address)
+                .await
+                .map_err(|err| QueryError::Connection {
+                    cause: err.to_string(),
+                })?;
+        socket
+            .connect(address)
+            .await
+            .map_err(|err| QueryError::Connection {
+                cause: err.to_string(),
+            })?;
+
+        Ok(socket)
+    }
+
+    /// Sends a request until it is answered, as datagrams may be lost.
+    ///
+    /// # Returns:
+    ///
+    /// The response to the request.
+    async fn request(
+        socket: &UdpSocket,
+        request: &QueryRequest,
+        configuration: &QueryConfiguration,
+    ) -> Result<QueryResponse, QueryError> {
+        let started_at = Instant::now();
+        let bytes = request.to_bytes();
+        let mut buffer = vec![0u8; MAX_QUERY_DATAGRAM_SIZE];
+
+        for attempt in 1..=configuration.max_attempts {
+            if attempt > 1 {
+                debug!(
+                    "Sending the query request again, attempt {}/{}",
+                    attempt, configuration.max_attempts
+                );
+            }
+
+            socket.send(&bytes).await.map_err(|err| match err.kind() {
+                io::ErrorKind::ConnectionRefused => QueryError::Connection {
+                    cause: err.to_string(),
+                },
+                _ => QueryError::Send {
+                    cause: err.to_string(),
+                },
+            })?;
+
+            match timeout(
+                Duration::from_millis(configuration.timeout),
+                Self::receive(socket, request, &mut buffer),
+            )
+            .await
+            {
+                Ok(result) => return result,
+                Err(_) => warn!("Query request attempt {} timed out", attempt),
+            }
+        }
+
+        Err(QueryError::Timeout {
+            attempts: configuration.max_attempts,
+            elapsed_ms: started_at.elapsed().as_millis() as u64,
+        })
+    }
+
+    /// Receives datagrams until one answers the request, discarding the late answers of previous
+    /// attempts and the invalid ones.
+    async fn receive(
+        socket: &UdpSocket,
+        request: &QueryRequest,
+        buffer: &mut [u8],
+    ) -> Result<QueryResponse, QueryError> {
+        loop {
+            let size = socket.recv(buffer).await.map_err(|err| match err.kind() {
+                // Reported on connected sockets when the port is closed.
+                io::ErrorKind::ConnectionRefused => QueryError::Connection {
+                    cause: err.to_string(),
+                },
+                _ => QueryError::Receive {
+                    cause: err.to_string(),
+                },
+            })?;
+
+            match QueryResponse::try_from_bytes(&buffer[..size]) {
+                Ok(response) if response.answers(request) => return Ok(response),
+                Ok(response) => debug!(
+                    "Discarding a query response to another request, session ID {}",
+                    response.session_id
+                ),
+                Err(error) => debug!("Discarding an invalid query response: {}", error),
+            }
+        }
+    }
+
+    fn record_error(error: &QueryError) {
+        Span::current().record("error.type", error.kind());
+    }
+}
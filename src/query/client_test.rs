@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use crate::query::{QueryClient, QueryError};
+    use serial_test::serial;
+    use temp_env::async_with_vars;
+    use tokio::net::UdpSocket;
+
+    const CHALLENGE_TOKEN: &str = "9513307";
+
+    /// Starts a local stand-in of the query port.
+    ///
+    /// # Arguments
+    ///
+    /// * `dropped` - The number of datagrams ignored before answering, as if they were lost.
+    ///
+    /// # Returns
+    ///
+    /// The port of the stand-in.
+    async fn start_responder(dropped: usize) -> String {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = socket.local_addr().unwrap().port().to_string();
+
+        tokio::spawn(async move {
+            let mut buffer = [0u8; 1024];
+            let mut received = 0;
+            loop {
+                let (size, peer) = socket.recv_from(&mut buffer).await.unwrap();
+                received += 1;
+                if received <= dropped {
+                    continue;
+                }
+
+                let request = &buffer[..size];
+                assert_eq!(&request[0..2], &[0xfe, 0xfd]);
+                let session_id = &request[3..7];
+
+                let mut response = vec![request[2]];
+                // A late answer to another session, to be discarded by the client.
+                response.extend_from_slice(&[0x0f, 0x0f, 0x0f, 0x0f]);
+                socket.send_to(&response, peer).await.unwrap();
+
+                let mut response = vec![request[2]];
+                response.extend_from_slice(session_id);
+                match (request[2], size) {
+                    (0x09, _) => response.extend_from_slice(CHALLENGE_TOKEN.as_bytes()),
+                    (0x00, 11) => {
+                        assert_eq!(&request[7..11], &9513307i32.to_be_bytes());
+                        response.extend_from_slice(b"Stand-in\x00SMP\x00world\x001\x0010\x00");
+                        response.extend_from_slice(&25565u16.to_le_bytes());
+                        response.extend_from_slice(b"127.0.0.1");
+                    }
+                    (0x00, 15) => {
+                        response.extend_from_slice(b"splitnum\x00\x80\x00");
+                        response.extend_from_slice(
+                            b"hostname\x00Stand-in\x00gametype\x00SMP\x00game_id\x00MINECRAFT\x00\
+                              version\x001.21.4\x00plugins\x00\x00map\x00world\x00\
+                              numplayers\x001\x00maxplayers\x0010\x00hostport\x0025565\x00\
+                              hostip\x00127.0.0.1\x00\x00",
+                        );
+                        response.extend_from_slice(b"\x01player_\x00\x00Steve\x00");
+                    }
+                    _ => panic!("Unexpected query request {request:?}"),
+                }
+                response.push(0);
+                socket.send_to(&response, peer).await.unwrap();
+            }
+        });
+
+        port
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_basic_stat() {
+        let port = start_responder(0).await;
+
+        async_with_vars(
+            [
+                ("QUERY_HOST", Some("127.0.0.1")),
+                ("QUERY_PORT", Some(port.as_str())),
+            ],
+            async {
+                let stat = QueryClient.basic_stat().await.unwrap();
+
+                assert_eq!(stat.motd, "Stand-in");
+                assert_eq!(stat.game_type, "SMP");
+                assert_eq!(stat.map, "world");
+                assert_eq!(stat.online_players, 1);
+                assert_eq!(stat.max_players, 10);
+                assert_eq!(stat.host_port, 25565);
+                assert_eq!(stat.host_ip, "127.0.0.1");
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_full_stat_lost_datagram() {
+        let port = start_responder(1).await;
+
+        async_with_vars(
+            [
+                ("QUERY_HOST", Some("127.0.0.1")),
+                ("QUERY_PORT", Some(port.as_str())),
+                ("QUERY_TIMEOUT", Some("100")),
+                ("QUERY_MAX_ATTEMPTS", Some("2")),
+            ],
+            async {
+                let stat = QueryClient.full_stat().await.unwrap();
+
+                assert_eq!(stat.motd, "Stand-in");
+                assert_eq!(stat.version, "1.21.4");
+                assert_eq!(stat.server_mod, None);
+                assert_eq!(stat.players, vec!["Steve"]);
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_stat_timeout() {
+        let port = start_responder(usize::MAX).await;
+
+        async_with_vars(
+            [
+                ("QUERY_HOST", Some("127.0.0.1")),
+                ("QUERY_PORT", Some(port.as_str())),
+                ("QUERY_TIMEOUT", Some("100")),
+                ("QUERY_MAX_ATTEMPTS", Some("2")),
+            ],
+            async {
+                let result = QueryClient.basic_stat().await;
+
+                assert!(matches!(
+                    result,
+                    Err(QueryError::Timeout { attempts: 2, .. })
+                ));
+            },
+        )
+        .await;
+    }
+}
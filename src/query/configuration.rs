@@ -0,0 +1,86 @@
+use super::QueryError;
+use std::env;
+use tracing::debug;
+
+/// Default port of the query, the same as the game port unless `query.port` is set in the
+/// `server.properties` file.
+pub const DEFAULT_QUERY_PORT: u16 = 25565;
+
+/// Default timeout of a query attempt, in milliseconds.
+///
+/// Short, as UDP datagrams are either answered quickly or lost, and lost ones are sent again.
+pub const DEFAULT_QUERY_TIMEOUT: u64 = 1000;
+
+/// Default number of attempts of a query request, including the first one.
+pub const DEFAULT_QUERY_MAX_ATTEMPTS: u32 = 3;
+
+#[derive(Debug, Clone)]
+pub struct QueryConfiguration {
+    pub host: String,
+    pub port: u16,
+    /// Timeout of an attempt, in milliseconds.
+    pub timeout: u64,
+    /// Number of attempts of a request, including the first one.
+    pub max_attempts: u32,
+}
+
+impl QueryConfiguration {
+    /// Returns the configuration from environment variables.
+    ///
+    /// The host defaults to the one of the RCON server, as both are usually the same.
+    ///
+    /// # Returns:
+    ///
+    /// The configuration.
+    pub fn try_new() -> Result<QueryConfiguration, QueryError> {
+        debug!("Attempting to create query configuration from environment variables...");
+
+        let host = env::var("QUERY_HOST")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .or_else(|| env::var("RCON_HOST").ok())
+            .ok_or_else(|| QueryError::Configuration {
+                cause: String::from("Environment variable 'QUERY_HOST' is not set"),
+            })?;
+        let port = env::var("QUERY_PORT")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u16>())
+            .unwrap_or(Ok(DEFAULT_QUERY_PORT))
+            .map_err(|_| QueryError::Configuration {
+                cause: String::from("Environment variable 'QUERY_PORT' is not a valid number"),
+            })?;
+        let timeout = env::var("QUERY_TIMEOUT")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u64>())
+            .unwrap_or(Ok(DEFAULT_QUERY_TIMEOUT))
+            .map_err(|_| QueryError::Configuration {
+                cause: String::from("Environment variable 'QUERY_TIMEOUT' is not a valid number"),
+            })?;
+        let max_attempts = env::var("QUERY_MAX_ATTEMPTS")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u32>())
+            .unwrap_or(Ok(DEFAULT_QUERY_MAX_ATTEMPTS))
+            .ok()
+            .filter(|max_attempts| *max_attempts > 0)
+            .ok_or_else(|| QueryError::Configuration {
+                cause: String::from(
+                    "Environment variable 'QUERY_MAX_ATTEMPTS' is not a positive number",
+                ),
+            })?;
+
+        Ok(QueryConfiguration {
+            host,
+            port,
+            timeout,
+            max_attempts,
+        })
+    }
+
+    /// Returns the address of the query port, in the `<host>:<port>` format.
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use crate::query::{
+        DEFAULT_QUERY_MAX_ATTEMPTS, DEFAULT_QUERY_PORT, DEFAULT_QUERY_TIMEOUT, QueryConfiguration,
+        QueryError,
+    };
+    use serial_test::serial;
+    use temp_env::with_vars;
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration() {
+        with_vars(
+            [
+                ("QUERY_HOST", None),
+                ("RCON_HOST", Some("minecraft")),
+                ("QUERY_PORT", None),
+                ("QUERY_TIMEOUT", None),
+                ("QUERY_MAX_ATTEMPTS", None),
+            ],
+            || {
+                let configuration = QueryConfiguration::try_new().unwrap();
+                assert_eq!(configuration.host, "minecraft");
+                assert_eq!(configuration.port, DEFAULT_QUERY_PORT);
+                assert_eq!(configuration.timeout, DEFAULT_QUERY_TIMEOUT);
+                assert_eq!(configuration.max_attempts, DEFAULT_QUERY_MAX_ATTEMPTS);
+                assert_eq!(configuration.address(), "minecraft:25565");
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_custom() {
+        with_vars(
+            [
+                ("QUERY_HOST", Some("play.example.com")),
+                ("RCON_HOST", Some("minecraft")),
+                ("QUERY_PORT", Some("25566")),
+                ("QUERY_TIMEOUT", Some("250")),
+                ("QUERY_MAX_ATTEMPTS", Some("5")),
+            ],
+            || {
+                let configuration = QueryConfiguration::try_new().unwrap();
+                assert_eq!(configuration.host, "play.example.com");
+                assert_eq!(configuration.port, 25566);
+                assert_eq!(configuration.timeout, 250);
+                assert_eq!(configuration.max_attempts, 5);
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_missing_host() {
+        with_vars([("QUERY_HOST", None::<&str>), ("RCON_HOST", None)], || {
+            assert_eq!(
+                QueryConfiguration::try_new().unwrap_err(),
+                QueryError::Configuration {
+                    cause: String::from("Environment variable 'QUERY_HOST' is not set")
+                }
+            );
+        });
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_zero_attempts() {
+        with_vars(
+            [
+                ("QUERY_HOST", Some("minecraft")),
+                ("QUERY_MAX_ATTEMPTS", Some("0")),
+            ],
+            || {
+                assert_eq!(
+                    QueryConfiguration::try_new().unwrap_err(),
+                    QueryError::Configuration {
+                        cause: String::from(
+                            "Environment variable 'QUERY_MAX_ATTEMPTS' is not a positive number"
+                        )
+                    }
+                );
+            },
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum QueryError {
+    #[error("Invalid query configuration: {cause}")]
+    Configuration { cause: String },
+    #[error("Failed to reach the Minecraft server query port: {cause}")]
+    Connection { cause: String },
+    #[error("Failed to send data to the Minecraft server query port: {cause}")]
+    Send { cause: String },
+    #[error("Failed to receive data from the Minecraft server query port: {cause}")]
+    Receive { cause: String },
+    #[error("Invalid query response from the Minecraft server: {cause}")]
+    InvalidResponse { cause: String },
+    #[error(
+        "Timeout waiting for the Minecraft server query response after {attempts} attempts, elapsed time: {elapsed_ms}ms"
+    )]
+    Timeout { attempts: u32, elapsed_ms: u64 },
+}
+
+impl QueryError {
+    /// Returns the kind of the error, as used in the telemetry.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            QueryError::Configuration { .. } => "configuration",
+            QueryError::Connection { .. } => "connection",
+            QueryError::Send { .. } => "send",
+            QueryError::Receive { .. } => "receive",
+            QueryError::InvalidResponse { .. } => "invalid_response",
+            QueryError::Timeout { .. } => "timeout",
+        }
+    }
+}
@@ -0,0 +1,15 @@
+pub mod client;
+pub mod client_test;
+pub mod configuration;
+pub mod configuration_test;
+pub mod error;
+pub mod packet;
+pub mod packet_test;
+pub mod stat;
+pub mod stat_test;
+
+pub use client::*;
+pub use configuration::*;
+pub use error::*;
+pub use packet::*;
+pub use stat::*;
@@ -0,0 +1,185 @@
+use super::QueryError;
+
+/// Magic bytes starting every request.
+///
+/// - [Query](https://minecraft.wiki/w/Query)
+pub const QUERY_MAGIC: [u8; 2] = [0xfe, 0xfd];
+
+/// Mask applied to the session IDs, the server ignoring the upper 4 bits of each byte.
+pub const QUERY_SESSION_ID_MASK: i32 = 0x0f0f0f0f;
+
+/// Size of the header of a response: the type and the session ID.
+const QUERY_RESPONSE_HEADER_SIZE: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryPacketType {
+    Handshake,
+    Stat,
+}
+
+impl QueryPacketType {
+    pub fn to_u8(self) -> u8 {
+        match self {
+            QueryPacketType::Handshake => 9,
+            QueryPacketType::Stat => 0,
+        }
+    }
+
+    pub fn try_from_u8(value: u8) -> Result<Self, QueryError> {
+        match value {
+            9 => Ok(QueryPacketType::Handshake),
+            0 => Ok(QueryPacketType::Stat),
+            _ => Err(QueryError::InvalidResponse {
+                cause: format!("unknown packet type {value}"),
+            }),
+        }
+    }
+}
+
+/// Request sent to the query port.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryRequest {
+    pub packet_type: QueryPacketType,
+    pub session_id: i32,
+    pub payload: Vec<u8>,
+}
+
+impl QueryRequest {
+    /// Creates a handshake request, answered with the challenge token of the session.
+    pub fn handshake(session_id: i32) -> Self {
+        QueryRequest {
+            packet_type: QueryPacketType::Handshake,
+            session_id: session_id & QUERY_SESSION_ID_MASK,
+            payload: Vec::new(),
+        }
+    }
+
+    /// Creates a basic stat request.
+    pub fn basic_stat(session_id: i32, challenge_token: i32) -> Self {
+        QueryRequest {
+            packet_type: QueryPacketType::Stat,
+            session_id: session_id & QUERY_SESSION_ID_MASK,
+            payload: challenge_token.to_be_bytes().to_vec(),
+        }
+    }
+
+    /// Creates a full stat request, which only differs from the basic one by its padding.
+    pub fn full_stat(session_id: i32, challenge_token: i32) -> Self {
+        let mut payload = challenge_token.to_be_bytes().to_vec();
+        payload.extend_from_slice(&[0x00; 4]);
+
+        QueryRequest {
+            packet_type: QueryPacketType::Stat,
+            session_id: session_id & QUERY_SESSION_ID_MASK,
+            payload,
+        }
+    }
+
+    /// Converts the request to bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = QUERY_MAGIC.to_vec();
+        bytes.push(self.packet_type.to_u8());
+        bytes.extend_from_slice(&self.session_id.to_be_bytes());
+        bytes.extend_from_slice(&self.payload);
+
+        bytes
+    }
+}
+
+/// Response received from the query port.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryResponse {
+    pub packet_type: QueryPacketType,
+    pub session_id: i32,
+    pub payload: Vec<u8>,
+}
+
+impl QueryResponse {
+    /// Parses a response from a datagram.
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, QueryError> {
+        if bytes.len() < QUERY_RESPONSE_HEADER_SIZE {
+            return Err(QueryError::InvalidResponse {
+                cause: format!("datagram of {} bytes is too short", bytes.len()),
+            });
+        }
+
+        Ok(QueryResponse {
+            packet_type: QueryPacketType::try_from_u8(bytes[0])?,
+            session_id: i32::from_be_bytes([bytes[1], bytes[2], bytes[3], bytes[4]]),
+            payload: bytes[QUERY_RESPONSE_HEADER_SIZE..].to_vec(),
+        })
+    }
+
+    /// Returns whether the response answers a request.
+    pub fn answers(&self, request: &QueryRequest) -> bool {
+        self.packet_type == request.packet_type && self.session_id == request.session_id
+    }
+
+    /// Reads the challenge token of a handshake response, sent as a decimal string.
+    pub fn challenge_token(&self) -> Result<i32, QueryError> {
+        let mut reader = QueryPayloadReader::new(&self.payload);
+        let token = reader.read_string()?;
+
+        token
+            .trim()
+            .parse::<i32>()
+            .map_err(|_| QueryError::InvalidResponse {
+                cause: format!("invalid challenge token '{token}'"),
+            })
+    }
+}
+
+/// Reader of the null-terminated strings and fixed-size fields of a response payload.
+pub struct QueryPayloadReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> QueryPayloadReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        QueryPayloadReader { bytes, position: 0 }
+    }
+
+    /// Reads a null-terminated string, replacing the invalid UTF-8 characters.
+    pub fn read_string(&mut self) -> Result<String, QueryError> {
+        let remaining = &self.bytes[self.position..];
+        let end = remaining
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| QueryError::InvalidResponse {
+                cause: String::from("unterminated string"),
+            })?;
+
+        self.position += end + 1;
+        Ok(String::from_utf8_lossy(&remaining[..end]).into_owned())
+    }
+
+    /// Reads a little-endian unsigned short.
+    pub fn read_u16_le(&mut self) -> Result<u16, QueryError> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// Skips the given bytes, failing if they are not the expected ones.
+    pub fn expect(&mut self, expected: &[u8]) -> Result<(), QueryError> {
+        if self.read_bytes(expected.len())? != expected {
+            return Err(QueryError::InvalidResponse {
+                cause: String::from("unexpected padding"),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn read_bytes(&mut self, size: usize) -> Result<&'a [u8], QueryError> {
+        let bytes = self
+            .bytes
+            .get(self.position..self.position + size)
+            .ok_or_else(|| QueryError::InvalidResponse {
+                cause: String::from("truncated payload"),
+            })?;
+
+        self.position += size;
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use crate::query::{QueryError, QueryPacketType, QueryRequest, QueryResponse};
+
+    #[test]
+    fn handshake_request_bytes() {
+        let request = QueryRequest::handshake(0x7f7f7f7f);
+
+        // The session ID is masked, the server ignoring the upper bits of each byte.
+        assert_eq!(
+            request.to_bytes(),
+            vec![0xfe, 0xfd, 0x09, 0x0f, 0x0f, 0x0f, 0x0f]
+        );
+    }
+
+    #[test]
+    fn basic_stat_request_bytes() {
+        let request = QueryRequest::basic_stat(1, 9513307);
+
+        assert_eq!(
+            request.to_bytes(),
+            vec![
+                0xfe, 0xfd, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x91, 0x29, 0x5b
+            ]
+        );
+    }
+
+    #[test]
+    fn full_stat_request_bytes() {
+        let request = QueryRequest::full_stat(1, 9513307);
+
+        assert_eq!(
+            request.to_bytes(),
+            vec![
+                0xfe, 0xfd, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x91, 0x29, 0x5b, 0x00, 0x00, 0x00,
+                0x00
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_handshake_response() {
+        let response = QueryResponse::try_from_bytes(b"\x09\x00\x00\x00\x01-9513307\x00").unwrap();
+
+        assert_eq!(response.packet_type, QueryPacketType::Handshake);
+        assert_eq!(response.session_id, 1);
+        assert!(response.answers(&QueryRequest::handshake(1)));
+        assert!(!response.answers(&QueryRequest::handshake(2)));
+        assert_eq!(response.challenge_token(), Ok(-9513307));
+    }
+
+    #[test]
+    fn parse_invalid_challenge_token() {
+        let response = QueryResponse::try_from_bytes(b"\x09\x00\x00\x00\x01abc\x00").unwrap();
+
+        assert_eq!(
+            response.challenge_token(),
+            Err(QueryError::InvalidResponse {
+                cause: String::from("invalid challenge token 'abc'")
+            })
+        );
+    }
+
+    #[test]
+    fn parse_short_response() {
+        assert!(matches!(
+            QueryResponse::try_from_bytes(&[0x09, 0x00]),
+            Err(QueryError::InvalidResponse { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_unknown_packet_type() {
+        assert!(matches!(
+            QueryResponse::try_from_bytes(&[0x05, 0x00, 0x00, 0x00, 0x01]),
+            Err(QueryError::InvalidResponse { .. })
+        ));
+    }
+}
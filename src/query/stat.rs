@@ -0,0 +1,134 @@
+use super::{QueryError, QueryPayloadReader};
+use std::collections::HashMap;
+
+/// Padding before the key/value section of a full stat response.
+const FULL_STAT_KEY_VALUES_PADDING: &[u8] = b"splitnum\x00\x80\x00";
+
+/// Padding before the player section of a full stat response.
+const FULL_STAT_PLAYERS_PADDING: &[u8] = b"\x01player_\x00\x00";
+
+/// Basic stat of the server.
+///
+/// - [Basic stat](https://minecraft.wiki/w/Query#Basic_stat)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryBasicStat {
+    pub motd: String,
+    pub game_type: String,
+    pub map: String,
+    pub online_players: u32,
+    pub max_players: u32,
+    pub host_port: u16,
+    pub host_ip: String,
+}
+
+impl QueryBasicStat {
+    /// Parses the basic stat from the payload of a stat response.
+    pub fn try_from_payload(payload: &[u8]) -> Result<Self, QueryError> {
+        let mut reader = QueryPayloadReader::new(payload);
+
+        Ok(QueryBasicStat {
+            motd: reader.read_string()?,
+            game_type: reader.read_string()?,
+            map: reader.read_string()?,
+            online_players: parse_number("numplayers", &reader.read_string()?)?,
+            max_players: parse_number("maxplayers", &reader.read_string()?)?,
+            host_port: reader.read_u16_le()?,
+            host_ip: reader.read_string()?,
+        })
+    }
+}
+
+/// Full stat of the server.
+///
+/// - [Full stat](https://minecraft.wiki/w/Query#Full_stat)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryFullStat {
+    pub motd: String,
+    pub game_type: String,
+    pub game_id: String,
+    pub version: String,
+    /// The name and version of the server software, missing on vanilla servers.
+    pub server_mod: Option<String>,
+    pub plugins: Vec<String>,
+    pub map: String,
+    pub online_players: u32,
+    pub max_players: u32,
+    pub host_port: u16,
+    pub host_ip: String,
+    /// The names of all the online players.
+    pub players: Vec<String>,
+}
+
+impl QueryFullStat {
+    /// Parses the full stat from the payload of a stat response.
+    pub fn try_from_payload(payload: &[u8]) -> Result<Self, QueryError> {
+        let mut reader = QueryPayloadReader::new(payload);
+        reader.expect(FULL_STAT_KEY_VALUES_PADDING)?;
+
+        let mut values = HashMap::new();
+        loop {
+            let key = reader.read_string()?;
+            if key.is_empty() {
+                break;
+            }
+            values.insert(key, reader.read_string()?);
+        }
+
+        reader.expect(FULL_STAT_PLAYERS_PADDING)?;
+        let mut players = Vec::new();
+        loop {
+            let player = reader.read_string()?;
+            if player.is_empty() {
+                break;
+            }
+            players.push(player);
+        }
+
+        let mut value = |key: &str| values.remove(key).unwrap_or_default();
+        let (server_mod, plugins) = parse_plugins(&value("plugins"));
+
+        Ok(QueryFullStat {
+            motd: value("hostname"),
+            game_type: value("gametype"),
+            game_id: value("game_id"),
+            version: value("version"),
+            server_mod,
+            plugins,
+            map: value("map"),
+            online_players: parse_number("numplayers", &value("numplayers"))?,
+            max_players: parse_number("maxplayers", &value("maxplayers"))?,
+            host_port: parse_number("hostport", &value("hostport"))?,
+            host_ip: value("hostip"),
+            players,
+        })
+    }
+}
+
+/// Parses the plugins value, in the `<server mod>: <plugin>; <plugin>` format.
+fn parse_plugins(value: &str) -> (Option<String>, Vec<String>) {
+    if value.trim().is_empty() {
+        return (None, Vec::new());
+    }
+
+    match value.split_once(':') {
+        Some((server_mod, plugins)) => (
+            Some(server_mod.trim().to_string()),
+            plugins
+                .split(';')
+                .map(str::trim)
+                .filter(|plugin| !plugin.is_empty())
+                .map(String::from)
+                .collect(),
+        ),
+        None => (Some(value.trim().to_string()), Vec::new()),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, QueryError> {
+    value
+        .trim()
+        .parse::<T>()
+        .map_err(|_| QueryError::InvalidResponse {
+            cause: format!("invalid {key} '{value}'"),
+        })
+}
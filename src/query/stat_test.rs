@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod tests {
+    use crate::query::{QueryBasicStat, QueryError, QueryFullStat};
+
+    /// Builds the payload of a full stat response.
+    fn full_stat_payload(plugins: &str, players: &[&str]) -> Vec<u8> {
+        let mut payload = b"splitnum\x00\x80\x00".to_vec();
+        for (key, value) in [
+            ("hostname", "A Minecraft Server"),
+            ("gametype", "SMP"),
+            ("game_id", "MINECRAFT"),
+            ("version", "1.21.4"),
+            ("plugins", plugins),
+            ("map", "world"),
+            ("numplayers", "2"),
+            ("maxplayers", "20"),
+            ("hostport", "25565"),
+            ("hostip", "127.0.0.1"),
+        ] {
+            payload.extend_from_slice(key.as_bytes());
+            payload.push(0);
+            payload.extend_from_slice(value.as_bytes());
+            payload.push(0);
+        }
+        payload.push(0);
+        payload.extend_from_slice(b"\x01player_\x00\x00");
+        for player in players {
+            payload.extend_from_slice(player.as_bytes());
+            payload.push(0);
+        }
+        payload.push(0);
+
+        payload
+    }
+
+    #[test]
+    fn parse_basic_stat() {
+        let mut payload = b"A Minecraft Server\x00SMP\x00world\x002\x0020\x00".to_vec();
+        payload.extend_from_slice(&25565u16.to_le_bytes());
+        payload.extend_from_slice(b"127.0.0.1\x00");
+
+        let stat = QueryBasicStat::try_from_payload(&payload).unwrap();
+
+        assert_eq!(
+            stat,
+            QueryBasicStat {
+                motd: String::from("A Minecraft Server"),
+                game_type: String::from("SMP"),
+                map: String::from("world"),
+                online_players: 2,
+                max_players: 20,
+                host_port: 25565,
+                host_ip: String::from("127.0.0.1"),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_basic_stat_truncated() {
+        let payload = b"A Minecraft Server\x00SMP\x00world\x002\x0020\x00\xdd";
+
+        assert!(matches!(
+            QueryBasicStat::try_from_payload(payload),
+            Err(QueryError::InvalidResponse { .. })
+        ));
+    }
+
+    #[test]
+    fn parse_full_stat() {
+        let payload = full_stat_payload(
+            "Paper on 1.21.4-R0.1-SNAPSHOT: WorldEdit 7.3.0; LuckPerms 5.4",
+            &["Steve", "Alex"],
+        );
+
+        let stat = QueryFullStat::try_from_payload(&payload).unwrap();
+
+        assert_eq!(stat.motd, "A Minecraft Server");
+        assert_eq!(stat.game_type, "SMP");
+        assert_eq!(stat.game_id, "MINECRAFT");
+        assert_eq!(stat.version, "1.21.4");
+        assert_eq!(
+            stat.server_mod.as_deref(),
+            Some("Paper on 1.21.4-R0.1-SNAPSHOT")
+        );
+        assert_eq!(stat.plugins, vec!["WorldEdit 7.3.0", "LuckPerms 5.4"]);
+        assert_eq!(stat.map, "world");
+        assert_eq!(stat.online_players, 2);
+        assert_eq!(stat.max_players, 20);
+        assert_eq!(stat.host_port, 25565);
+        assert_eq!(stat.host_ip, "127.0.0.1");
+        assert_eq!(stat.players, vec!["Steve", "Alex"]);
+    }
+
+    #[test]
+    fn parse_full_stat_vanilla() {
+        let payload = full_stat_payload("", &[]);
+
+        let stat = QueryFullStat::try_from_payload(&payload).unwrap();
+
+        assert_eq!(stat.server_mod, None);
+        assert!(stat.plugins.is_empty());
+        assert!(stat.players.is_empty());
+    }
+
+    #[test]
+    fn parse_full_stat_invalid_padding() {
+        let mut payload = full_stat_payload("", &[]);
+        payload[0] = b'S';
+
+        assert_eq!(
+            QueryFullStat::try_from_payload(&payload),
+            Err(QueryError::InvalidResponse {
+                cause: String::from("unexpected padding")
+            })
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use std::time::{Duration, Instant};
+
+/// Wait advertised to clients whose bucket is never refilled (zero capacity or refill rate).
+const NEVER_REFILLED_RETRY_AFTER: Duration = Duration::from_secs(3600);
+
+/// Token bucket tracking the requests of a single client on a single route.
+///
+/// The bucket starts full, every request takes one token, and tokens are given back continuously
+/// at the refill rate, up to the capacity.
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    refill_rate: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a new full bucket.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The maximum number of tokens in the bucket.
+    /// * `refill_rate` - The number of tokens given back every second.
+    /// * `now` - The creation instant.
+    pub fn new(capacity: u32, refill_rate: f64, now: Instant) -> Self {
+        TokenBucket {
+            capacity: capacity as f64,
+            refill_rate,
+            tokens: capacity as f64,
+            last_refill: now,
+        }
+    }
+
+    /// Attempts to take a token from the bucket.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - The instant of the request.
+    ///
+    /// # Returns
+    ///
+    /// The number of remaining tokens if a token was taken, otherwise the duration to wait for
+    /// the next token.
+    pub fn try_acquire(&mut self, now: Instant) -> Result<u32, Duration> {
+        self.refill(now);
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(self.tokens.floor() as u32)
+        } else if self.refill_rate > 0.0 && self.capacity >= 1.0 {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / self.refill_rate,
+            ))
+        } else {
+            Err(NEVER_REFILLED_RETRY_AFTER)
+        }
+    }
+
+    /// Returns `true` if the bucket is full at the given instant, meaning that forgetting it is
+    /// equivalent to keeping it.
+    pub fn is_full(&self, now: Instant) -> bool {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens + elapsed * self.refill_rate >= self.capacity
+    }
+
+    /// Gives back the tokens accumulated since the last refill.
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_rate).min(self.capacity);
+        self.last_refill = now;
+    }
+}
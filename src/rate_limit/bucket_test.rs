@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use crate::rate_limit::TokenBucket;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_burst_then_limited() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(3, 1.0, now);
+
+        assert_eq!(bucket.try_acquire(now), Ok(2));
+        assert_eq!(bucket.try_acquire(now), Ok(1));
+        assert_eq!(bucket.try_acquire(now), Ok(0));
+        assert_eq!(bucket.try_acquire(now), Err(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_refill() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(2, 2.0, now);
+
+        assert!(bucket.try_acquire(now).is_ok());
+        assert!(bucket.try_acquire(now).is_ok());
+        assert!(bucket.try_acquire(now).is_err());
+
+        // Half a second gives back a single token.
+        let later = now + Duration::from_millis(500);
+        assert_eq!(bucket.try_acquire(later), Ok(0));
+        assert_eq!(bucket.try_acquire(later), Err(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_refill_capped_to_capacity() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(2, 10.0, now);
+
+        assert!(bucket.try_acquire(now).is_ok());
+        assert!(bucket.is_full(now + Duration::from_secs(60)));
+
+        assert_eq!(bucket.try_acquire(now + Duration::from_secs(60)), Ok(1));
+    }
+
+    #[test]
+    fn test_never_refilled() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(0, 0.0, now);
+
+        assert!(bucket.try_acquire(now).is_err());
+        assert!(bucket.try_acquire(now + Duration::from_secs(60)).is_err());
+    }
+}
@@ -0,0 +1,18 @@
+use super::RateLimitDecision;
+use crate::api::ApiError;
+use rocket::{Request, catch, http::Status};
+
+/// Catcher for the requests rejected by the `RateLimited` guard, responding with the same JSON
+/// body as the other API errors.
+#[catch(429)]
+pub fn too_many_requests(request: &Request<'_>) -> ApiError {
+    let message = match request.local_cache(|| None::<RateLimitDecision>) {
+        Some(RateLimitDecision::Limited { retry_after, .. }) => format!(
+            "Too many requests, retry after {}ms",
+            retry_after.as_millis()
+        ),
+        _ => String::from("Too many requests"),
+    };
+
+    ApiError::new(Status::TooManyRequests, message)
+}
@@ -0,0 +1,162 @@
+use super::RateLimitError;
+use std::{collections::HashMap, env};
+use tracing::debug;
+
+/// Default number of requests a client can burst before being limited.
+pub const DEFAULT_RATE_LIMIT_CAPACITY: u32 = 20;
+
+/// Default number of requests given back to a client every second.
+pub const DEFAULT_RATE_LIMIT_REFILL_RATE: f64 = 2.0;
+
+/// Limits applied to a token bucket.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RateLimitRule {
+    /// The client can burst `capacity` requests, then gets `refill_rate` requests per second.
+    Limited { capacity: u32, refill_rate: f64 },
+    /// The client is never limited.
+    Unlimited,
+}
+
+impl TryFrom<&str> for RateLimitRule {
+    type Error = String;
+
+    /// Parses a rule from its `<capacity>:<refill_rate>` or `unlimited` representation.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The rule to parse, e.g. `10:0.5`.
+    ///
+    /// # Returns
+    ///
+    /// A `RateLimitRule` if the parsing was successful, otherwise a `String`.
+    fn try_from(value: &str) -> Result<RateLimitRule, Self::Error> {
+        let value = value.trim();
+
+        if value.eq_ignore_ascii_case("unlimited") {
+            return Ok(RateLimitRule::Unlimited);
+        }
+
+        let (capacity, refill_rate) = value
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid rate limit rule '{value}'"))?;
+        let capacity = capacity
+            .trim()
+            .parse::<u32>()
+            .map_err(|_| format!("Invalid rate limit capacity '{capacity}'"))?;
+        let refill_rate = refill_rate
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|rate| rate.is_finite() && *rate >= 0.0)
+            .ok_or_else(|| format!("Invalid rate limit refill rate '{refill_rate}'"))?;
+
+        Ok(RateLimitRule::Limited {
+            capacity,
+            refill_rate,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RateLimitConfiguration {
+    pub enabled: bool,
+    pub default_rule: RateLimitRule,
+    pub route_rules: HashMap<String, RateLimitRule>,
+    pub client_rules: HashMap<String, RateLimitRule>,
+}
+
+impl RateLimitConfiguration {
+    /// Returns the configuration from environment variables.
+    ///
+    /// # Returns:
+    ///
+    /// The configuration.
+    pub fn try_new() -> Result<RateLimitConfiguration, RateLimitError> {
+        debug!("Attempting to create rate limit configuration from environment variables...");
+
+        let enabled = env::var("RATE_LIMIT_ENABLED")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<bool>())
+            .unwrap_or(Ok(true))
+            .map_err(|_| RateLimitError::Configuration {
+                cause: String::from("Environment variable 'RATE_LIMIT_ENABLED' is not a boolean"),
+            })?;
+        let capacity = env::var("RATE_LIMIT_CAPACITY")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u32>())
+            .unwrap_or(Ok(DEFAULT_RATE_LIMIT_CAPACITY))
+            .map_err(|_| RateLimitError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RATE_LIMIT_CAPACITY' is not a valid number",
+                ),
+            })?;
+        let refill_rate = env::var("RATE_LIMIT_REFILL_RATE")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<f64>())
+            .unwrap_or(Ok(DEFAULT_RATE_LIMIT_REFILL_RATE))
+            .ok()
+            .filter(|rate| rate.is_finite() && *rate >= 0.0)
+            .ok_or_else(|| RateLimitError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RATE_LIMIT_REFILL_RATE' is not a valid number",
+                ),
+            })?;
+        let route_rules = Self::parse_rules("RATE_LIMIT_ROUTES")?;
+        let client_rules = Self::parse_rules("RATE_LIMIT_CLIENTS")?;
+
+        Ok(RateLimitConfiguration {
+            enabled,
+            default_rule: RateLimitRule::Limited {
+                capacity,
+                refill_rate,
+            },
+            route_rules,
+            client_rules,
+        })
+    }
+
+    /// Returns the rule to apply for a client on a route.
+    ///
+    /// Client rules take precedence over route rules, which take precedence over the default rule.
+    pub fn rule_for(&self, route: &str, client: &str) -> &RateLimitRule {
+        self.client_rules
+            .get(client)
+            .or_else(|| self.route_rules.get(route))
+            .unwrap_or(&self.default_rule)
+    }
+
+    /// Parses a list of rules from an environment variable, in the
+    /// `<key>=<capacity>:<refill_rate>,<key>=unlimited` format.
+    fn parse_rules(variable: &str) -> Result<HashMap<String, RateLimitRule>, RateLimitError> {
+        let Ok(value) = env::var(variable) else {
+            return Ok(HashMap::new());
+        };
+
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                // Split on the last '=' as API tokens may contain some.
+                let (key, rule) =
+                    entry
+                        .rsplit_once('=')
+                        .ok_or_else(|| RateLimitError::Configuration {
+                            cause: format!(
+                                "Environment variable '{variable}' has an invalid entry '{entry}'"
+                            ),
+                        })?;
+                let rule = RateLimitRule::try_from(rule).map_err(|cause| {
+                    RateLimitError::Configuration {
+                        cause: format!("Environment variable '{variable}': {cause}"),
+                    }
+                })?;
+
+                Ok((String::from(key.trim()), rule))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use crate::rate_limit::{
+        DEFAULT_RATE_LIMIT_CAPACITY, DEFAULT_RATE_LIMIT_REFILL_RATE, RateLimitConfiguration,
+        RateLimitError, RateLimitRule,
+    };
+    use serial_test::serial;
+    use temp_env::with_vars;
+    use test_case::test_case;
+
+    #[test_case("10:0.5" => Ok(RateLimitRule::Limited { capacity: 10, refill_rate: 0.5 }); "limited")]
+    #[test_case("unlimited" => Ok(RateLimitRule::Unlimited); "unlimited")]
+    #[test_case("10" => matches Err(_); "missing_refill_rate")]
+    #[test_case("-1:1" => matches Err(_); "negative_capacity")]
+    #[test_case("1:-1" => matches Err(_); "negative_refill_rate")]
+    fn try_from_str_to_rule(value: &str) -> Result<RateLimitRule, String> {
+        RateLimitRule::try_from(value)
+    }
+
+    #[test]
+    #[serial(rate_limit)]
+    fn test_get_configuration_default() {
+        with_vars(
+            [
+                ("RATE_LIMIT_ENABLED", None::<&str>),
+                ("RATE_LIMIT_CAPACITY", None),
+                ("RATE_LIMIT_REFILL_RATE", None),
+                ("RATE_LIMIT_ROUTES", None),
+                ("RATE_LIMIT_CLIENTS", None),
+            ],
+            || {
+                let configuration = RateLimitConfiguration::try_new().unwrap();
+
+                assert!(configuration.enabled);
+                assert_eq!(
+                    configuration.default_rule,
+                    RateLimitRule::Limited {
+                        capacity: DEFAULT_RATE_LIMIT_CAPACITY,
+                        refill_rate: DEFAULT_RATE_LIMIT_REFILL_RATE
+                    }
+                );
+                assert!(configuration.route_rules.is_empty());
+                assert!(configuration.client_rules.is_empty());
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rate_limit)]
+    fn test_get_configuration_rules() {
+        with_vars(
+            [
+                ("RATE_LIMIT_ENABLED", Some("true")),
+                ("RATE_LIMIT_CAPACITY", Some("5")),
+                ("RATE_LIMIT_REFILL_RATE", Some("0.5")),
+                ("RATE_LIMIT_ROUTES", Some("/api/rcon=10:1")),
+                (
+                    "RATE_LIMIT_CLIENTS",
+                    Some("dG9rZW4==unlimited, 10.0.0.1=1:0"),
+                ),
+            ],
+            || {
+                let configuration = RateLimitConfiguration::try_new().unwrap();
+
+                assert_eq!(
+                    configuration.rule_for("/api/rcon", "127.0.0.1"),
+                    &RateLimitRule::Limited {
+                        capacity: 10,
+                        refill_rate: 1.0
+                    }
+                );
+                assert_eq!(
+                    configuration.rule_for("/api/rcon", "dG9rZW4="),
+                    &RateLimitRule::Unlimited
+                );
+                assert_eq!(
+                    configuration.rule_for("/api/rcon", "10.0.0.1"),
+                    &RateLimitRule::Limited {
+                        capacity: 1,
+                        refill_rate: 0.0
+                    }
+                );
+                assert_eq!(
+                    configuration.rule_for("/api/other", "127.0.0.1"),
+                    &RateLimitRule::Limited {
+                        capacity: 5,
+                        refill_rate: 0.5
+                    }
+                );
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rate_limit)]
+    fn test_get_configuration_invalid_rule() {
+        with_vars(
+            [
+                ("RATE_LIMIT_ENABLED", None::<&str>),
+                ("RATE_LIMIT_CAPACITY", None),
+                ("RATE_LIMIT_REFILL_RATE", None),
+                ("RATE_LIMIT_ROUTES", Some("/api/rcon")),
+                ("RATE_LIMIT_CLIENTS", None),
+            ],
+            || {
+                let result = RateLimitConfiguration::try_new();
+
+                assert_eq!(
+                    result.unwrap_err(),
+                    RateLimitError::Configuration {
+                        cause: String::from(
+                            "Environment variable 'RATE_LIMIT_ROUTES' has an invalid entry '/api/rcon'"
+                        )
+                    }
+                );
+            },
+        );
+    }
+}
@@ -0,0 +1,7 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RateLimitError {
+    #[error("Invalid rate limit configuration: {cause}")]
+    Configuration { cause: String },
+}
@@ -0,0 +1,41 @@
+use super::RateLimitDecision;
+use rocket::{
+    Request, Response,
+    fairing::{Fairing, Info, Kind},
+    http::Header,
+};
+
+/// Rocket fairing adding the rate limit headers to the responses of rate limited routes.
+///
+/// - `X-RateLimit-Limit`: The number of requests the client can burst.
+/// - `X-RateLimit-Remaining`: The number of requests the client can still perform right away.
+/// - `Retry-After`: The number of seconds to wait before retrying, when the client is limited.
+pub struct RateLimitFairing;
+
+#[rocket::async_trait]
+impl Fairing for RateLimitFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Rate limit headers",
+            kind: Kind::Response,
+        }
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        match request.local_cache(|| None::<RateLimitDecision>) {
+            Some(RateLimitDecision::Allowed { limit, remaining }) => {
+                response.set_header(Header::new("X-RateLimit-Limit", limit.to_string()));
+                response.set_header(Header::new("X-RateLimit-Remaining", remaining.to_string()));
+            }
+            Some(RateLimitDecision::Limited { limit, retry_after }) => {
+                // Round up, so that the client does not retry too early.
+                let retry_after = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+
+                response.set_header(Header::new("X-RateLimit-Limit", limit.to_string()));
+                response.set_header(Header::new("X-RateLimit-Remaining", "0"));
+                response.set_header(Header::new("Retry-After", retry_after.to_string()));
+            }
+            Some(RateLimitDecision::Unlimited) | None => {}
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use super::{RateLimitDecision, RateLimiter};
+use crate::api::ClientIdentity;
+use rocket::{
+    Request,
+    http::Status,
+    request::{FromRequest, Outcome},
+};
+use std::time::Instant;
+
+/// Request guard enforcing the rate limits of the route it is used on.
+///
+/// The decision is stored in Rocket's request-local cache, allowing the `RateLimitFairing` to
+/// add the rate limit headers to the response. When the client is limited, the request is
+/// rejected with a `429 Too Many Requests` status.
+pub struct RateLimited;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RateLimited {
+    type Error = RateLimitDecision;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let Some(limiter) = request.rocket().state::<RateLimiter>() else {
+            return Outcome::Success(RateLimited);
+        };
+
+        let route = request
+            .route()
+            .map(|route| route.uri.path())
+            .unwrap_or("unmatched");
+        // Unknown tokens are not trusted as identities, otherwise a client could get a fresh
+        // bucket on each request by sending a new token.
+        let client = match ClientIdentity::from_request(request) {
+            ClientIdentity::Token(token) if limiter.is_known_token(&token) => {
+                ClientIdentity::Token(token)
+            }
+            _ => ClientIdentity::from_ip(request),
+        };
+
+        let decision = limiter.check(route, &client.key(), Instant::now());
+
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "metrics")] {
+                let outcome = match decision {
+                    RateLimitDecision::Limited { .. } => "limited",
+                    _ => "allowed",
+                };
+                crate::metrics::RATE_LIMIT_REQUESTS_TOTAL
+                    .with_label_values(&[route, outcome])
+                    .inc();
+            }
+        }
+
+        match request.local_cache(|| Some(decision)) {
+            Some(decision @ RateLimitDecision::Limited { .. }) => {
+                Outcome::Error((Status::TooManyRequests, decision.clone()))
+            }
+            _ => Outcome::Success(RateLimited),
+        }
+    }
+}
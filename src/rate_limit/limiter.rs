@@ -0,0 +1,111 @@
+use super::{RateLimitConfiguration, RateLimitError, RateLimitRule, TokenBucket};
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tracing::{debug, warn};
+
+/// Number of tracked buckets above which the full buckets are forgotten.
+const BUCKETS_PRUNE_THRESHOLD: usize = 1024;
+
+/// Outcome of a rate limit check.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RateLimitDecision {
+    /// The request is allowed, and the client can still perform `remaining` requests right away.
+    Allowed { limit: u32, remaining: u32 },
+    /// The request is rejected, the client should wait `retry_after` before retrying.
+    Limited { limit: u32, retry_after: Duration },
+    /// The request is not subject to rate limiting.
+    Unlimited,
+}
+
+/// Token bucket rate limiter, keeping a bucket per route and per client.
+pub struct RateLimiter {
+    configuration: RateLimitConfiguration,
+    buckets: Mutex<HashMap<(String, String), TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Creates a new rate limiter from a configuration.
+    pub fn new(configuration: RateLimitConfiguration) -> Self {
+        RateLimiter {
+            configuration,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Creates a new rate limiter from the environment variables.
+    pub fn try_new() -> Result<Self, RateLimitError> {
+        RateLimitConfiguration::try_new().map(RateLimiter::new)
+    }
+
+    /// Returns `true` if the API token is configured in the client rules, meaning that it can be
+    /// used to identify a client.
+    ///
+    /// IP addresses are not considered as tokens, preventing a client from claiming the rule of
+    /// another IP address.
+    pub fn is_known_token(&self, token: &str) -> bool {
+        token.parse::<IpAddr>().is_err() && self.configuration.client_rules.contains_key(token)
+    }
+
+    /// Checks whether a client can perform a request on a route, consuming a token if so.
+    ///
+    /// # Arguments
+    ///
+    /// * `route` - The route path of the request, e.g. `/api/rcon`.
+    /// * `client` - The key identifying the client.
+    /// * `now` - The instant of the request.
+    ///
+    /// # Returns
+    ///
+    /// The decision for the request.
+    pub fn check(&self, route: &str, client: &str, now: Instant) -> RateLimitDecision {
+        if !self.configuration.enabled {
+            return RateLimitDecision::Unlimited;
+        }
+
+        let (capacity, refill_rate) = match self.configuration.rule_for(route, client) {
+            RateLimitRule::Unlimited => return RateLimitDecision::Unlimited,
+            RateLimitRule::Limited {
+                capacity,
+                refill_rate,
+            } => (*capacity, *refill_rate),
+        };
+
+        let mut buckets = self
+            .buckets
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if buckets.len() >= BUCKETS_PRUNE_THRESHOLD {
+            buckets.retain(|_, bucket| !bucket.is_full(now));
+            debug!("Pruned rate limit buckets, {} remaining", buckets.len());
+        }
+
+        let bucket = buckets
+            .entry((String::from(route), String::from(client)))
+            .or_insert_with(|| TokenBucket::new(capacity, refill_rate, now));
+
+        match bucket.try_acquire(now) {
+            Ok(remaining) => RateLimitDecision::Allowed {
+                limit: capacity,
+                remaining,
+            },
+            Err(retry_after) => {
+                // The client key is not logged as it may be an API token.
+                warn!(
+                    "Rate limit exceeded on route '{}', retry after {}ms",
+                    route,
+                    retry_after.as_millis()
+                );
+
+                RateLimitDecision::Limited {
+                    limit: capacity,
+                    retry_after,
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use crate::rate_limit::{
+        RateLimitConfiguration, RateLimitDecision, RateLimitRule, RateLimiter,
+    };
+    use std::{
+        collections::HashMap,
+        time::{Duration, Instant},
+    };
+
+    fn limiter(enabled: bool) -> RateLimiter {
+        RateLimiter::new(RateLimitConfiguration {
+            enabled,
+            default_rule: RateLimitRule::Limited {
+                capacity: 1,
+                refill_rate: 1.0,
+            },
+            route_rules: HashMap::from([(
+                String::from("/api/status"),
+                RateLimitRule::Limited {
+                    capacity: 2,
+                    refill_rate: 1.0,
+                },
+            )]),
+            client_rules: HashMap::from([(String::from("ci-token"), RateLimitRule::Unlimited)]),
+        })
+    }
+
+    #[test]
+    fn test_limited_per_client() {
+        let limiter = limiter(true);
+        let now = Instant::now();
+
+        assert_eq!(
+            limiter.check("/api/rcon", "127.0.0.1", now),
+            RateLimitDecision::Allowed {
+                limit: 1,
+                remaining: 0
+            }
+        );
+        assert_eq!(
+            limiter.check("/api/rcon", "127.0.0.1", now),
+            RateLimitDecision::Limited {
+                limit: 1,
+                retry_after: Duration::from_secs(1)
+            }
+        );
+
+        // Another client has its own bucket.
+        assert!(matches!(
+            limiter.check("/api/rcon", "127.0.0.2", now),
+            RateLimitDecision::Allowed { .. }
+        ));
+    }
+
+    #[test]
+    fn test_route_rule() {
+        let limiter = limiter(true);
+        let now = Instant::now();
+
+        assert_eq!(
+            limiter.check("/api/status", "127.0.0.1", now),
+            RateLimitDecision::Allowed {
+                limit: 2,
+                remaining: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_client_rule() {
+        let limiter = limiter(true);
+        let now = Instant::now();
+
+        for _ in 0..10 {
+            assert_eq!(
+                limiter.check("/api/rcon", "ci-token", now),
+                RateLimitDecision::Unlimited
+            );
+        }
+    }
+
+    #[test]
+    fn test_known_token() {
+        let limiter = RateLimiter::new(RateLimitConfiguration {
+            enabled: true,
+            default_rule: RateLimitRule::Unlimited,
+            route_rules: HashMap::new(),
+            client_rules: HashMap::from([
+                (String::from("ci-token"), RateLimitRule::Unlimited),
+                (String::from("10.0.0.1"), RateLimitRule::Unlimited),
+            ]),
+        });
+
+        assert!(limiter.is_known_token("ci-token"));
+        assert!(!limiter.is_known_token("random-token"));
+        assert!(!limiter.is_known_token("10.0.0.1"));
+    }
+
+    #[test]
+    fn test_disabled() {
+        let limiter = limiter(false);
+        let now = Instant::now();
+
+        for _ in 0..10 {
+            assert_eq!(
+                limiter.check("/api/rcon", "127.0.0.1", now),
+                RateLimitDecision::Unlimited
+            );
+        }
+    }
+}
@@ -0,0 +1,18 @@
+mod bucket;
+mod bucket_test;
+mod catcher;
+mod configuration;
+mod configuration_test;
+mod error;
+mod fairing;
+mod guard;
+mod limiter;
+mod limiter_test;
+
+pub use bucket::*;
+pub use catcher::*;
+pub use configuration::*;
+pub use error::*;
+pub use fairing::*;
+pub use guard::*;
+pub use limiter::*;
@@ -0,0 +1,343 @@
+use super::RconError;
+use std::{
+    collections::HashMap,
+    env,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tracing::{debug, info, warn};
+
+/// Default number of consecutive failures opening the circuit of a target.
+pub const DEFAULT_RCON_CIRCUIT_FAILURE_THRESHOLD: u32 = 5;
+
+/// Default time the circuit of a target stays open before being probed, in milliseconds.
+pub const DEFAULT_RCON_CIRCUIT_OPEN_DURATION: u64 = 30000;
+
+/// Default interval between two background probes of the open circuits, in milliseconds.
+pub const DEFAULT_RCON_CIRCUIT_PROBE_INTERVAL: u64 = 5000;
+
+#[derive(Debug, Clone)]
+pub struct RconCircuitBreakerConfiguration {
+    pub failure_threshold: u32,
+    pub open_duration: u64,
+    pub probe_interval: u64,
+}
+
+impl Default for RconCircuitBreakerConfiguration {
+    fn default() -> Self {
+        RconCircuitBreakerConfiguration {
+            failure_threshold: DEFAULT_RCON_CIRCUIT_FAILURE_THRESHOLD,
+            open_duration: DEFAULT_RCON_CIRCUIT_OPEN_DURATION,
+            probe_interval: DEFAULT_RCON_CIRCUIT_PROBE_INTERVAL,
+        }
+    }
+}
+
+impl RconCircuitBreakerConfiguration {
+    /// Returns the configuration from environment variables.
+    ///
+    /// # Returns:
+    ///
+    /// The configuration.
+    pub fn try_new() -> Result<RconCircuitBreakerConfiguration, RconError> {
+        debug!(
+            "Attempting to create RCON circuit breaker configuration from environment variables..."
+        );
+
+        let failure_threshold = env::var("RCON_CIRCUIT_FAILURE_THRESHOLD")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u32>())
+            .unwrap_or(Ok(DEFAULT_RCON_CIRCUIT_FAILURE_THRESHOLD))
+            .map_err(|_| RconError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RCON_CIRCUIT_FAILURE_THRESHOLD' is not a valid number",
+                ),
+            })?;
+        let open_duration = env::var("RCON_CIRCUIT_OPEN_DURATION")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u64>())
+            .unwrap_or(Ok(DEFAULT_RCON_CIRCUIT_OPEN_DURATION))
+            .map_err(|_| RconError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RCON_CIRCUIT_OPEN_DURATION' is not a valid number",
+                ),
+            })?;
+        let probe_interval = env::var("RCON_CIRCUIT_PROBE_INTERVAL")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u64>())
+            .unwrap_or(Ok(DEFAULT_RCON_CIRCUIT_PROBE_INTERVAL))
+            .ok()
+            .filter(|probe_interval| *probe_interval > 0)
+            .ok_or_else(|| RconError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RCON_CIRCUIT_PROBE_INTERVAL' is not a valid positive number",
+                ),
+            })?;
+
+        Ok(RconCircuitBreakerConfiguration {
+            failure_threshold,
+            open_duration,
+            probe_interval,
+        })
+    }
+}
+
+/// State of the circuit of a target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RconCircuitState {
+    /// The target is healthy, commands are sent to it.
+    Closed,
+    /// The target is unreachable, commands fail fast without being sent to it.
+    Open,
+    /// The target was unreachable, a single probe command is sent to check if it recovered.
+    HalfOpen,
+}
+
+impl RconCircuitState {
+    /// Returns the name of the state, as used in the API and in the telemetry.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RconCircuitState::Closed => "closed",
+            RconCircuitState::Open => "open",
+            RconCircuitState::HalfOpen => "half_open",
+        }
+    }
+}
+
+/// Snapshot of the circuit of a target.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RconCircuitStatus {
+    pub target: String,
+    pub state: RconCircuitState,
+    pub consecutive_failures: u32,
+    pub retry_in_ms: u64,
+}
+
+#[derive(Debug)]
+struct RconCircuit {
+    state: RconCircuitState,
+    consecutive_failures: u32,
+    opened_at: Instant,
+    probe_started_at: Option<Instant>,
+}
+
+impl RconCircuit {
+    fn new(now: Instant) -> Self {
+        RconCircuit {
+            state: RconCircuitState::Closed,
+            consecutive_failures: 0,
+            opened_at: now,
+            probe_started_at: None,
+        }
+    }
+}
+
+/// Circuit breaker failing fast on the targets that are known to be unreachable.
+///
+/// The circuit of a target opens after a number of consecutive failures. While open, commands
+/// fail right away. Once the open duration elapsed, the circuit becomes half-open and a single
+/// probe, either a command or a background connection attempt, decides whether the circuit closes
+/// again or stays open for another period.
+#[derive(Debug, Clone, Default)]
+pub struct RconCircuitBreaker {
+    configuration: Arc<RconCircuitBreakerConfiguration>,
+    circuits: Arc<Mutex<HashMap<String, RconCircuit>>>,
+}
+
+impl RconCircuitBreaker {
+    /// Creates a new circuit breaker from a configuration.
+    pub fn new(configuration: RconCircuitBreakerConfiguration) -> Self {
+        RconCircuitBreaker {
+            configuration: Arc::new(configuration),
+            circuits: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Creates a new circuit breaker from the environment variables.
+    pub fn try_new() -> Result<Self, RconError> {
+        RconCircuitBreakerConfiguration::try_new().map(RconCircuitBreaker::new)
+    }
+
+    /// Returns the configuration of the circuit breaker.
+    pub fn configuration(&self) -> &RconCircuitBreakerConfiguration {
+        &self.configuration
+    }
+
+    /// Checks whether a command can be sent to a target.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The address of the RCON server.
+    /// * `now` - The instant of the command.
+    ///
+    /// # Returns
+    ///
+    /// An `RconError::CircuitOpen` error if the command must fail fast.
+    pub fn check(&self, target: &str, now: Instant) -> Result<(), RconError> {
+        let open_duration = Duration::from_millis(self.configuration.open_duration);
+        let mut circuits = self.lock_circuits();
+        let circuit = circuits
+            .entry(String::from(target))
+            .or_insert_with(|| RconCircuit::new(now));
+
+        match circuit.state {
+            RconCircuitState::Closed => Ok(()),
+            _ if Self::can_probe(circuit, open_duration, now) => {
+                info!("Probing the RCON circuit of '{}'...", target);
+                Self::transition(target, circuit, RconCircuitState::HalfOpen);
+                circuit.probe_started_at = Some(now);
+                Ok(())
+            }
+            _ => Err(RconError::CircuitOpen {
+                target: String::from(target),
+                retry_in_ms: Self::retry_in(circuit, open_duration, now).as_millis() as u64,
+            }),
+        }
+    }
+
+    /// Records the success of a command sent to a target, closing its circuit.
+    pub fn record_success(&self, target: &str) {
+        let mut circuits = self.lock_circuits();
+        let Some(circuit) = circuits.get_mut(target) else {
+            return;
+        };
+
+        if circuit.state != RconCircuitState::Closed {
+            info!("RCON target '{}' recovered, closing its circuit", target);
+        }
+
+        circuit.consecutive_failures = 0;
+        circuit.probe_started_at = None;
+        Self::transition(target, circuit, RconCircuitState::Closed);
+    }
+
+    /// Records the failure of a command sent to a target, opening its circuit if needed.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the circuit was closed and just opened, i.e. the target became unreachable.
+    pub fn record_failure(&self, target: &str, now: Instant) -> bool {
+        let threshold = self.configuration.failure_threshold;
+        let mut circuits = self.lock_circuits();
+        let circuit = circuits
+            .entry(String::from(target))
+            .or_insert_with(|| RconCircuit::new(now));
+
+        circuit.consecutive_failures = circuit.consecutive_failures.saturating_add(1);
+        circuit.probe_started_at = None;
+
+        let should_open = match circuit.state {
+            RconCircuitState::HalfOpen => true,
+            RconCircuitState::Closed => threshold > 0 && circuit.consecutive_failures >= threshold,
+            RconCircuitState::Open => false,
+        };
+        let became_unreachable = should_open && circuit.state == RconCircuitState::Closed;
+
+        if should_open {
+            warn!(
+                "RCON target '{}' is unreachable after {} consecutive failures, opening its circuit",
+                target, circuit.consecutive_failures
+            );
+            circuit.opened_at = now;
+            Self::transition(target, circuit, RconCircuitState::Open);
+
+            #[cfg(feature = "metrics")]
+            crate::metrics::RCON_CIRCUIT_OPENED_TOTAL
+                .with_label_values(&[target])
+                .inc();
+        }
+
+        became_unreachable
+    }
+
+    /// Returns the open targets that are due for a probe, marking them as being probed.
+    pub fn targets_to_probe(&self, now: Instant) -> Vec<String> {
+        let open_duration = Duration::from_millis(self.configuration.open_duration);
+        let mut circuits = self.lock_circuits();
+
+        circuits
+            .iter_mut()
+            .filter(|(_, circuit)| circuit.state != RconCircuitState::Closed)
+            .filter(|(_, circuit)| Self::can_probe(circuit, open_duration, now))
+            .map(|(target, circuit)| {
+                Self::transition(target, circuit, RconCircuitState::HalfOpen);
+                circuit.probe_started_at = Some(now);
+                target.clone()
+            })
+            .collect()
+    }
+
+    /// Returns a snapshot of the circuits of all the known targets.
+    pub fn status(&self, now: Instant) -> Vec<RconCircuitStatus> {
+        let open_duration = Duration::from_millis(self.configuration.open_duration);
+        let circuits = self.lock_circuits();
+
+        let mut status: Vec<RconCircuitStatus> = circuits
+            .iter()
+            .map(|(target, circuit)| RconCircuitStatus {
+                target: target.clone(),
+                state: circuit.state,
+                consecutive_failures: circuit.consecutive_failures,
+                retry_in_ms: Self::retry_in(circuit, open_duration, now).as_millis() as u64,
+            })
+            .collect();
+        status.sort_by(|a, b| a.target.cmp(&b.target));
+
+        status
+    }
+
+    /// Returns `true` if the error means that the target may be unreachable.
+    pub fn is_failure(error: &RconError) -> bool {
+        matches!(
+            error,
+            RconError::Connection { .. }
+                | RconError::ConnectTimeout { .. }
+                | RconError::AuthTimeout { .. }
+                | RconError::Send { .. }
+                | RconError::WriteTimeout { .. }
+                | RconError::Receive { .. }
+                | RconError::Timeout { .. }
+                | RconError::DeadlineExceeded { .. }
+        )
+    }
+
+    /// Returns `true` if a probe can be sent through a non-closed circuit. A new probe is allowed
+    /// once the open duration elapsed, and again if the previous probe never completed.
+    fn can_probe(circuit: &RconCircuit, open_duration: Duration, now: Instant) -> bool {
+        let since = circuit.probe_started_at.unwrap_or(circuit.opened_at);
+        now.saturating_duration_since(since) >= open_duration
+    }
+
+    fn retry_in(circuit: &RconCircuit, open_duration: Duration, now: Instant) -> Duration {
+        match circuit.state {
+            RconCircuitState::Closed => Duration::ZERO,
+            _ => {
+                let since = circuit.probe_started_at.unwrap_or(circuit.opened_at);
+                open_duration.saturating_sub(now.saturating_duration_since(since))
+            }
+        }
+    }
+
+    #[allow(unused_variables)]
+    fn transition(target: &str, circuit: &mut RconCircuit, state: RconCircuitState) {
+        circuit.state = state;
+
+        #[cfg(feature = "metrics")]
+        crate::metrics::RCON_CIRCUIT_STATE
+            .with_label_values(&[target])
+            .set(match state {
+                RconCircuitState::Closed => 0,
+                RconCircuitState::HalfOpen => 1,
+                RconCircuitState::Open => 2,
+            });
+    }
+
+    fn lock_circuits(&self) -> std::sync::MutexGuard<'_, HashMap<String, RconCircuit>> {
+        self.circuits
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use crate::rcon::{
+        RconCircuitBreaker, RconCircuitBreakerConfiguration, RconCircuitState, RconError,
+    };
+    use std::time::{Duration, Instant};
+
+    const TARGET: &str = "localhost:25575";
+
+    fn circuit_breaker() -> RconCircuitBreaker {
+        RconCircuitBreaker::new(RconCircuitBreakerConfiguration {
+            failure_threshold: 2,
+            open_duration: 1000,
+            probe_interval: 100,
+        })
+    }
+
+    fn state(circuit_breaker: &RconCircuitBreaker, now: Instant) -> RconCircuitState {
+        circuit_breaker.status(now)[0].state
+    }
+
+    #[test]
+    fn test_opens_after_threshold() {
+        let circuit_breaker = circuit_breaker();
+        let now = Instant::now();
+
+        assert!(circuit_breaker.check(TARGET, now).is_ok());
+        assert!(!circuit_breaker.record_failure(TARGET, now));
+        assert_eq!(state(&circuit_breaker, now), RconCircuitState::Closed);
+
+        assert!(circuit_breaker.record_failure(TARGET, now));
+        assert_eq!(state(&circuit_breaker, now), RconCircuitState::Open);
+
+        let later = now + Duration::from_millis(400);
+        assert_eq!(
+            circuit_breaker.check(TARGET, later),
+            Err(RconError::CircuitOpen {
+                target: String::from(TARGET),
+                retry_in_ms: 600
+            })
+        );
+    }
+
+    #[test]
+    fn test_success_resets_failures() {
+        let circuit_breaker = circuit_breaker();
+        let now = Instant::now();
+
+        circuit_breaker.record_failure(TARGET, now);
+        circuit_breaker.record_success(TARGET);
+        circuit_breaker.record_failure(TARGET, now);
+
+        assert_eq!(state(&circuit_breaker, now), RconCircuitState::Closed);
+        assert_eq!(circuit_breaker.status(now)[0].consecutive_failures, 1);
+    }
+
+    #[test]
+    fn test_half_open_single_probe() {
+        let circuit_breaker = circuit_breaker();
+        let now = Instant::now();
+
+        circuit_breaker.record_failure(TARGET, now);
+        circuit_breaker.record_failure(TARGET, now);
+
+        // The first command after the open duration is the probe, the others still fail fast.
+        let later = now + Duration::from_millis(1000);
+        assert!(circuit_breaker.check(TARGET, later).is_ok());
+        assert_eq!(state(&circuit_breaker, later), RconCircuitState::HalfOpen);
+        assert!(circuit_breaker.check(TARGET, later).is_err());
+
+        circuit_breaker.record_success(TARGET);
+        assert_eq!(state(&circuit_breaker, later), RconCircuitState::Closed);
+        assert!(circuit_breaker.check(TARGET, later).is_ok());
+    }
+
+    #[test]
+    fn test_half_open_failed_probe_reopens() {
+        let circuit_breaker = circuit_breaker();
+        let now = Instant::now();
+
+        circuit_breaker.record_failure(TARGET, now);
+        circuit_breaker.record_failure(TARGET, now);
+
+        let later = now + Duration::from_millis(1000);
+        assert!(circuit_breaker.check(TARGET, later).is_ok());
+        // The target was already known to be unreachable.
+        assert!(!circuit_breaker.record_failure(TARGET, later));
+
+        assert_eq!(state(&circuit_breaker, later), RconCircuitState::Open);
+        assert!(circuit_breaker.check(TARGET, later).is_err());
+    }
+
+    #[test]
+    fn test_targets_to_probe() {
+        let circuit_breaker = circuit_breaker();
+        let now = Instant::now();
+
+        circuit_breaker.record_failure(TARGET, now);
+        circuit_breaker.record_failure(TARGET, now);
+        assert!(circuit_breaker.targets_to_probe(now).is_empty());
+
+        let later = now + Duration::from_millis(1000);
+        assert_eq!(circuit_breaker.targets_to_probe(later), vec![TARGET]);
+        // Already being probed.
+        assert!(circuit_breaker.targets_to_probe(later).is_empty());
+    }
+}
@@ -1,51 +1,95 @@
-use super::{RconConfiguration, RconError, RconRequest, RconRequestType, RconResponse};
-use std::time::Duration;
+use super::{
+    DEFAULT_RCON_TIMEOUT, ProcessRconConnection, RconCircuitBreaker, RconConfiguration,
+    RconConnection, RconConsole, RconError, RconRequest, RconRequestType, RconResponse,
+    RconResponsePacket, RconResponseType, RconRetryPolicy, RconTermination, RconTransport,
+    RconTransportType,
+};
+use crate::webhooks::{WebhookDispatcher, WebhookEvent, WebhookEventKind};
+use std::time::{Duration, Instant};
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
     net::TcpStream,
-    time::timeout,
+    time::{sleep, timeout},
 };
-use tracing::{debug, info, trace, warn};
-
-/// Maximum packet size for sending data.
-///
-/// # Note:
-///
-/// The maximum length of the payload is 1446 bytes.
-/// The message lenght is 4 bytes.
-/// The request ID is 4 bytes.
-/// The request type is 4 bytes.
-/// The null terminator for the payload is 1 byte.
-/// The null terminator for the packet is 1 byte.
-///
-/// Total: 1446 + 4 + 4 + 4 + 1 + 1 = 1460
-///
-/// - [Fragmentation](https://minecraft.wiki/w/RCON#Fragmentation)
-/// - [Packet format](https://minecraft.wiki/w/RCON#Packet_format)
-const MAX_RCON_REQUEST_SIZE: usize = 1460;
-
-/// Maximum packet size for receiving data.
-///     
-/// # Note:
-///
-/// The maximum length of the payload is 4096 bytes.
-/// The message lenght is 4 bytes.
-/// The request ID is 4 bytes.
-/// The request type is 4 bytes.
-/// The null terminator for the payload is 1 byte.
-/// The null terminator for the packet is 1 byte.
-///
-/// Total: 4096 + 4 + 4 + 4 + 1 + 1 = 4110
-///
-/// - [Fragmentation](https://minecraft.wiki/w/RCON#Fragmentation)
-/// - [Packet format](https://minecraft.wiki/w/RCON#Packet_format)
-const MAX_RCON_RESPONSE_SIZE: usize = 4110;
-
-#[derive(Default, Debug)]
-pub struct RconClient {}
+use tracing::{Instrument, Span, debug, info, info_span, trace, warn};
+
+/// Maximum number of packets with an unexpected ID discarded while waiting for a response,
+/// before considering that the client and the server are out of sync.
+const MAX_RCON_STALE_PACKETS: usize = 8;
+
+#[derive(Default, Debug, Clone)]
+pub struct RconClient {
+    circuit_breaker: RconCircuitBreaker,
+    /// Console of the server process, used when the commands are written to it.
+    console: RconConsole,
+    /// Dispatcher notified when the server becomes unreachable or rejects the password.
+    webhooks: WebhookDispatcher,
+}
 
 impl RconClient {
-    /// Connects to the server.
+    /// Creates a new client from the environment variables.
+    pub fn try_new() -> Result<Self, RconError> {
+        Ok(RconClient {
+            circuit_breaker: RconCircuitBreaker::try_new()?,
+            console: RconConsole::default(),
+            webhooks: WebhookDispatcher::default(),
+        })
+    }
+
+    /// Notifies a webhook dispatcher of the failures of the client.
+    pub fn with_webhooks(mut self, webhooks: WebhookDispatcher) -> Self {
+        self.webhooks = webhooks;
+        self
+    }
+
+    /// Returns the dispatcher notified of the failures of the client.
+    pub fn webhooks(&self) -> &WebhookDispatcher {
+        &self.webhooks
+    }
+
+    /// Returns the console of the server process, used when the commands are written to it.
+    pub fn console(&self) -> &RconConsole {
+        &self.console
+    }
+
+    /// Returns the circuit breaker guarding the targets of the client.
+    pub fn circuit_breaker(&self) -> &RconCircuitBreaker {
+        &self.circuit_breaker
+    }
+
+    /// Starts the server process when the commands are written to its console, so it does not
+    /// wait for the first command, unless the process is supervised.
+    pub async fn start_console(&self) -> Result<(), RconError> {
+        // The errors of the configuration are reported by the commands.
+        match RconConfiguration::try_new() {
+            Ok(configuration)
+                if configuration.transport == RconTransportType::Process
+                    && !self.console.is_supervised() =>
+            {
+                self.console.start(&configuration).await
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Opens a connection to the server over the configured transport.
+    ///
+    /// # Returns:
+    ///
+    /// A connection to the server, not logged in yet.
+    pub async fn connect(&self) -> Result<RconConnection, RconError> {
+        let configuration = RconConfiguration::try_new()?;
+
+        match configuration.transport {
+            RconTransportType::Tcp => self.get_connection().await.map(RconConnection::Tcp),
+            RconTransportType::Process => Ok(RconConnection::Process(ProcessRconConnection::new(
+                self.console.clone(),
+                configuration,
+            ))),
+        }
+    }
+
+    /// Connects to the server over TCP.
     ///
     /// # Returns:
     ///
@@ -55,29 +99,312 @@ impl RconClient {
         skip(self),
         fields(
             app.component = "rcon",
+            server.address = tracing::field::Empty,
+            server.port = tracing::field::Empty,
         )
     )]
     pub async fn get_connection(&self) -> Result<ConnectedRconClient, RconError> {
         let configuration = RconConfiguration::try_new()?;
-        let address = format!("{}:{}", configuration.host, configuration.port);
+        let address = configuration.address();
+
+        let span = Span::current();
+        span.record("server.address", configuration.host.as_str());
+        span.record("server.port", configuration.port);
 
         info!("Getting a new RCON connection to '{}'...", address);
-        let stream = TcpStream::connect(address)
-            .await
-            .map_err(|err| RconError::Connection {
-                cause: err.to_string(),
-            })?;
+        let started_at = Instant::now();
+        let stream = timeout(
+            Duration::from_millis(configuration.connect_timeout),
+            TcpStream::connect(address),
+        )
+        .await
+        .map_err(|_| RconError::ConnectTimeout {
+            elapsed_ms: started_at.elapsed().as_millis() as u64,
+        })?
+        .map_err(|err| RconError::Connection {
+            cause: err.to_string(),
+        })?;
+
+        Ok(ConnectedRconClient {
+            stream,
+            configuration,
+        })
+    }
+
+    /// Executes a request on the server: connects, logs in, sends the request and disconnects.
+    ///
+    /// Failed attempts are retried according to the `RconRetryPolicy`. Once the request may have
+    /// reached the server, it is only retried if it is idempotent, either because it was marked
+    /// as such or because its command is listed in the idempotent commands of the policy.
+    ///
+    /// The request fails fast with an `RconError::CircuitOpen` error while the server is known to
+    /// be unreachable, and with an `RconError::DeadlineExceeded` error once the command timeout
+    /// elapsed, whatever the number of attempts left.
+    ///
+    /// The span of the execution describes the request and its outcome. The arguments of the
+    /// command are redacted unless `RCON_REDACT_COMMAND_ARGUMENTS` is disabled.
+    ///
+    /// # Parameters
+    ///
+    /// - `request`: The request to execute on the server.
+    ///
+    /// # Returns
+    ///
+    /// The response from the server.
+    #[tracing::instrument(
+        name = "Rcon.Execute",
+        skip(self, request),
+        fields(
+            app.component = "rcon",
+            server.address = tracing::field::Empty,
+            rcon.dialect = tracing::field::Empty,
+            rcon.request.id = request.request_id,
+            rcon.request.type = request.request_type.as_str(),
+            rcon.request.size = request.request_payload.len(),
+            rcon.command.verb = tracing::field::Empty,
+            rcon.command = tracing::field::Empty,
+            rcon.response.type = tracing::field::Empty,
+            rcon.response.size = tracing::field::Empty,
+            rcon.attempts = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+            otel.status_code = tracing::field::Empty,
+        )
+    )]
+    pub async fn execute(&self, request: &RconRequest) -> Result<RconResponse, RconError> {
+        let configuration = RconConfiguration::try_new()?;
+        let target = configuration.address();
+        let started_at = Instant::now();
+
+        let span = Span::current();
+        span.record("server.address", target.as_str());
+        span.record("rcon.dialect", configuration.dialect.as_str());
+        if let Some(verb) = request.command_verb() {
+            span.record("rcon.command.verb", verb.as_str());
+        }
+        span.record(
+            "rcon.command",
+            request
+                .traced_payload(configuration.redact_command_arguments)
+                .as_str(),
+        );
+
+        self.circuit_breaker
+            .check(&target, started_at)
+            .inspect_err(|error| Self::record_command(&span, &target, started_at, Err(error)))?;
+
+        let result = timeout(
+            Duration::from_millis(configuration.command_timeout),
+            self.execute_with_retries(request),
+        )
+        .await
+        .unwrap_or_else(|_| {
+            let elapsed_ms = started_at.elapsed().as_millis() as u64;
+            warn!("RCON command exceeded its deadline after {}ms", elapsed_ms);
+            Err(RconError::DeadlineExceeded { elapsed_ms })
+        });
+
+        match &result {
+            Err(error) if RconCircuitBreaker::is_failure(error) => {
+                if self.circuit_breaker.record_failure(&target, Instant::now()) {
+                    self.webhooks.dispatch(
+                        WebhookEvent::new(
+                            WebhookEventKind::Unreachable,
+                            format!("The RCON server '{target}' is unreachable: {error}"),
+                        )
+                        .with_data("target", target.as_str())
+                        .with_data("error", error.kind()),
+                    );
+                }
+            }
+            Err(error @ RconError::Authentication { .. }) => {
+                // The server answered, but the password is wrong.
+                self.circuit_breaker.record_success(&target);
+                self.webhooks.dispatch(
+                    WebhookEvent::new(
+                        WebhookEventKind::AuthenticationFailed,
+                        format!("The RCON login to '{target}' failed: {error}"),
+                    )
+                    .with_data("target", target.as_str()),
+                );
+            }
+            // The server answered, even if the outcome is not the expected one.
+            Ok(_) | Err(RconError::Shutdown { .. }) => self.circuit_breaker.record_success(&target),
+            Err(_) => {}
+        }
+
+        Self::record_command(&span, &target, started_at, result.as_ref());
+
+        result
+    }
+
+    /// Records the outcome of a command in its span and in the metrics.
+    #[allow(unused_variables)]
+    fn record_command(
+        span: &Span,
+        target: &str,
+        started_at: Instant,
+        result: Result<&RconResponse, &RconError>,
+    ) {
+        match result {
+            Ok(response) => {
+                span.record("rcon.response.type", response.response_type.as_str());
+                span.record("rcon.response.size", response.response_bytes.len());
+            }
+            Err(error) => {
+                span.record("error.type", error.kind());
+                span.record("otel.status_code", "ERROR");
+            }
+        }
+
+        #[cfg(feature = "opentelemetry")]
+        crate::telemetry::record_rcon_command(target, started_at.elapsed(), result.err());
+    }
+
+    /// Probes the open circuits that are due for a probe, with a connection attempt to their
+    /// target, or an attempt to start the server process for the console.
+    #[tracing::instrument(
+        name = "Rcon.ProbeCircuits",
+        skip(self),
+        fields(
+            app.component = "rcon",
+        )
+    )]
+    pub async fn probe_circuits(&self) {
+        let configuration = RconConfiguration::try_new().ok();
+        let connect_timeout = configuration
+            .as_ref()
+            .map(|configuration| configuration.connect_timeout)
+            .unwrap_or(DEFAULT_RCON_TIMEOUT);
+
+        for target in self.circuit_breaker.targets_to_probe(Instant::now()) {
+            debug!("Probing the RCON server '{}'...", target);
+
+            let reachable = match &configuration {
+                Some(configuration)
+                    if configuration.transport == RconTransportType::Process
+                        && target == configuration.address() =>
+                {
+                    self.console.start(configuration).await.is_ok()
+                }
+                _ => matches!(
+                    timeout(
+                        Duration::from_millis(connect_timeout),
+                        TcpStream::connect(&target),
+                    )
+                    .await,
+                    Ok(Ok(_))
+                ),
+            };
+
+            if reachable {
+                self.circuit_breaker.record_success(&target);
+            } else {
+                self.circuit_breaker.record_failure(&target, Instant::now());
+            }
+        }
+    }
+
+    /// Executes a request on the server, retrying the failed attempts according to the
+    /// `RconRetryPolicy`.
+    async fn execute_with_retries(&self, request: &RconRequest) -> Result<RconResponse, RconError> {
+        let policy = RconRetryPolicy::try_new()?;
+        let idempotent =
+            request.idempotent || policy.is_idempotent_command(&request.request_payload);
+
+        let mut attempt = 1;
+        loop {
+            let attempt_span = info_span!(
+                "Rcon.Attempt",
+                app.component = "rcon",
+                rcon.attempt = attempt,
+                error.type = tracing::field::Empty,
+            );
+            let result = self
+                .execute_attempt(request)
+                .instrument(attempt_span.clone())
+                .await;
+
+            if let Err((error, _)) = &result {
+                attempt_span.record("error.type", error.kind());
+            }
 
-        Ok(ConnectedRconClient { stream })
+            match result {
+                Ok(response) => {
+                    Span::current().record("rcon.attempts", attempt);
+                    return Ok(response);
+                }
+                Err((error, sent)) if policy.should_retry(&error, attempt, !sent || idempotent) => {
+                    let delay = policy.delay(attempt);
+                    warn!(
+                        "RCON attempt {} failed: {}, retrying in {}ms...",
+                        attempt,
+                        error,
+                        delay.as_millis()
+                    );
+
+                    sleep(delay).await;
+                    attempt += 1;
+                }
+                Err((error, _)) => {
+                    Span::current().record("rcon.attempts", attempt);
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    /// Executes a single attempt of a request on the server.
+    ///
+    /// # Returns
+    ///
+    /// The response from the server, otherwise the error along with whether the request may have
+    /// reached the server.
+    async fn execute_attempt(
+        &self,
+        request: &RconRequest,
+    ) -> Result<RconResponse, (RconError, bool)> {
+        let mut connection = self.connect().await.map_err(|err| (err, false))?;
+
+        // Keep the result aside to ensure the connection is disconnected regardless of it.
+        let mut sent = false;
+        let result = async {
+            if !connection.login().await? {
+                return Err(RconError::Authentication {
+                    cause: String::from("The RCON password was refused"),
+                });
+            }
+
+            sent = true;
+            let response = connection.request(request).await?;
+
+            if response.response_type == RconResponseType::AuthResponse {
+                // This would happen only if the auth changes between the login and the command.
+                return Err(RconError::Authentication {
+                    cause: String::from("The RCON session is not authenticated"),
+                });
+            }
+
+            Ok(response)
+        }
+        .await;
+
+        let disconnect_result = connection.disconnect().await;
+
+        match (result, disconnect_result) {
+            (Ok(response), Ok(())) => Ok(response),
+            (Ok(_), Err(err)) => Err((err, true)),
+            (Err(err), _) => Err((err, sent)),
+        }
     }
 }
 
 #[derive(Debug)]
 pub struct ConnectedRconClient {
     stream: TcpStream,
+    configuration: RconConfiguration,
 }
 
-impl ConnectedRconClient {
+impl RconTransport for ConnectedRconClient {
     /// Sends a request to the server and receives a response.
     ///
     /// # Parameters
@@ -92,21 +419,37 @@ impl ConnectedRconClient {
         skip(self),
         fields(
             app.component = "rcon",
+            rcon.login.result = tracing::field::Empty,
         )
     )]
-    pub async fn login(&mut self) -> Result<bool, RconError> {
-        let configuration = RconConfiguration::try_new()?;
-        let login_request = RconRequest::new(RconRequestType::Auth, configuration.password);
+    async fn login(&mut self) -> Result<bool, RconError> {
+        let login_request = RconRequest::new(
+            RconRequestType::Auth,
+            self.configuration.password.expose().into(),
+        );
+        let auth_timeout = self.configuration.auth_timeout;
 
         // Send the request
         info!("Logging in to the RCON server...");
-        let response = self.request(&login_request).await?;
+        let started_at = Instant::now();
+        let response = timeout(
+            Duration::from_millis(auth_timeout),
+            self.request(&login_request),
+        )
+        .await
+        .map_err(|_| RconError::AuthTimeout {
+            elapsed_ms: started_at.elapsed().as_millis() as u64,
+        })??;
 
         // If authentication was successful, the ID assigned by the request.
         // If auth failed, -1.
         //
         // - [RCON Auth Response](https://developer.valvesoftware.com/wiki/Source_RCON_Protocol#SERVERDATA_AUTH_RESPONSE)
         let login_status = response.response_id != -1;
+        Span::current().record(
+            "rcon.login.result",
+            if login_status { "success" } else { "refused" },
+        );
 
         if !login_status {
             warn!("Failed to login to the RCON server");
@@ -119,6 +462,16 @@ impl ConnectedRconClient {
 
     /// Sends a request to the server and receives a response.
     ///
+    /// The response to a command may be split in several packets when too long. Depending on the
+    /// dialect of the server, a sentinel request is sent after the command, its answer marking the
+    /// end of the response.
+    ///
+    /// The empty `RESPONSE_VALUE` packet sent by the Source servers before the `AUTH_RESPONSE` one
+    /// is skipped.
+    ///
+    /// Packets whose ID does not match the one of the request, left over by a previous request,
+    /// are discarded. An `RconError::Desync` error is returned if too many of them are received.
+    ///
     /// # Parameters
     ///
     /// - `request`: The request to send to the server.
@@ -131,18 +484,109 @@ impl ConnectedRconClient {
         skip(self, request),
         fields(
             app.component = "rcon",
+            rcon.request.id = request.request_id,
+            rcon.request.type = request.request_type.as_str(),
+            rcon.request.size = request.request_payload.len(),
+            rcon.response.type = tracing::field::Empty,
+            rcon.response.size = tracing::field::Empty,
+            rcon.response.fragments = tracing::field::Empty,
+            rcon.packets.discarded = tracing::field::Empty,
         )
     )]
-    pub async fn request(&mut self, request: &RconRequest) -> Result<RconResponse, RconError> {
+    async fn request(&mut self, request: &RconRequest) -> Result<RconResponse, RconError> {
         // Send the request
         info!("Sending a request to the RCON server...");
         self.send(request).await?;
+
+        let dialect = self.configuration.dialect;
+        let sentinel_type = match (&request.request_type, dialect.termination()) {
+            (RconRequestType::ExecCommand, RconTermination::Sentinel) => {
+                Some(RconRequestType::Sentinel)
+            }
+            (RconRequestType::ExecCommand, RconTermination::Mirror) => {
+                Some(RconRequestType::Mirror)
+            }
+            _ => None,
+        };
+        let sentinel_id = match sentinel_type {
+            Some(sentinel_type) => {
+                let mut sentinel = RconRequest::new(sentinel_type, String::new());
+                if sentinel.request_id == request.request_id {
+                    sentinel.request_id = sentinel.request_id.wrapping_add(1);
+                }
+
+                self.send(&sentinel).await?;
+                Some(sentinel.request_id)
+            }
+            None => None,
+        };
+        let mut expects_auth_preamble = request.request_type == RconRequestType::Auth
+            && dialect.sends_empty_response_before_auth();
         debug!("Request sent successfully");
 
         // Receive the response
         info!("Waiging for response...");
-        let response = self.receive().await?;
+        let mut fragments = Vec::new();
+        let mut discarded = 0;
+        loop {
+            let packet = self.receive().await?;
+
+            if expects_auth_preamble && packet.response_type == RconResponseType::ResponseValue {
+                expects_auth_preamble = false;
+                debug!("Skipping the empty response sent before the authentication response");
+                continue;
+            }
+
+            // A refused authentication is answered with the -1 ID, whatever the request.
+            //
+            // - [RCON Auth Response](https://developer.valvesoftware.com/wiki/Source_RCON_Protocol#SERVERDATA_AUTH_RESPONSE)
+            if packet.response_id == -1 && packet.response_type == RconResponseType::AuthResponse {
+                fragments = vec![packet];
+                break;
+            }
+
+            if packet.response_id == request.request_id {
+                fragments.push(packet);
+                match sentinel_id {
+                    Some(_) => continue,
+                    None => break,
+                }
+            }
+
+            if sentinel_id == Some(packet.response_id) && !fragments.is_empty() {
+                break;
+            }
+
+            if discarded >= MAX_RCON_STALE_PACKETS {
+                warn!(
+                    "Too many unexpected RCON packets, expected ID {} but received {}",
+                    request.request_id, packet.response_id
+                );
+                return Err(RconError::Desync {
+                    expected: request.request_id,
+                    received: packet.response_id,
+                });
+            }
+
+            discarded += 1;
+            warn!(
+                "Discarding an RCON packet with the unexpected ID {}, expected {}",
+                packet.response_id, request.request_id
+            );
+        }
+
+        let span = Span::current();
+        span.record("rcon.response.fragments", fragments.len());
+        span.record("rcon.packets.discarded", discarded);
+
+        let response = RconResponse::try_from_fragments(fragments, self.configuration.encoding)
+            .map_err(|err| RconError::Receive {
+                cause: err.to_string(),
+            })?;
+        span.record("rcon.response.type", response.response_type.as_str());
+        span.record("rcon.response.size", response.response_bytes.len());
         debug!("Response received successfully");
+        info!("Response: {:?}", response);
 
         Ok(response)
     }
@@ -159,7 +603,7 @@ impl ConnectedRconClient {
             app.component = "rcon",
         )
     )]
-    pub async fn disconnect(&mut self) -> Result<(), RconError> {
+    async fn disconnect(&mut self) -> Result<(), RconError> {
         info!("Disconnecting from the RCON server...");
         self.stream
             .shutdown()
@@ -168,7 +612,9 @@ impl ConnectedRconClient {
                 cause: err.to_string(),
             })
     }
+}
 
+impl ConnectedRconClient {
     /// Sends a request to the server.
     ///
     /// # Parameters
@@ -188,33 +634,56 @@ impl ConnectedRconClient {
     async fn send(&mut self, request: &RconRequest) -> Result<(), RconError> {
         let bytes = request.to_rcon_bytes();
         trace!("Request: {:?}", request);
-        trace!("Request bytes: {:?}", bytes);
+        // The payload of the `Auth` requests is the password.
+        if request.request_type != RconRequestType::Auth {
+            trace!("Request bytes: {:?}", bytes);
+        }
 
-        if bytes.len() > MAX_RCON_REQUEST_SIZE {
-            return Err(RconError::Send {
-                cause: String::from("Request size exceeds the maximum size"),
+        let max_size = self.configuration.max_payload_size;
+        if request.request_payload.len() > max_size {
+            return Err(RconError::RequestTooLarge {
+                size: request.request_payload.len(),
+                max_size,
             });
         }
 
-        debug!("Sending request to the RCON server...");
-        self.stream
-            .write_all(&bytes)
-            .await
-            .map_err(|err| RconError::Send {
-                cause: err.to_string(),
-            })?;
+        let started_at = Instant::now();
+        let write = async {
+            debug!("Sending request to the RCON server...");
+            self.stream.write_all(&bytes).await?;
+
+            debug!("Flushing...");
+            self.stream.flush().await
+        };
 
-        debug!("Flushing...");
-        self.stream.flush().await.map_err(|err| RconError::Send {
+        timeout(
+            Duration::from_millis(self.configuration.write_timeout),
+            write,
+        )
+        .await
+        .map_err(|_| RconError::WriteTimeout {
+            elapsed_ms: started_at.elapsed().as_millis() as u64,
+        })?
+        .map_err(|err| RconError::Send {
             cause: err.to_string(),
-        })
+        })?;
+
+        info!(
+            rcon.packet.id = request.request_id,
+            rcon.packet.type = request.request_type.as_str(),
+            rcon.packet.size = bytes.len(),
+            rcon.packet.elapsed_ms = started_at.elapsed().as_millis() as u64,
+            "RCON packet sent"
+        );
+
+        Ok(())
     }
 
-    /// Receives a response from the server.
+    /// Receives a packet from the server.
     ///
     /// # Returns
     ///
-    /// The response from the server.
+    /// The packet from the server.
     #[tracing::instrument(
         name = "Rcon.Receive",
         skip(self),
@@ -222,41 +691,77 @@ impl ConnectedRconClient {
             app.component = "rcon",
         )
     )]
-    async fn receive(&mut self) -> Result<RconResponse, RconError> {
-        let mut response_buffer = [0u8; MAX_RCON_RESPONSE_SIZE];
-
+    async fn receive(&mut self) -> Result<RconResponsePacket, RconError> {
         debug!("Receiving response from the RCON server...");
 
-        let configuration = RconConfiguration::try_new()?;
-        timeout(
-            Duration::from_millis(configuration.timeout),
-            self.stream.read(&mut response_buffer),
+        let read_timeout = self.configuration.read_timeout;
+        let started_at = Instant::now();
+        let response_buffer = timeout(Duration::from_millis(read_timeout), self.read_packet())
+            .await
+            .map_err(|_| RconError::Timeout {
+                elapsed_ms: read_timeout,
+            })??;
+
+        trace!("Response bytes: {:?}", response_buffer);
+
+        let packet = RconResponsePacket::try_from_rcon_bytes_with_max_size(
+            &response_buffer,
+            self.configuration.dialect.max_response_size(),
         )
-        .await
-        .map_err(|_| RconError::Timeout {
-            elapsed_ms: configuration.timeout,
-        })?
         .map_err(|err| RconError::Receive {
             cause: err.to_string(),
         })?;
 
-        trace!("Response bytes: {:?}", response_buffer);
+        info!(
+            rcon.packet.id = packet.response_id,
+            rcon.packet.type = packet.response_type.as_str(),
+            rcon.packet.size = response_buffer.len(),
+            rcon.packet.elapsed_ms = started_at.elapsed().as_millis() as u64,
+            "RCON packet received"
+        );
+
+        Ok(packet)
+    }
 
-        let response = RconResponse::try_from_rcon_bytes(&response_buffer).map_err(|err| {
-            RconError::Receive {
+    /// Reads a whole packet from the server, using its size field to know where it ends.
+    ///
+    /// # Returns
+    ///
+    /// The bytes of the packet, including its size field.
+    async fn read_packet(&mut self) -> Result<Vec<u8>, RconError> {
+        let mut size_bytes = [0u8; 4];
+        self.stream
+            .read_exact(&mut size_bytes)
+            .await
+            .map_err(|err| RconError::Receive {
                 cause: err.to_string(),
-            }
+            })?;
+
+        // Remember to use little endian
+        let size = RconResponsePacket::validate_size(
+            i32::from_le_bytes(size_bytes),
+            self.configuration.dialect.max_response_size(),
+        )
+        .map_err(|err| RconError::Receive {
+            cause: err.to_string(),
         })?;
 
-        info!("Response: {:?}", response);
+        let mut packet = vec![0u8; 4 + size];
+        packet[0..4].copy_from_slice(&size_bytes);
+        self.stream
+            .read_exact(&mut packet[4..])
+            .await
+            .map_err(|err| RconError::Receive {
+                cause: err.to_string(),
+            })?;
 
-        Ok(response)
+        Ok(packet)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::rcon::RconClient;
+    use crate::rcon::{RconClient, RconTransport};
     use serial_test::serial;
 
     #[tokio::test]
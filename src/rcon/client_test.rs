@@ -1,8 +1,22 @@
 #[cfg(test)]
 mod tests {
-    use crate::rcon::{RconClient, RconError, RconRequest, RconRequestType, RconResponseType};
+    use crate::rcon::{
+        RconClient, RconError, RconRequest, RconRequestType, RconResponseType, RconTransport,
+    };
     use serial_test::serial;
+    use std::time::{Duration, Instant};
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
     use temp_env::async_with_vars;
+    use test_case::test_case;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+    use tracing::Level;
+    use tracing_subscriber::fmt::{MakeWriter, format::FmtSpan};
 
     #[tokio::test]
     #[serial(rcon)]
@@ -119,4 +133,608 @@ mod tests {
 
         connection.disconnect().await.ok();
     }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_execute() {
+        let request = RconRequest::new(RconRequestType::ExecCommand, String::from("help"));
+        let response_result = RconClient::default().execute(&request).await;
+        assert!(response_result.is_ok());
+
+        let response = response_result.unwrap();
+        assert_eq!(response.response_type, RconResponseType::ResponseValue);
+        assert_eq!(response.response_id, request.request_id);
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_execute_wrong_password() {
+        async_with_vars([("RCON_PASSWORD", Some("wrong_password"))], async {
+            let request = RconRequest::new(RconRequestType::ExecCommand, String::from("help"));
+            let response_result = RconClient::default().execute(&request).await;
+
+            assert!(matches!(
+                response_result,
+                Err(RconError::Authentication { .. })
+            ));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_execute_retries_connection() {
+        async_with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_RETRY_MAX_ATTEMPTS", Some("3")),
+                ("RCON_RETRY_BASE_DELAY", Some("50")),
+                ("RCON_RETRY_JITTER", Some("false")),
+            ],
+            async {
+                let started_at = Instant::now();
+
+                let request =
+                    RconRequest::new(RconRequestType::ExecCommand, String::from("say hi"));
+                let response_result = RconClient::default().execute(&request).await;
+
+                assert!(matches!(response_result, Err(RconError::Connection { .. })));
+                // Two retries, waiting 50ms then 100ms.
+                assert!(started_at.elapsed() >= Duration::from_millis(150));
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_execute_auth_timeout() {
+        // A server accepting connections without ever answering.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port().to_string();
+        tokio::spawn(async move {
+            let mut streams = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                streams.push(stream);
+            }
+        });
+
+        async_with_vars(
+            [
+                ("RCON_HOST", Some("127.0.0.1")),
+                ("RCON_PORT", Some(port.as_str())),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_AUTH_TIMEOUT", Some("100")),
+                ("RCON_READ_TIMEOUT", Some("1000")),
+            ],
+            async {
+                let request = RconRequest::new(RconRequestType::ExecCommand, String::from("help"));
+                let response_result = RconClient::default().execute(&request).await;
+
+                assert!(matches!(
+                    response_result,
+                    Err(RconError::AuthTimeout { .. })
+                ));
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_execute_deadline_exceeded() {
+        async_with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_COMMAND_TIMEOUT", Some("100")),
+                ("RCON_RETRY_MAX_ATTEMPTS", Some("5")),
+                ("RCON_RETRY_BASE_DELAY", Some("200")),
+                ("RCON_RETRY_JITTER", Some("false")),
+            ],
+            async {
+                let started_at = Instant::now();
+
+                let request = RconRequest::new(RconRequestType::ExecCommand, String::from("help"));
+                let response_result = RconClient::default().execute(&request).await;
+
+                assert!(matches!(
+                    response_result,
+                    Err(RconError::DeadlineExceeded { .. })
+                ));
+                assert!(started_at.elapsed() < Duration::from_millis(200));
+            },
+        )
+        .await;
+    }
+
+    /// Reads a request from the stream, returning its ID.
+    async fn read_request_id(stream: &mut TcpStream) -> i32 {
+        let size = stream.read_i32_le().await.unwrap();
+        let mut body = vec![0u8; size as usize];
+        stream.read_exact(&mut body).await.unwrap();
+
+        i32::from_le_bytes(body[0..4].try_into().unwrap())
+    }
+
+    /// Writes a packet to the stream.
+    async fn write_packet(stream: &mut TcpStream, id: i32, code: i32, payload: &[u8]) {
+        let size = (4 + 4 + payload.len() + 2) as i32;
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&size.to_le_bytes());
+        bytes.extend_from_slice(&id.to_le_bytes());
+        bytes.extend_from_slice(&code.to_le_bytes());
+        bytes.extend_from_slice(payload);
+        bytes.extend_from_slice(&[0, 0]);
+        stream.write_all(&bytes).await.unwrap();
+    }
+
+    /// Writes a response value packet to the stream.
+    async fn write_response(stream: &mut TcpStream, id: i32, payload: &[u8]) {
+        write_packet(stream, id, 0, payload).await;
+    }
+
+    /// Packet sent by the scripted server.
+    enum ScriptedPacket {
+        /// A fragment of the response to the command.
+        Response(&'static [u8]),
+        /// A packet left over by a previous command.
+        Stale,
+        /// The answer to the sentinel request.
+        Sentinel,
+    }
+
+    /// Starts a server answering a single command with the scripted packets.
+    async fn spawn_scripted_server(packets: Vec<ScriptedPacket>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port().to_string();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let id = read_request_id(&mut stream).await;
+            let sentinel_id = read_request_id(&mut stream).await;
+            for packet in packets {
+                match packet {
+                    ScriptedPacket::Response(payload) => {
+                        write_response(&mut stream, id, payload).await
+                    }
+                    ScriptedPacket::Stale => {
+                        write_response(&mut stream, id.wrapping_add(1), b"stale").await
+                    }
+                    ScriptedPacket::Sentinel => {
+                        write_response(&mut stream, sentinel_id, b"Unknown request c8").await
+                    }
+                }
+            }
+            stream.read_u8().await.ok();
+        });
+
+        port
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_request_discards_stale_packets() {
+        let port = spawn_scripted_server(vec![
+            ScriptedPacket::Stale,
+            ScriptedPacket::Stale,
+            ScriptedPacket::Response(b"response"),
+            ScriptedPacket::Sentinel,
+        ])
+        .await;
+
+        async_with_vars(
+            [
+                ("RCON_HOST", Some("127.0.0.1")),
+                ("RCON_PORT", Some(port.as_str())),
+                ("RCON_PASSWORD", Some("password")),
+            ],
+            async {
+                let mut connection = RconClient::default().get_connection().await.unwrap();
+
+                let request = RconRequest::new(RconRequestType::ExecCommand, String::from("list"));
+                let response = connection.request(&request).await.unwrap();
+
+                assert_eq!(response.response_id, request.request_id);
+                assert_eq!(response.response_payload, "response");
+
+                connection.disconnect().await.ok();
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_request_desync() {
+        let port = spawn_scripted_server((0..20).map(|_| ScriptedPacket::Stale).collect()).await;
+
+        async_with_vars(
+            [
+                ("RCON_HOST", Some("127.0.0.1")),
+                ("RCON_PORT", Some(port.as_str())),
+                ("RCON_PASSWORD", Some("password")),
+            ],
+            async {
+                let mut connection = RconClient::default().get_connection().await.unwrap();
+
+                let request = RconRequest::new(RconRequestType::ExecCommand, String::from("list"));
+                let response_result = connection.request(&request).await;
+
+                assert_eq!(
+                    response_result.unwrap_err(),
+                    RconError::Desync {
+                        expected: request.request_id,
+                        received: request.request_id.wrapping_add(1),
+                    }
+                );
+
+                connection.disconnect().await.ok();
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_request_fragments() {
+        let port = spawn_scripted_server(vec![
+            ScriptedPacket::Response(b"Caf\xc3"),
+            ScriptedPacket::Response(b"\xa9 au lait"),
+            ScriptedPacket::Sentinel,
+        ])
+        .await;
+
+        async_with_vars(
+            [
+                ("RCON_HOST", Some("127.0.0.1")),
+                ("RCON_PORT", Some(port.as_str())),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_PAYLOAD_ENCODING", None),
+            ],
+            async {
+                let mut connection = RconClient::default().get_connection().await.unwrap();
+
+                let request = RconRequest::new(RconRequestType::ExecCommand, String::from("list"));
+                let response = connection.request(&request).await.unwrap();
+
+                assert_eq!(response.response_payload, "Café au lait");
+
+                connection.disconnect().await.ok();
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_request_latin1_payload() {
+        let port = spawn_scripted_server(vec![
+            ScriptedPacket::Response(b"Caf\xe9"),
+            ScriptedPacket::Sentinel,
+        ])
+        .await;
+
+        async_with_vars(
+            [
+                ("RCON_HOST", Some("127.0.0.1")),
+                ("RCON_PORT", Some(port.as_str())),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_PAYLOAD_ENCODING", Some("latin1")),
+            ],
+            async {
+                let mut connection = RconClient::default().get_connection().await.unwrap();
+
+                let request = RconRequest::new(RconRequestType::ExecCommand, String::from("list"));
+                let response = connection.request(&request).await.unwrap();
+
+                assert_eq!(response.response_payload, "Café");
+                assert_eq!(response.response_bytes, b"Caf\xe9");
+
+                connection.disconnect().await.ok();
+            },
+        )
+        .await;
+    }
+
+    /// Reads a request from the stream, returning its ID, type and payload.
+    async fn read_request(stream: &mut TcpStream) -> (i32, i32, Vec<u8>) {
+        let size = stream.read_i32_le().await.unwrap();
+        let mut body = vec![0u8; size as usize];
+        stream.read_exact(&mut body).await.unwrap();
+
+        let id = i32::from_le_bytes(body[0..4].try_into().unwrap());
+        let code = i32::from_le_bytes(body[4..8].try_into().unwrap());
+        (id, code, body[8..body.len() - 2].to_vec())
+    }
+
+    #[test_case(true => true; "accepted")]
+    #[test_case(false => false; "refused")]
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_login_source_dialect(accepted: bool) -> bool {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port().to_string();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let (id, code, _) = read_request(&mut stream).await;
+            assert_eq!(code, 3);
+
+            // An empty response value always comes before the authentication response.
+            write_response(&mut stream, id, b"").await;
+            write_packet(&mut stream, if accepted { id } else { -1 }, 2, b"").await;
+            stream.read_u8().await.ok();
+        });
+
+        async_with_vars(
+            [
+                ("RCON_HOST", Some("127.0.0.1")),
+                ("RCON_PORT", Some(port.as_str())),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_DIALECT", Some("source")),
+            ],
+            async {
+                let mut connection = RconClient::default().get_connection().await.unwrap();
+                let login_result = connection.login().await.unwrap();
+                connection.disconnect().await.ok();
+
+                login_result
+            },
+        )
+        .await
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_request_source_dialect_mirror() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port().to_string();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let (id, _, _) = read_request(&mut stream).await;
+            let (mirror_id, mirror_code, mirror_payload) = read_request(&mut stream).await;
+            assert_eq!(mirror_code, 0);
+            assert!(mirror_payload.is_empty());
+
+            write_response(&mut stream, id, b"first ").await;
+            write_response(&mut stream, id, b"second").await;
+            write_response(&mut stream, mirror_id, b"").await;
+            write_response(&mut stream, mirror_id, &[0x00, 0x01]).await;
+            stream.read_u8().await.ok();
+        });
+
+        async_with_vars(
+            [
+                ("RCON_HOST", Some("127.0.0.1")),
+                ("RCON_PORT", Some(port.as_str())),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_DIALECT", Some("source")),
+            ],
+            async {
+                let mut connection = RconClient::default().get_connection().await.unwrap();
+
+                let request =
+                    RconRequest::new(RconRequestType::ExecCommand, String::from("status"));
+                let response = connection.request(&request).await.unwrap();
+
+                assert_eq!(response.response_payload, "first second");
+
+                connection.disconnect().await.ok();
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_request_single_packet_dialect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port().to_string();
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let (id, _, _) = read_request(&mut stream).await;
+
+            // Longer than a Minecraft packet, and answered without waiting for a sentinel.
+            write_response(&mut stream, id, &[b'a'; 6000]).await;
+
+            let mut next = Vec::new();
+            stream.read_to_end(&mut next).await.ok();
+            sender.send(next).ok();
+        });
+
+        async_with_vars(
+            [
+                ("RCON_HOST", Some("127.0.0.1")),
+                ("RCON_PORT", Some(port.as_str())),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_DIALECT", Some("palworld")),
+            ],
+            async {
+                let mut connection = RconClient::default().get_connection().await.unwrap();
+
+                let request =
+                    RconRequest::new(RconRequestType::ExecCommand, String::from("ShowPlayers"));
+                let response = connection.request(&request).await.unwrap();
+
+                assert_eq!(response.response_bytes.len(), 6000);
+
+                connection.disconnect().await.ok();
+            },
+        )
+        .await;
+
+        // Nothing is sent after the command.
+        assert!(receiver.await.unwrap().is_empty());
+    }
+
+    /// Writer keeping the logs in memory.
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl CapturedLogs {
+        fn contents(&self) -> String {
+            String::from_utf8_lossy(&self.0.lock().unwrap()).into_owned()
+        }
+    }
+
+    impl io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for CapturedLogs {
+        type Writer = CapturedLogs;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_execute_password_not_logged() {
+        const PASSWORD: &str = "s3cr3t-rcon-password";
+
+        // A server accepting the login, then answering the command.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port().to_string();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let auth_id = read_request_id(&mut stream).await;
+            write_packet(&mut stream, auth_id, 2, b"").await;
+            let id = read_request_id(&mut stream).await;
+            let sentinel_id = read_request_id(&mut stream).await;
+            write_response(&mut stream, id, b"There are 0 players online").await;
+            write_response(&mut stream, sentinel_id, b"Unknown request c8").await;
+            stream.read_u8().await.ok();
+        });
+
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(Level::TRACE)
+            .with_span_events(FmtSpan::FULL)
+            .with_ansi(false)
+            .with_writer(logs.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        async_with_vars(
+            [
+                ("RCON_HOST", Some("127.0.0.1")),
+                ("RCON_PORT", Some(port.as_str())),
+                ("RCON_PASSWORD", Some(PASSWORD)),
+            ],
+            async {
+                let request = RconRequest::new(RconRequestType::ExecCommand, String::from("list"));
+                let response = RconClient::default().execute(&request).await.unwrap();
+
+                assert_eq!(response.response_payload, "There are 0 players online");
+            },
+        )
+        .await;
+
+        let logs = logs.contents();
+        assert!(logs.contains("Rcon.Login"));
+        assert!(logs.contains("[REDACTED]"));
+        assert!(!logs.contains(PASSWORD));
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_execute_span_attributes() {
+        // A server accepting the login, then answering the command in two packets.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port().to_string();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let auth_id = read_request_id(&mut stream).await;
+            write_packet(&mut stream, auth_id, 2, b"").await;
+            let id = read_request_id(&mut stream).await;
+            let sentinel_id = read_request_id(&mut stream).await;
+            write_response(&mut stream, id, b"Hello ").await;
+            write_response(&mut stream, id, b"Steve").await;
+            write_response(&mut stream, sentinel_id, b"Unknown request c8").await;
+            stream.read_u8().await.ok();
+        });
+
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(Level::INFO)
+            .with_span_events(FmtSpan::CLOSE)
+            .with_ansi(false)
+            .with_writer(logs.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        async_with_vars(
+            [
+                ("RCON_HOST", Some("127.0.0.1")),
+                ("RCON_PORT", Some(port.as_str())),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_REDACT_COMMAND_ARGUMENTS", None),
+            ],
+            async {
+                let request = RconRequest::new(
+                    RconRequestType::ExecCommand,
+                    String::from("/tell Steve the-secret-base-is-at-100-64-200"),
+                );
+                RconClient::default().execute(&request).await.unwrap();
+            },
+        )
+        .await;
+
+        let logs = logs.contents();
+        assert!(logs.contains("server.address=\"127.0.0.1:"));
+        assert!(logs.contains("rcon.command.verb=\"tell\""));
+        assert!(logs.contains("rcon.command=\"/tell [REDACTED]\""));
+        assert!(logs.contains("rcon.request.type=\"exec_command\""));
+        assert!(logs.contains("rcon.response.fragments=2"));
+        assert!(logs.contains("rcon.response.type=\"response_value\""));
+        assert!(logs.contains("rcon.login.result=\"success\""));
+        assert!(logs.contains("RCON packet sent"));
+        assert!(logs.contains("RCON packet received"));
+        assert!(!logs.contains("the-secret-base"));
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_execute_span_error_type() {
+        let logs = CapturedLogs::default();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(Level::INFO)
+            .with_span_events(FmtSpan::CLOSE)
+            .with_ansi(false)
+            .with_writer(logs.clone())
+            .finish();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let result = async_with_vars(
+            [
+                ("RCON_HOST", Some("127.0.0.1")),
+                // Nothing listens on the discard port.
+                ("RCON_PORT", Some("9")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_RETRY_MAX_ATTEMPTS", Some("1")),
+            ],
+            async {
+                let request = RconRequest::new(RconRequestType::ExecCommand, String::from("list"));
+                RconClient::default().execute(&request).await
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+        let logs = logs.contents();
+        assert!(logs.contains("error.type=\"connection\""));
+        assert!(logs.contains("otel.status_code=\"ERROR\""));
+    }
 }
@@ -1,4 +1,4 @@
-use super::RconError;
+use super::{RconDialect, RconError, RconPayloadEncoding, RconTransportType, Secret};
 use std::env;
 use tracing::debug;
 
@@ -6,12 +6,66 @@ use tracing::debug;
 #[allow(unused)]
 pub const DEFAULT_RCON_TIMEOUT: u64 = 5000;
 
+/// Default maximum size of the payload of a request, in bytes.
+///
+/// # Note:
+///
+/// The maximum length of the packet is 1460 bytes.
+/// The message lenght is 4 bytes.
+/// The request ID is 4 bytes.
+/// The request type is 4 bytes.
+/// The null terminator for the payload is 1 byte.
+/// The null terminator for the packet is 1 byte.
+///
+/// Total: 1460 - 4 - 4 - 4 - 1 - 1 = 1446
+///
+/// - [Fragmentation](https://minecraft.wiki/w/RCON#Fragmentation)
+/// - [Packet format](https://minecraft.wiki/w/RCON#Packet_format)
+pub const DEFAULT_RCON_MAX_PAYLOAD_SIZE: usize = 1446;
+
+/// Default time the output of the server process must stay silent for its response to be
+/// complete, in milliseconds.
+pub const DEFAULT_RCON_PROCESS_RESPONSE_IDLE: u64 = 500;
+
+/// Default overall deadline of a command, as a multiple of the base timeout.
+pub const DEFAULT_RCON_COMMAND_TIMEOUT_FACTOR: u64 = 3;
+
 #[derive(Debug, Clone)]
 pub struct RconConfiguration {
+    /// Way the requests reach the server.
+    pub transport: RconTransportType,
     pub host: String,
     pub port: u16,
-    pub password: String,
-    pub timeout: u64,
+    pub password: Secret,
+    /// Timeout to establish the TCP connection, in milliseconds.
+    pub connect_timeout: u64,
+    /// Timeout of the login exchange, in milliseconds.
+    pub auth_timeout: u64,
+    /// Timeout to write a request, in milliseconds.
+    pub write_timeout: u64,
+    /// Timeout waiting for a response, in milliseconds.
+    pub read_timeout: u64,
+    /// Overall deadline of a command, including the connection, the login and the retries, in
+    /// milliseconds.
+    pub command_timeout: u64,
+    /// Flavor of the RCON protocol spoken by the server.
+    pub dialect: RconDialect,
+    /// Encoding of the payloads of the responses.
+    pub encoding: RconPayloadEncoding,
+    /// Maximum size of the payload of a request, in bytes.
+    pub max_payload_size: usize,
+    /// Whether the commands too long for a single request are split in several ones, when safe.
+    pub split_long_commands: bool,
+    /// Whether the arguments of the commands are redacted from the traces, only their verb being
+    /// recorded.
+    pub redact_command_arguments: bool,
+    /// Command line of the server process, when the commands are written to its console.
+    pub process_command: Option<String>,
+    /// Working directory of the server process.
+    pub process_working_dir: Option<String>,
+    /// Time the output of the server process must stay silent for its response to be complete,
+    /// in milliseconds.
+    pub process_response_idle: u64,
 }
 
 impl RconConfiguration {
@@ -23,6 +77,128 @@ impl RconConfiguration {
     pub fn try_new() -> Result<RconConfiguration, RconError> {
         debug!("Attempting to create RCON configuration from environment variables...");
 
+        let transport = env::var("RCON_TRANSPORT")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| RconTransportType::try_from(value.as_str()))
+            .unwrap_or(Ok(RconTransportType::default()))
+            .map_err(|_| RconError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RCON_TRANSPORT' is not a valid transport",
+                ),
+            })?;
+        let process_command = ["RCON_PROCESS_COMMAND", "SERVER_COMMAND"]
+            .into_iter()
+            .filter_map(|variable| env::var(variable).ok())
+            .find(|value| !value.trim().is_empty());
+        let process_working_dir = env::var("RCON_PROCESS_WORKING_DIR")
+            .ok()
+            .filter(|value| !value.is_empty());
+        let process_response_idle = Self::parse_timeout(
+            "RCON_PROCESS_RESPONSE_IDLE",
+            DEFAULT_RCON_PROCESS_RESPONSE_IDLE,
+        )?;
+
+        // The console of the server process needs neither an address nor a password.
+        let (host, port, password) = match transport {
+            RconTransportType::Tcp => Self::parse_server()?,
+            RconTransportType::Process => {
+                if process_command.is_none() {
+                    return Err(RconError::Configuration {
+                        cause: String::from(
+                            "Environment variable 'RCON_PROCESS_COMMAND' is not set",
+                        ),
+                    });
+                }
+
+                (String::new(), 0, Secret::from(String::new()))
+            }
+        };
+        // The base timeout is only the default of the other ones.
+        let timeout = Self::parse_timeout("RCON_TIMEOUT", DEFAULT_RCON_TIMEOUT)?;
+        let connect_timeout = Self::parse_timeout("RCON_CONNECT_TIMEOUT", timeout)?;
+        let auth_timeout = Self::parse_timeout("RCON_AUTH_TIMEOUT", timeout)?;
+        let write_timeout = Self::parse_timeout("RCON_WRITE_TIMEOUT", timeout)?;
+        let read_timeout = Self::parse_timeout("RCON_READ_TIMEOUT", timeout)?;
+        let command_timeout = Self::parse_timeout(
+            "RCON_COMMAND_TIMEOUT",
+            timeout.saturating_mul(DEFAULT_RCON_COMMAND_TIMEOUT_FACTOR),
+        )?;
+        let dialect = env::var("RCON_DIALECT")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| RconDialect::try_from(value.as_str()))
+            .unwrap_or(Ok(RconDialect::default()))
+            .map_err(|_| RconError::Configuration {
+                cause: String::from("Environment variable 'RCON_DIALECT' is not a valid dialect"),
+            })?;
+        let encoding = env::var("RCON_PAYLOAD_ENCODING")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| RconPayloadEncoding::try_from(value.as_str()))
+            .unwrap_or(Ok(dialect.default_encoding()))
+            .map_err(|_| RconError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RCON_PAYLOAD_ENCODING' is not a valid encoding",
+                ),
+            })?;
+
+        let max_payload_size = env::var("RCON_MAX_PAYLOAD_SIZE")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<usize>())
+            .unwrap_or(Ok(dialect.max_payload_size()))
+            .ok()
+            .filter(|max_payload_size| *max_payload_size > 0)
+            .ok_or_else(|| RconError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RCON_MAX_PAYLOAD_SIZE' is not a valid positive number",
+                ),
+            })?;
+        let split_long_commands = env::var("RCON_SPLIT_LONG_COMMANDS")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<bool>())
+            .unwrap_or(Ok(false))
+            .map_err(|_| RconError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RCON_SPLIT_LONG_COMMANDS' is not a boolean",
+                ),
+            })?;
+        let redact_command_arguments = env::var("RCON_REDACT_COMMAND_ARGUMENTS")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<bool>())
+            .unwrap_or(Ok(true))
+            .map_err(|_| RconError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RCON_REDACT_COMMAND_ARGUMENTS' is not a boolean",
+                ),
+            })?;
+
+        Ok(RconConfiguration {
+            transport,
+            host,
+            port,
+            password,
+            connect_timeout,
+            auth_timeout,
+            write_timeout,
+            read_timeout,
+            command_timeout,
+            dialect,
+            encoding,
+            max_payload_size,
+            split_long_commands,
+            redact_command_arguments,
+            process_command,
+            process_working_dir,
+            process_response_idle,
+        })
+    }
+
+    /// Parses the address and the password of the RCON server from the environment variables.
+    fn parse_server() -> Result<(String, u16, Secret), RconError> {
         let host = env::var("RCON_HOST").map_err(|_| RconError::Configuration {
             cause: String::from("Environment variable 'RCON_HOST' is not set"),
         })?;
@@ -34,27 +210,39 @@ impl RconConfiguration {
             .map_err(|_| RconError::Configuration {
                 cause: String::from("Environment variable 'RCON_PORT' is not a valid number"),
             })?;
-        let password = env::var("RCON_PASSWORD").map_err(|_| RconError::Configuration {
-            cause: String::from("Environment variable 'RCON_PASSWORD' is not set"),
-        })?;
-        let timeout = env::var("RCON_TIMEOUT")
+        let password =
+            env::var("RCON_PASSWORD")
+                .map(Secret::from)
+                .map_err(|_| RconError::Configuration {
+                    cause: String::from("Environment variable 'RCON_PASSWORD' is not set"),
+                })?;
+
+        Ok((host, port, password))
+    }
+
+    /// Parses a timeout in milliseconds from an environment variable, falling back to a default
+    /// value if the variable is not set or empty.
+    fn parse_timeout(variable: &str, default: u64) -> Result<u64, RconError> {
+        env::var(variable)
             .map(|value| {
                 if value.is_empty() {
-                    Ok(DEFAULT_RCON_TIMEOUT)
+                    Ok(default)
                 } else {
                     value.parse::<u64>()
                 }
             })
-            .unwrap_or_else(|_| Ok(DEFAULT_RCON_TIMEOUT))
+            .unwrap_or_else(|_| Ok(default))
             .map_err(|_| RconError::Configuration {
-                cause: String::from("Environment variable 'RCON_TIMEOUT' is not a valid number"),
-            })?;
+                cause: format!("Environment variable '{variable}' is not a valid number"),
+            })
+    }
 
-        Ok(RconConfiguration {
-            host,
-            port,
-            password,
-            timeout,
-        })
+    /// Returns the address of the RCON server, in the `<host>:<port>` format, or `process` when
+    /// the commands are written to the console of the server process.
+    pub fn address(&self) -> String {
+        match self.transport {
+            RconTransportType::Tcp => format!("{}:{}", self.host, self.port),
+            RconTransportType::Process => String::from(RconTransportType::Process.as_str()),
+        }
     }
 }
@@ -1,8 +1,13 @@
 #[cfg(test)]
 mod tests {
-    use crate::rcon::{DEFAULT_RCON_TIMEOUT, RconConfiguration, RconError};
+    use crate::rcon::{
+        DEFAULT_RCON_MAX_PAYLOAD_SIZE, DEFAULT_RCON_PROCESS_RESPONSE_IDLE, DEFAULT_RCON_TIMEOUT,
+        RconConfiguration, RconDialect, RconError, RconPayloadEncoding, RconTransportType,
+        SOURCE_RCON_MAX_PAYLOAD_SIZE,
+    };
     use serial_test::serial;
     use temp_env::with_vars;
+    use test_case::test_case;
 
     #[test]
     #[serial(rcon)]
@@ -22,7 +27,7 @@ mod tests {
                 assert_eq!(configuration.host, "localhost");
                 assert_eq!(configuration.port, 25575);
                 assert_eq!(configuration.password, "password");
-                assert_eq!(configuration.timeout, DEFAULT_RCON_TIMEOUT);
+                assert_eq!(configuration.read_timeout, DEFAULT_RCON_TIMEOUT);
             },
         );
     }
@@ -144,7 +149,7 @@ mod tests {
                 assert!(result.is_ok());
 
                 let configuration = result.unwrap();
-                assert_eq!(configuration.timeout, DEFAULT_RCON_TIMEOUT);
+                assert_eq!(configuration.read_timeout, DEFAULT_RCON_TIMEOUT);
             },
         );
     }
@@ -164,7 +169,7 @@ mod tests {
                 assert!(result.is_ok());
 
                 let configuration = result.unwrap();
-                assert_eq!(configuration.timeout, 10000);
+                assert_eq!(configuration.read_timeout, 10000);
             },
         );
     }
@@ -211,8 +216,405 @@ mod tests {
                 assert!(result.is_ok());
 
                 let configuration = result.unwrap();
-                assert_eq!(configuration.timeout, DEFAULT_RCON_TIMEOUT);
+                assert_eq!(configuration.read_timeout, DEFAULT_RCON_TIMEOUT);
             },
         );
     }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_default_phase_timeouts() {
+        with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_TIMEOUT", Some("1000")),
+                ("RCON_CONNECT_TIMEOUT", None),
+                ("RCON_AUTH_TIMEOUT", None),
+                ("RCON_WRITE_TIMEOUT", None),
+                ("RCON_READ_TIMEOUT", Some("")),
+                ("RCON_COMMAND_TIMEOUT", None),
+            ],
+            || {
+                let result = RconConfiguration::try_new();
+                assert!(result.is_ok());
+
+                let configuration = result.unwrap();
+                assert_eq!(configuration.connect_timeout, 1000);
+                assert_eq!(configuration.auth_timeout, 1000);
+                assert_eq!(configuration.write_timeout, 1000);
+                assert_eq!(configuration.read_timeout, 1000);
+                assert_eq!(configuration.command_timeout, 3000);
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_custom_phase_timeouts() {
+        with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_TIMEOUT", None),
+                ("RCON_CONNECT_TIMEOUT", Some("100")),
+                ("RCON_AUTH_TIMEOUT", Some("200")),
+                ("RCON_WRITE_TIMEOUT", Some("300")),
+                ("RCON_READ_TIMEOUT", Some("400")),
+                ("RCON_COMMAND_TIMEOUT", Some("500")),
+            ],
+            || {
+                let result = RconConfiguration::try_new();
+                assert!(result.is_ok());
+
+                let configuration = result.unwrap();
+                assert_eq!(configuration.connect_timeout, 100);
+                assert_eq!(configuration.auth_timeout, 200);
+                assert_eq!(configuration.write_timeout, 300);
+                assert_eq!(configuration.read_timeout, 400);
+                assert_eq!(configuration.command_timeout, 500);
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_invalid_connect_timeout() {
+        with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_CONNECT_TIMEOUT", Some("invalid")),
+            ],
+            || {
+                let result = RconConfiguration::try_new();
+                assert!(result.is_err());
+
+                let error = result.unwrap_err();
+                assert_eq!(
+                    error,
+                    RconError::Configuration {
+                        cause: String::from(
+                            "Environment variable 'RCON_CONNECT_TIMEOUT' is not a valid number"
+                        )
+                    }
+                );
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_payload_encoding() {
+        with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_PAYLOAD_ENCODING", Some("windows-1252")),
+            ],
+            || {
+                let configuration = RconConfiguration::try_new().unwrap();
+                assert_eq!(configuration.encoding, RconPayloadEncoding::Windows1252);
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_default_dialect() {
+        with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_DIALECT", None),
+                ("RCON_PAYLOAD_ENCODING", None),
+                ("RCON_MAX_PAYLOAD_SIZE", None),
+            ],
+            || {
+                let configuration = RconConfiguration::try_new().unwrap();
+                assert_eq!(configuration.dialect, RconDialect::Minecraft);
+                assert_eq!(configuration.encoding, RconPayloadEncoding::Utf8);
+                assert_eq!(
+                    configuration.max_payload_size,
+                    DEFAULT_RCON_MAX_PAYLOAD_SIZE
+                );
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_dialect_defaults() {
+        with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_DIALECT", Some("palworld")),
+                ("RCON_PAYLOAD_ENCODING", None),
+                ("RCON_MAX_PAYLOAD_SIZE", None),
+            ],
+            || {
+                let configuration = RconConfiguration::try_new().unwrap();
+                assert_eq!(configuration.dialect, RconDialect::Palworld);
+                assert_eq!(configuration.encoding, RconPayloadEncoding::Utf8Lossy);
+                assert_eq!(configuration.max_payload_size, SOURCE_RCON_MAX_PAYLOAD_SIZE);
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_dialect_overridden() {
+        with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_DIALECT", Some("source")),
+                ("RCON_PAYLOAD_ENCODING", Some("latin1")),
+                ("RCON_MAX_PAYLOAD_SIZE", Some("1000")),
+            ],
+            || {
+                let configuration = RconConfiguration::try_new().unwrap();
+                assert_eq!(configuration.dialect, RconDialect::Source);
+                assert_eq!(configuration.encoding, RconPayloadEncoding::Latin1);
+                assert_eq!(configuration.max_payload_size, 1000);
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_invalid_dialect() {
+        with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_DIALECT", Some("bedrock")),
+            ],
+            || {
+                assert_eq!(
+                    RconConfiguration::try_new().unwrap_err(),
+                    RconError::Configuration {
+                        cause: String::from(
+                            "Environment variable 'RCON_DIALECT' is not a valid dialect"
+                        )
+                    }
+                );
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_default_transport() {
+        with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_TRANSPORT", None),
+            ],
+            || {
+                let configuration = RconConfiguration::try_new().unwrap();
+                assert_eq!(configuration.transport, RconTransportType::Tcp);
+                assert_eq!(configuration.address(), "localhost:25575");
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_process_transport() {
+        with_vars(
+            [
+                ("RCON_HOST", None),
+                ("RCON_PORT", None),
+                ("RCON_PASSWORD", None),
+                ("RCON_TRANSPORT", Some("process")),
+                ("RCON_PROCESS_COMMAND", Some("java -jar server.jar nogui")),
+                ("RCON_PROCESS_WORKING_DIR", Some("/srv/minecraft")),
+                ("RCON_PROCESS_RESPONSE_IDLE", None),
+            ],
+            || {
+                let configuration = RconConfiguration::try_new().unwrap();
+                assert_eq!(configuration.transport, RconTransportType::Process);
+                assert_eq!(
+                    configuration.process_command.as_deref(),
+                    Some("java -jar server.jar nogui")
+                );
+                assert_eq!(
+                    configuration.process_working_dir.as_deref(),
+                    Some("/srv/minecraft")
+                );
+                assert_eq!(
+                    configuration.process_response_idle,
+                    DEFAULT_RCON_PROCESS_RESPONSE_IDLE
+                );
+                assert_eq!(configuration.address(), "process");
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_process_transport_missing_command() {
+        with_vars(
+            [
+                ("RCON_TRANSPORT", Some("process")),
+                ("RCON_PROCESS_COMMAND", Some(" ")),
+                ("SERVER_COMMAND", None),
+            ],
+            || {
+                assert_eq!(
+                    RconConfiguration::try_new().unwrap_err(),
+                    RconError::Configuration {
+                        cause: String::from(
+                            "Environment variable 'RCON_PROCESS_COMMAND' is not set"
+                        )
+                    }
+                );
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_invalid_transport() {
+        with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_TRANSPORT", Some("udp")),
+            ],
+            || {
+                assert_eq!(
+                    RconConfiguration::try_new().unwrap_err(),
+                    RconError::Configuration {
+                        cause: String::from(
+                            "Environment variable 'RCON_TRANSPORT' is not a valid transport"
+                        )
+                    }
+                );
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_invalid_payload_encoding() {
+        with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_PAYLOAD_ENCODING", Some("ebcdic")),
+            ],
+            || {
+                let error = RconConfiguration::try_new().unwrap_err();
+                assert_eq!(
+                    error,
+                    RconError::Configuration {
+                        cause: String::from(
+                            "Environment variable 'RCON_PAYLOAD_ENCODING' is not a valid encoding"
+                        )
+                    }
+                );
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_request_size() {
+        with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_MAX_PAYLOAD_SIZE", None),
+                ("RCON_SPLIT_LONG_COMMANDS", None),
+            ],
+            || {
+                let configuration = RconConfiguration::try_new().unwrap();
+                assert_eq!(
+                    configuration.max_payload_size,
+                    DEFAULT_RCON_MAX_PAYLOAD_SIZE
+                );
+                assert!(!configuration.split_long_commands);
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_custom_request_size() {
+        with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_MAX_PAYLOAD_SIZE", Some("4000")),
+                ("RCON_SPLIT_LONG_COMMANDS", Some("true")),
+            ],
+            || {
+                let configuration = RconConfiguration::try_new().unwrap();
+                assert_eq!(configuration.max_payload_size, 4000);
+                assert!(configuration.split_long_commands);
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_invalid_max_payload_size() {
+        with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_MAX_PAYLOAD_SIZE", Some("0")),
+            ],
+            || {
+                let error = RconConfiguration::try_new().unwrap_err();
+                assert_eq!(
+                    error,
+                    RconError::Configuration {
+                        cause: String::from(
+                            "Environment variable 'RCON_MAX_PAYLOAD_SIZE' is not a valid positive number"
+                        )
+                    }
+                );
+            },
+        );
+    }
+
+    #[test_case(None => Ok(true); "default")]
+    #[test_case(Some("false") => Ok(false); "disabled")]
+    #[test_case(Some("no") => matches Err(_); "invalid")]
+    #[serial(rcon)]
+    fn test_get_configuration_redact_command_arguments(
+        value: Option<&str>,
+    ) -> Result<bool, RconError> {
+        with_vars(
+            [
+                ("RCON_HOST", Some("localhost")),
+                ("RCON_PORT", Some("25575")),
+                ("RCON_PASSWORD", Some("password")),
+                ("RCON_REDACT_COMMAND_ARGUMENTS", value),
+            ],
+            || {
+                RconConfiguration::try_new()
+                    .map(|configuration| configuration.redact_command_arguments)
+            },
+        )
+    }
 }
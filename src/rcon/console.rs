@@ -0,0 +1,401 @@
+use super::{
+    RconConfiguration, RconError, RconRequest, RconResponse, RconResponseType, RconTransport,
+};
+use std::{
+    process::Stdio,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    process::{Child, ChildStdin, ChildStdout, Command},
+    sync::{Mutex, broadcast},
+    time::timeout,
+};
+use tracing::{Span, debug, info, trace, warn};
+
+/// Number of lines of the output kept for the readers lagging behind, before the oldest ones are
+/// dropped.
+pub const CONSOLE_OUTPUT_CAPACITY: usize = 1024;
+
+/// Console of the server process launched by the application, shared by all the connections.
+///
+/// The process is started on demand, and started again if it exited in the meantime, unless it
+/// is supervised: the supervisor then starts it and attaches its streams to the console.
+#[derive(Debug, Clone, Default)]
+pub struct RconConsole {
+    process: Arc<Mutex<Option<ConsoleProcess>>>,
+    supervised: Arc<AtomicBool>,
+}
+
+#[derive(Debug)]
+struct ConsoleProcess {
+    /// The process, missing when it is owned by the supervisor.
+    child: Option<Child>,
+    stdin: ChildStdin,
+    /// The lines printed by the process on its standard output, weak so the channel is closed
+    /// along with the output.
+    output: broadcast::WeakSender<Vec<u8>>,
+}
+
+impl RconConsole {
+    /// Starts the server process, unless it is already running.
+    ///
+    /// # Parameters
+    ///
+    /// - `configuration`: The configuration holding the command of the process.
+    pub async fn start(&self, configuration: &RconConfiguration) -> Result<(), RconError> {
+        let mut process = self.process.lock().await;
+        Self::ensure_running(&mut process, configuration, self.is_supervised())
+            .await
+            .map(|_| ())
+    }
+
+    /// Hands the process over to the supervisor, the console no longer starting it by itself.
+    pub fn set_supervised(&self) {
+        self.supervised.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether the process is started by the supervisor.
+    pub fn is_supervised(&self) -> bool {
+        self.supervised.load(Ordering::Relaxed)
+    }
+
+    /// Attaches the console to a process started by the supervisor.
+    ///
+    /// # Parameters
+    ///
+    /// - `stdin`: The standard input of the process.
+    /// - `output`: The lines printed by the process on its standard output.
+    pub async fn attach(&self, stdin: ChildStdin, output: broadcast::WeakSender<Vec<u8>>) {
+        *self.process.lock().await = Some(ConsoleProcess {
+            child: None,
+            stdin,
+            output,
+        });
+    }
+
+    /// Returns the running process, starting it if needed and not supervised.
+    ///
+    /// A process whose output is closed is considered as exited, as nothing could be read from
+    /// it anymore.
+    async fn ensure_running<'a>(
+        process: &'a mut Option<ConsoleProcess>,
+        configuration: &RconConfiguration,
+        supervised: bool,
+    ) -> Result<&'a mut ConsoleProcess, RconError> {
+        let running = match process.as_mut() {
+            None => false,
+            Some(current) => match current.child.as_mut().map(Child::try_wait) {
+                None => current.output.strong_count() > 0,
+                Some(Ok(None)) if current.output.strong_count() > 0 => true,
+                Some(Ok(None)) => {
+                    warn!("The output of the server process is closed, stopping it");
+                    if let Some(child) = current.child.as_mut() {
+                        child.kill().await.ok();
+                    }
+                    false
+                }
+                Some(Ok(Some(status))) => {
+                    warn!(
+                        "The server process exited with {}, starting it again",
+                        status
+                    );
+                    false
+                }
+                Some(Err(err)) => {
+                    warn!("Failed to get the state of the server process: {}", err);
+                    false
+                }
+            },
+        };
+
+        if !running && supervised {
+            return Err(RconError::Connection {
+                cause: String::from("The server process is not running"),
+            });
+        } else if !running {
+            *process = Some(Self::spawn(configuration)?);
+        }
+
+        process.as_mut().ok_or_else(|| RconError::Connection {
+            cause: String::from("The server process is not running"),
+        })
+    }
+
+    /// Spawns the server process, its output being forwarded to the subscribers line by line.
+    fn spawn(configuration: &RconConfiguration) -> Result<ConsoleProcess, RconError> {
+        let command_line = configuration.process_command.as_deref().unwrap_or_default();
+        let mut arguments = command_line.split_whitespace();
+        let program = arguments.next().ok_or_else(|| RconError::Configuration {
+            cause: String::from("Environment variable 'RCON_PROCESS_COMMAND' is not set"),
+        })?;
+
+        let mut command = Command::new(program);
+        command
+            .args(arguments)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(working_dir) = &configuration.process_working_dir {
+            command.current_dir(working_dir);
+        }
+
+        let mut child = command.spawn().map_err(|err| RconError::Connection {
+            cause: format!("Failed to start '{program}': {err}"),
+        })?;
+        let (stdin, stdout) =
+            child
+                .stdin
+                .take()
+                .zip(child.stdout.take())
+                .ok_or_else(|| RconError::Connection {
+                    cause: String::from(
+                        "The standard streams of the server process are unavailable",
+                    ),
+                })?;
+        info!(
+            "Started the server process '{}', PID {}",
+            program,
+            child.id().unwrap_or_default()
+        );
+
+        let (output, _) = broadcast::channel(CONSOLE_OUTPUT_CAPACITY);
+        let weak_output = output.downgrade();
+        tokio::spawn(Self::forward_output(stdout, output));
+
+        Ok(ConsoleProcess {
+            child: Some(child),
+            stdin,
+            output: weak_output,
+        })
+    }
+
+    /// Reads the output of the process line by line until it is closed.
+    async fn forward_output(stdout: ChildStdout, output: broadcast::Sender<Vec<u8>>) {
+        let mut reader = BufReader::new(stdout);
+        let mut line = Vec::new();
+
+        loop {
+            line.clear();
+            match reader.read_until(b'\n', &mut line).await {
+                Ok(0) => break,
+                Ok(_) => {
+                    while line
+                        .last()
+                        .is_some_and(|byte| *byte == b'\n' || *byte == b'\r')
+                    {
+                        line.pop();
+                    }
+                    trace!("Server output: {}", String::from_utf8_lossy(&line));
+                    // Nobody is waiting for a response when there is no subscriber.
+                    output.send(line.clone()).ok();
+                }
+                Err(err) => {
+                    warn!("Failed to read the output of the server process: {}", err);
+                    break;
+                }
+            }
+        }
+
+        debug!("The output of the server process is closed");
+    }
+}
+
+/// Returns the message of a line of the server output, without the time, thread and level prefix
+/// of the logs such as `[12:34:56] [Server thread/INFO]: `.
+///
+/// # Arguments
+///
+/// * `line` - The line printed by the server.
+///
+/// # Returns
+///
+/// The message of the line, or the whole line if it has no prefix.
+pub fn console_message(line: &[u8]) -> &[u8] {
+    if !line.starts_with(b"[") {
+        return line;
+    }
+
+    line.windows(3)
+        .position(|window| window == b"]: ")
+        .map(|position| &line[position + 3..])
+        .unwrap_or(line)
+}
+
+/// Connection to the console of the server process.
+///
+/// The console has no notion of request: the response to a command is made of the lines printed
+/// by the server until its output stays silent for `RCON_PROCESS_RESPONSE_IDLE` milliseconds. The
+/// commands are executed one at a time so their outputs are not mixed up, but the log lines
+/// printed meanwhile, such as a player joining, end up in the response.
+#[derive(Debug)]
+pub struct ProcessRconConnection {
+    console: RconConsole,
+    configuration: RconConfiguration,
+}
+
+impl ProcessRconConnection {
+    /// Creates a connection to the console of the server process.
+    ///
+    /// # Parameters
+    ///
+    /// - `console`: The console of the server process.
+    /// - `configuration`: The configuration of the connection.
+    pub fn new(console: RconConsole, configuration: RconConfiguration) -> Self {
+        ProcessRconConnection {
+            console,
+            configuration,
+        }
+    }
+
+    /// Collects the lines printed by the server until its output stays silent.
+    async fn collect_output(
+        &self,
+        output: &mut broadcast::Receiver<Vec<u8>>,
+    ) -> Result<Vec<u8>, RconError> {
+        let idle = Duration::from_millis(self.configuration.process_response_idle);
+        let deadline = Instant::now() + Duration::from_millis(self.configuration.read_timeout);
+        let mut received = Vec::new();
+
+        loop {
+            let wait = idle.min(deadline.saturating_duration_since(Instant::now()));
+            match timeout(wait, output.recv()).await {
+                Ok(Ok(line)) => received.push(line),
+                Ok(Err(broadcast::error::RecvError::Lagged(skipped))) => {
+                    warn!("{} lines of the server output were dropped", skipped)
+                }
+                Ok(Err(broadcast::error::RecvError::Closed)) if received.is_empty() => {
+                    return Err(RconError::Receive {
+                        cause: String::from("The server process exited"),
+                    });
+                }
+                Ok(Err(broadcast::error::RecvError::Closed)) | Err(_) => break,
+            }
+
+            if Instant::now() >= deadline {
+                warn!("The server output did not stay silent before the read timeout");
+                break;
+            }
+        }
+
+        Ok(received
+            .iter()
+            .map(|line| console_message(line))
+            .collect::<Vec<_>>()
+            .join(&b'\n'))
+    }
+}
+
+impl RconTransport for ProcessRconConnection {
+    /// Logs in to the console, which needs no password.
+    ///
+    /// # Returns
+    ///
+    /// Always `true`, once the server process is running.
+    #[tracing::instrument(
+        name = "Rcon.Login",
+        skip(self),
+        fields(
+            app.component = "rcon",
+            rcon.login.result = tracing::field::Empty,
+        )
+    )]
+    async fn login(&mut self) -> Result<bool, RconError> {
+        self.console.start(&self.configuration).await?;
+        Span::current().record("rcon.login.result", "success");
+
+        Ok(true)
+    }
+
+    /// Writes the command of a request to the console and reads its output.
+    ///
+    /// # Parameters
+    ///
+    /// - `request`: The request to send to the server.
+    ///
+    /// # Returns
+    ///
+    /// The response from the server.
+    #[tracing::instrument(
+        name = "Rcon.Request",
+        skip(self, request),
+        fields(
+            app.component = "rcon",
+            rcon.request.id = request.request_id,
+            rcon.request.type = request.request_type.as_str(),
+            rcon.request.size = request.request_payload.len(),
+            rcon.response.type = tracing::field::Empty,
+            rcon.response.size = tracing::field::Empty,
+        )
+    )]
+    async fn request(&mut self, request: &RconRequest) -> Result<RconResponse, RconError> {
+        if request.request_payload.contains(['\n', '\r']) {
+            return Err(RconError::InvalidCommand {
+                cause: String::from("The command spans several lines"),
+            });
+        }
+
+        let mut process = self.console.process.lock().await;
+        let process = RconConsole::ensure_running(
+            &mut process,
+            &self.configuration,
+            self.console.is_supervised(),
+        )
+        .await?;
+        // Subscribe before writing, so the first lines of the output are not missed.
+        let mut output = process
+            .output
+            .upgrade()
+            .map(|output| output.subscribe())
+            .ok_or_else(|| RconError::Receive {
+                cause: String::from("The server process exited"),
+            })?;
+
+        info!("Writing a command to the server console...");
+        let line = format!("{}\n", request.request_payload);
+        let started_at = Instant::now();
+        timeout(
+            Duration::from_millis(self.configuration.write_timeout),
+            async {
+                process.stdin.write_all(line.as_bytes()).await?;
+                process.stdin.flush().await
+            },
+        )
+        .await
+        .map_err(|_| RconError::WriteTimeout {
+            elapsed_ms: started_at.elapsed().as_millis() as u64,
+        })?
+        .map_err(|err| RconError::Send {
+            cause: err.to_string(),
+        })?;
+
+        let response_bytes = self.collect_output(&mut output).await?;
+        let response = RconResponse {
+            response_id: request.request_id,
+            response_type: RconResponseType::ResponseValue,
+            response_payload: self
+                .configuration
+                .encoding
+                .decode(&response_bytes)
+                .map_err(|err| RconError::Receive {
+                    cause: err.to_string(),
+                })?,
+            response_bytes,
+        };
+
+        let span = Span::current();
+        span.record("rcon.response.type", response.response_type.as_str());
+        span.record("rcon.response.size", response.response_bytes.len());
+
+        Ok(response)
+    }
+
+    /// Leaves the console, the server process keeps running.
+    async fn disconnect(&mut self) -> Result<(), RconError> {
+        Ok(())
+    }
+}
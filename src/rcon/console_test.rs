@@ -0,0 +1,197 @@
+#[cfg(test)]
+mod tests {
+    use crate::rcon::{
+        RconCircuitState, RconClient, RconError, RconRequest, RconRequestType, console_message,
+    };
+    use serial_test::serial;
+    use std::{
+        fs,
+        path::PathBuf,
+        time::{Duration, Instant},
+    };
+    use temp_env::async_with_vars;
+    use test_case::test_case;
+    use tokio::time::sleep;
+
+    /// Writes a stand-in of the server console, answering a few commands with log lines.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the script, unique to the test.
+    ///
+    /// # Returns
+    ///
+    /// The path of the script.
+    fn write_console_script(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{name}-{}.sh", std::process::id()));
+        fs::write(
+            &path,
+            r#"echo "[12:00:00] [Server thread/INFO]: Starting minecraft server"
+while read -r line; do
+    case "$line" in
+        list) echo "[12:00:01] [Server thread/INFO]: There are 0 of a max of 20 players online: " ;;
+        help)
+            echo "[12:00:01 INFO]: /help"
+            echo "[12:00:01 INFO]: /list"
+            ;;
+        stop) exit 0 ;;
+        *) echo "Unknown or incomplete command: $line" ;;
+    esac
+done
+"#,
+        )
+        .unwrap();
+
+        path
+    }
+
+    #[test_case(b"[12:34:56] [Server thread/INFO]: Done (3.2s)!" => b"Done (3.2s)!".to_vec(); "vanilla")]
+    #[test_case(b"[12:34:56 INFO]: Steve joined the game" => b"Steve joined the game".to_vec(); "paper")]
+    #[test_case(b"Unknown command" => b"Unknown command".to_vec(); "no prefix")]
+    #[test_case(b"[12:34:56] no message separator" => b"[12:34:56] no message separator".to_vec(); "no separator")]
+    #[test_case(b"" => Vec::<u8>::new(); "empty")]
+    fn test_console_message(line: &[u8]) -> Vec<u8> {
+        console_message(line).to_vec()
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_execute_process_transport() {
+        let script = write_console_script("rcon-console-execute");
+        let command = format!("sh {}", script.display());
+
+        async_with_vars(
+            [
+                ("RCON_TRANSPORT", Some("process")),
+                ("RCON_PROCESS_COMMAND", Some(command.as_str())),
+                ("RCON_PROCESS_RESPONSE_IDLE", Some("200")),
+                ("RCON_TIMEOUT", Some("2000")),
+            ],
+            async {
+                let client = RconClient::default();
+                client.start_console().await.unwrap();
+                sleep(Duration::from_millis(300)).await;
+
+                let request = RconRequest::new(RconRequestType::ExecCommand, "list".into());
+                let response = client.execute(&request).await.unwrap();
+                // The lines printed before the command are not part of its response.
+                assert_eq!(
+                    response.response_payload,
+                    "There are 0 of a max of 20 players online: "
+                );
+                assert_eq!(response.response_id, request.request_id);
+
+                let request = RconRequest::new(RconRequestType::ExecCommand, "help".into());
+                let response = client.execute(&request).await.unwrap();
+                assert_eq!(response.response_payload, "/help\n/list");
+
+                let request = RconRequest::new(RconRequestType::ExecCommand, "seed".into());
+                let response = client.execute(&request).await.unwrap();
+                assert_eq!(
+                    response.response_payload,
+                    "Unknown or incomplete command: seed"
+                );
+            },
+        )
+        .await;
+
+        fs::remove_file(script).ok();
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_execute_process_transport_restarted() {
+        let script = write_console_script("rcon-console-restart");
+        let command = format!("sh {}", script.display());
+
+        async_with_vars(
+            [
+                ("RCON_TRANSPORT", Some("process")),
+                ("RCON_PROCESS_COMMAND", Some(command.as_str())),
+                ("RCON_PROCESS_RESPONSE_IDLE", Some("200")),
+                ("RCON_TIMEOUT", Some("2000")),
+                ("RCON_RETRY_MAX_ATTEMPTS", Some("1")),
+            ],
+            async {
+                let client = RconClient::default();
+                client.start_console().await.unwrap();
+                sleep(Duration::from_millis(300)).await;
+
+                // The process exits without printing anything.
+                let request = RconRequest::new(RconRequestType::ExecCommand, "stop".into());
+                let result = client.execute(&request).await;
+                assert_eq!(
+                    result.unwrap_err(),
+                    RconError::Receive {
+                        cause: String::from("The server process exited")
+                    }
+                );
+
+                // The process is started again for the next command, its startup lines possibly
+                // being part of the response.
+                let request = RconRequest::new(RconRequestType::ExecCommand, "list".into());
+                let response = client.execute(&request).await.unwrap();
+                assert!(
+                    response
+                        .response_payload
+                        .ends_with("There are 0 of a max of 20 players online: ")
+                );
+            },
+        )
+        .await;
+
+        fs::remove_file(script).ok();
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_execute_process_transport_invalid_command() {
+        async_with_vars(
+            [
+                ("RCON_TRANSPORT", Some("process")),
+                (
+                    "RCON_PROCESS_COMMAND",
+                    Some("/nonexistent/minecraft-server"),
+                ),
+                ("RCON_RETRY_MAX_ATTEMPTS", Some("1")),
+            ],
+            async {
+                let request = RconRequest::new(RconRequestType::ExecCommand, "list".into());
+                let result = RconClient::default().execute(&request).await;
+
+                assert!(matches!(result, Err(RconError::Connection { .. })));
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_execute_process_transport_multiline_command() {
+        async_with_vars(
+            [
+                ("RCON_TRANSPORT", Some("process")),
+                ("RCON_PROCESS_COMMAND", Some("cat")),
+                ("RCON_RETRY_MAX_ATTEMPTS", Some("1")),
+            ],
+            async {
+                let client = RconClient::default();
+                let request = RconRequest::new(RconRequestType::ExecCommand, "say hi\nstop".into());
+
+                // The invalid commands are not failures of the server, and never open its circuit.
+                for _ in 0..10 {
+                    let result = client.execute(&request).await;
+                    assert!(matches!(result, Err(RconError::InvalidCommand { .. })));
+                }
+                assert!(
+                    client
+                        .circuit_breaker()
+                        .status(Instant::now())
+                        .iter()
+                        .all(|circuit| circuit.state == RconCircuitState::Closed)
+                );
+            },
+        )
+        .await;
+    }
+}
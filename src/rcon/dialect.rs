@@ -0,0 +1,152 @@
+use super::{DEFAULT_RCON_MAX_PAYLOAD_SIZE, MAX_RCON_RESPONSE_SIZE, RconPayloadEncoding};
+
+/// Maximum size of the payload of a request to the servers following the Source limits, in bytes.
+///
+/// # Note:
+///
+/// The maximum value of the size field is 4096 bytes.
+/// The request ID is 4 bytes.
+/// The request type is 4 bytes.
+/// The null terminator for the payload is 1 byte.
+/// The null terminator for the packet is 1 byte.
+///
+/// Total: 4096 - 4 - 4 - 1 - 1 = 4086
+///
+/// - [Packet size](https://developer.valvesoftware.com/wiki/Source_RCON_Protocol#Packet_Size)
+pub const SOURCE_RCON_MAX_PAYLOAD_SIZE: usize = 4086;
+
+/// Maximum value of the size field of a response from the servers answering with a single packet,
+/// whatever the length of the response.
+pub const MAX_RCON_SINGLE_PACKET_RESPONSE_SIZE: usize = 1024 * 1024;
+
+/// Flavor of the Source RCON protocol spoken by a server.
+///
+/// The packet format is the same for all of them, but the servers differ on the packets they
+/// answer with, how long the packets can be and how their payloads are encoded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RconDialect {
+    /// Minecraft: Java Edition.
+    ///
+    /// - [RCON](https://minecraft.wiki/w/RCON)
+    #[default]
+    Minecraft,
+    /// Servers of the Source engine, and the ones following its reference implementation.
+    ///
+    /// - [Source RCON Protocol](https://developer.valvesoftware.com/wiki/Source_RCON_Protocol)
+    Source,
+    /// Factorio.
+    Factorio,
+    /// ARK: Survival Evolved and ARK: Survival Ascended.
+    Ark,
+    /// Palworld.
+    Palworld,
+}
+
+/// Strategy used to detect the end of a response split in several packets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RconTermination {
+    /// A request of an unknown type is sent after the command, its answer marking the end of the
+    /// response.
+    Sentinel,
+    /// An empty `RESPONSE_VALUE` request is sent after the command, the server mirroring it back
+    /// once the response is sent.
+    ///
+    /// - [Multiple-packet responses](https://developer.valvesoftware.com/wiki/Source_RCON_Protocol#Multiple-packet_Responses)
+    Mirror,
+    /// The response is always a single packet, nothing is sent after the command.
+    SinglePacket,
+}
+
+impl TryFrom<&str> for RconDialect {
+    type Error = String;
+
+    /// Parses a dialect from its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The name of the dialect, e.g. `factorio`.
+    ///
+    /// # Returns
+    ///
+    /// An `RconDialect` if the parsing was successful, otherwise a `String`.
+    fn try_from(value: &str) -> Result<RconDialect, Self::Error> {
+        match value.trim().to_lowercase().as_str() {
+            "minecraft" => Ok(RconDialect::Minecraft),
+            "source" => Ok(RconDialect::Source),
+            "factorio" => Ok(RconDialect::Factorio),
+            "ark" => Ok(RconDialect::Ark),
+            "palworld" => Ok(RconDialect::Palworld),
+            _ => Err(format!("Unknown RCON dialect '{value}'")),
+        }
+    }
+}
+
+impl RconDialect {
+    /// Returns the name of the dialect, as used in the configuration and the telemetry.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RconDialect::Minecraft => "minecraft",
+            RconDialect::Source => "source",
+            RconDialect::Factorio => "factorio",
+            RconDialect::Ark => "ark",
+            RconDialect::Palworld => "palworld",
+        }
+    }
+
+    /// Returns whether the server sends an empty `RESPONSE_VALUE` packet before the
+    /// `AUTH_RESPONSE` one when logging in.
+    ///
+    /// - [Auth response](https://developer.valvesoftware.com/wiki/Source_RCON_Protocol#SERVERDATA_AUTH_RESPONSE)
+    pub fn sends_empty_response_before_auth(&self) -> bool {
+        matches!(self, RconDialect::Source)
+    }
+
+    /// Returns the strategy used to detect the end of a response.
+    ///
+    /// Minecraft answers the requests of an unknown type, the Source servers mirror the empty
+    /// `RESPONSE_VALUE` requests, and the other servers never split their responses but would run
+    /// anything sent after the command as another command.
+    pub fn termination(&self) -> RconTermination {
+        match self {
+            RconDialect::Minecraft => RconTermination::Sentinel,
+            RconDialect::Source => RconTermination::Mirror,
+            RconDialect::Factorio | RconDialect::Ark | RconDialect::Palworld => {
+                RconTermination::SinglePacket
+            }
+        }
+    }
+
+    /// Returns the default maximum size of the payload of a request, in bytes.
+    pub fn max_payload_size(&self) -> usize {
+        match self {
+            RconDialect::Minecraft => DEFAULT_RCON_MAX_PAYLOAD_SIZE,
+            RconDialect::Source
+            | RconDialect::Factorio
+            | RconDialect::Ark
+            | RconDialect::Palworld => SOURCE_RCON_MAX_PAYLOAD_SIZE,
+        }
+    }
+
+    /// Returns the maximum value of the size field of a response packet.
+    ///
+    /// The servers answering with a single packet send it whole, however long the response is.
+    pub fn max_response_size(&self) -> usize {
+        match self.termination() {
+            RconTermination::SinglePacket => MAX_RCON_SINGLE_PACKET_RESPONSE_SIZE,
+            RconTermination::Sentinel | RconTermination::Mirror => MAX_RCON_RESPONSE_SIZE,
+        }
+    }
+
+    /// Returns the default encoding of the payloads of the responses.
+    ///
+    /// The servers known to send invalid UTF-8, such as player names cut in the middle of a
+    /// character, are decoded leniently.
+    pub fn default_encoding(&self) -> RconPayloadEncoding {
+        match self {
+            RconDialect::Minecraft | RconDialect::Factorio => RconPayloadEncoding::Utf8,
+            RconDialect::Source | RconDialect::Ark | RconDialect::Palworld => {
+                RconPayloadEncoding::Utf8Lossy
+            }
+        }
+    }
+}
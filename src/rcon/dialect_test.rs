@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use crate::rcon::{
+        DEFAULT_RCON_MAX_PAYLOAD_SIZE, MAX_RCON_RESPONSE_SIZE,
+        MAX_RCON_SINGLE_PACKET_RESPONSE_SIZE, RconDialect, RconPayloadEncoding, RconTermination,
+        SOURCE_RCON_MAX_PAYLOAD_SIZE,
+    };
+    use test_case::test_case;
+
+    #[test_case("minecraft" => Ok(RconDialect::Minecraft); "minecraft")]
+    #[test_case(" Source " => Ok(RconDialect::Source); "untrimmed")]
+    #[test_case("FACTORIO" => Ok(RconDialect::Factorio); "uppercase")]
+    #[test_case("ark" => Ok(RconDialect::Ark); "ark")]
+    #[test_case("palworld" => Ok(RconDialect::Palworld); "palworld")]
+    #[test_case("bedrock" => Err(String::from("Unknown RCON dialect 'bedrock'")); "unknown")]
+    fn parse_dialect(value: &str) -> Result<RconDialect, String> {
+        RconDialect::try_from(value)
+    }
+
+    #[test_case(RconDialect::Minecraft; "minecraft")]
+    #[test_case(RconDialect::Source; "source")]
+    #[test_case(RconDialect::Factorio; "factorio")]
+    #[test_case(RconDialect::Ark; "ark")]
+    #[test_case(RconDialect::Palworld; "palworld")]
+    fn dialect_name_round_trip(dialect: RconDialect) {
+        assert_eq!(RconDialect::try_from(dialect.as_str()), Ok(dialect));
+    }
+
+    #[test]
+    fn minecraft_dialect() {
+        let dialect = RconDialect::default();
+
+        assert_eq!(dialect, RconDialect::Minecraft);
+        assert!(!dialect.sends_empty_response_before_auth());
+        assert_eq!(dialect.termination(), RconTermination::Sentinel);
+        assert_eq!(dialect.max_payload_size(), DEFAULT_RCON_MAX_PAYLOAD_SIZE);
+        assert_eq!(dialect.max_response_size(), MAX_RCON_RESPONSE_SIZE);
+        assert_eq!(dialect.default_encoding(), RconPayloadEncoding::Utf8);
+    }
+
+    #[test]
+    fn source_dialect() {
+        let dialect = RconDialect::Source;
+
+        assert!(dialect.sends_empty_response_before_auth());
+        assert_eq!(dialect.termination(), RconTermination::Mirror);
+        assert_eq!(dialect.max_payload_size(), SOURCE_RCON_MAX_PAYLOAD_SIZE);
+        assert_eq!(dialect.max_response_size(), MAX_RCON_RESPONSE_SIZE);
+        assert_eq!(dialect.default_encoding(), RconPayloadEncoding::Utf8Lossy);
+    }
+
+    #[test_case(RconDialect::Factorio; "factorio")]
+    #[test_case(RconDialect::Ark; "ark")]
+    #[test_case(RconDialect::Palworld; "palworld")]
+    fn single_packet_dialects(dialect: RconDialect) {
+        assert!(!dialect.sends_empty_response_before_auth());
+        assert_eq!(dialect.termination(), RconTermination::SinglePacket);
+        assert_eq!(dialect.max_payload_size(), SOURCE_RCON_MAX_PAYLOAD_SIZE);
+        assert_eq!(
+            dialect.max_response_size(),
+            MAX_RCON_SINGLE_PACKET_RESPONSE_SIZE
+        );
+    }
+}
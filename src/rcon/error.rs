@@ -6,12 +6,59 @@ pub enum RconError {
     Configuration { cause: String },
     #[error("Failed to connect to the RCON server: {cause}")]
     Connection { cause: String },
+    #[error("Failed to authenticate to the RCON server: {cause}")]
+    Authentication { cause: String },
     #[error("Failed to send data to the RCON server: {cause}")]
     Send { cause: String },
     #[error("Failed to receive data from the RCON server: {cause}")]
     Receive { cause: String },
     #[error("Failed to shutdown the RCON connection: {cause}")]
     Shutdown { cause: String },
+    #[error("Timeout connecting to the RCON server, elapsed time: {elapsed_ms}ms")]
+    ConnectTimeout { elapsed_ms: u64 },
+    #[error("Timeout authenticating to the RCON server, elapsed time: {elapsed_ms}ms")]
+    AuthTimeout { elapsed_ms: u64 },
+    #[error("Timeout sending data to the RCON server, elapsed time: {elapsed_ms}ms")]
+    WriteTimeout { elapsed_ms: u64 },
     #[error("Timeout waiting for RCON response, elapsed time: {elapsed_ms}ms")]
     Timeout { elapsed_ms: u64 },
+    #[error("The RCON command exceeded its deadline, elapsed time: {elapsed_ms}ms")]
+    DeadlineExceeded { elapsed_ms: u64 },
+    #[error("The RCON request is too large, {size} bytes for a maximum of {max_size} bytes")]
+    RequestTooLarge { size: usize, max_size: usize },
+    #[error("Invalid RCON command: {cause}")]
+    InvalidCommand { cause: String },
+    #[error("RCON protocol desync, expected response id {expected} but received {received}")]
+    Desync { expected: i32, received: i32 },
+    #[error("The RCON command queue is full, {depth} commands are already waiting")]
+    QueueFull { depth: usize },
+    #[error("Timeout waiting for a free RCON command slot, elapsed time: {elapsed_ms}ms")]
+    QueueTimeout { elapsed_ms: u64 },
+    #[error("The RCON server '{target}' is unreachable, next attempt in {retry_in_ms}ms")]
+    CircuitOpen { target: String, retry_in_ms: u64 },
+}
+
+impl RconError {
+    /// Returns the kind of the error, as used in the configuration and in the telemetry.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            RconError::Configuration { .. } => "configuration",
+            RconError::Connection { .. } => "connection",
+            RconError::Authentication { .. } => "authentication",
+            RconError::Send { .. } => "send",
+            RconError::Receive { .. } => "receive",
+            RconError::Shutdown { .. } => "shutdown",
+            RconError::ConnectTimeout { .. } => "connect_timeout",
+            RconError::AuthTimeout { .. } => "auth_timeout",
+            RconError::WriteTimeout { .. } => "write_timeout",
+            RconError::Timeout { .. } => "timeout",
+            RconError::DeadlineExceeded { .. } => "deadline_exceeded",
+            RconError::RequestTooLarge { .. } => "request_too_large",
+            RconError::InvalidCommand { .. } => "invalid_command",
+            RconError::Desync { .. } => "desync",
+            RconError::QueueFull { .. } => "queue_full",
+            RconError::QueueTimeout { .. } => "queue_timeout",
+            RconError::CircuitOpen { .. } => "circuit_open",
+        }
+    }
 }
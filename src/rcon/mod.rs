@@ -1,15 +1,38 @@
+pub mod circuit_breaker;
+pub mod circuit_breaker_test;
 pub mod client;
 pub mod client_test;
 pub mod configuration;
 pub mod configuration_test;
+pub mod console;
+pub mod console_test;
+pub mod dialect;
+pub mod dialect_test;
 pub mod error;
+pub mod queue;
+pub mod queue_test;
 pub mod request;
 pub mod request_test;
 pub mod response;
 pub mod response_test;
+pub mod retry;
+pub mod retry_test;
+pub mod secret;
+pub mod secret_test;
+pub mod split;
+pub mod split_test;
+pub mod transport;
 
+pub use circuit_breaker::*;
 pub use client::*;
 pub use configuration::*;
+pub use console::*;
+pub use dialect::*;
 pub use error::*;
+pub use queue::*;
 pub use request::*;
 pub use response::*;
+pub use retry::*;
+pub use secret::*;
+pub use split::*;
+pub use transport::*;
@@ -0,0 +1,341 @@
+use super::RconError;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    env,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+use tokio::{sync::oneshot, time::timeout};
+use tracing::{debug, warn};
+
+/// Default number of commands executed at the same time on a target.
+pub const DEFAULT_RCON_QUEUE_CONCURRENCY: usize = 1;
+
+/// Default number of commands waiting for their turn on a target.
+pub const DEFAULT_RCON_QUEUE_MAX_DEPTH: usize = 32;
+
+/// Default time a command can wait for its turn, in milliseconds.
+pub const DEFAULT_RCON_QUEUE_TIMEOUT: u64 = 10000;
+
+#[derive(Debug, Clone)]
+pub struct RconQueueConfiguration {
+    pub concurrency: usize,
+    pub max_depth: usize,
+    pub timeout: u64,
+    pub priority_tokens: HashSet<String>,
+}
+
+impl RconQueueConfiguration {
+    /// Returns the configuration from environment variables.
+    ///
+    /// # Returns:
+    ///
+    /// The configuration.
+    pub fn try_new() -> Result<RconQueueConfiguration, RconError> {
+        debug!("Attempting to create RCON queue configuration from environment variables...");
+
+        let concurrency = env::var("RCON_QUEUE_CONCURRENCY")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<usize>())
+            .unwrap_or(Ok(DEFAULT_RCON_QUEUE_CONCURRENCY))
+            .ok()
+            .filter(|concurrency| *concurrency > 0)
+            .ok_or_else(|| RconError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RCON_QUEUE_CONCURRENCY' is not a valid positive number",
+                ),
+            })?;
+        let max_depth = env::var("RCON_QUEUE_MAX_DEPTH")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<usize>())
+            .unwrap_or(Ok(DEFAULT_RCON_QUEUE_MAX_DEPTH))
+            .map_err(|_| RconError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RCON_QUEUE_MAX_DEPTH' is not a valid number",
+                ),
+            })?;
+        let timeout = env::var("RCON_QUEUE_TIMEOUT")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u64>())
+            .unwrap_or(Ok(DEFAULT_RCON_QUEUE_TIMEOUT))
+            .map_err(|_| RconError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RCON_QUEUE_TIMEOUT' is not a valid number",
+                ),
+            })?;
+        let priority_tokens = env::var("RCON_QUEUE_PRIORITY_TOKENS")
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|token| !token.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(RconQueueConfiguration {
+            concurrency,
+            max_depth,
+            timeout,
+            priority_tokens,
+        })
+    }
+}
+
+/// Snapshot of the queue of a target.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RconQueueStatus {
+    pub target: String,
+    pub running: usize,
+    pub waiting: usize,
+    pub concurrency: usize,
+    pub max_depth: usize,
+}
+
+/// Command waiting for its turn on a target.
+struct RconQueueWaiter {
+    id: u64,
+    sender: oneshot::Sender<RconQueuePermit>,
+}
+
+/// Commands running and waiting on a single target.
+#[derive(Default)]
+struct RconTargetQueue {
+    running: usize,
+    priority_waiters: VecDeque<RconQueueWaiter>,
+    waiters: VecDeque<RconQueueWaiter>,
+}
+
+impl RconTargetQueue {
+    fn waiting(&self) -> usize {
+        self.priority_waiters.len() + self.waiters.len()
+    }
+}
+
+struct RconCommandQueueInner {
+    configuration: RconQueueConfiguration,
+    targets: Mutex<HashMap<String, RconTargetQueue>>,
+    next_waiter_id: AtomicU64,
+}
+
+/// Bounded queue limiting the number of commands executed at the same time on each target.
+///
+/// Commands get their turn in order of arrival, priority commands being served before the
+/// others. A command waits at most the configured timeout for its turn, and is rejected right
+/// away when the queue of its target is full.
+#[derive(Clone)]
+pub struct RconCommandQueue {
+    inner: Arc<RconCommandQueueInner>,
+}
+
+impl RconCommandQueue {
+    /// Creates a new queue from a configuration.
+    pub fn new(configuration: RconQueueConfiguration) -> Self {
+        RconCommandQueue {
+            inner: Arc::new(RconCommandQueueInner {
+                configuration,
+                targets: Mutex::new(HashMap::new()),
+                next_waiter_id: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Creates a new queue from the environment variables.
+    pub fn try_new() -> Result<Self, RconError> {
+        RconQueueConfiguration::try_new().map(RconCommandQueue::new)
+    }
+
+    /// Returns `true` if the API token gives priority in the queue.
+    pub fn is_priority_token(&self, token: &str) -> bool {
+        self.inner.configuration.priority_tokens.contains(token)
+    }
+
+    /// Waits for the turn of a command on a target.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The address of the RCON server.
+    /// * `priority` - Whether the command is served before the non-priority ones.
+    ///
+    /// # Returns
+    ///
+    /// A permit allowing the command to be executed, releasing its slot when dropped.
+    #[tracing::instrument(
+        name = "RconQueue.Acquire",
+        skip(self),
+        fields(
+            app.component = "rcon",
+        )
+    )]
+    pub async fn acquire(
+        &self,
+        target: &str,
+        priority: bool,
+    ) -> Result<RconQueuePermit, RconError> {
+        let configuration = &self.inner.configuration;
+        let started_at = Instant::now();
+        let id = self.inner.next_waiter_id.fetch_add(1, Ordering::Relaxed);
+
+        let receiver = {
+            let mut targets = self.lock_targets();
+            let queue = targets.entry(String::from(target)).or_default();
+
+            if queue.running < configuration.concurrency && queue.waiting() == 0 {
+                queue.running += 1;
+                self.record_status(target, queue);
+                debug!("Command slot acquired without waiting");
+
+                return Ok(RconQueuePermit::new(self.clone(), target));
+            }
+
+            if queue.waiting() >= configuration.max_depth {
+                warn!("RCON command queue is full for '{}'", target);
+                return Err(RconError::QueueFull {
+                    depth: queue.waiting(),
+                });
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            let waiter = RconQueueWaiter { id, sender };
+            if priority {
+                queue.priority_waiters.push_back(waiter);
+            } else {
+                queue.waiters.push_back(waiter);
+            }
+            self.record_status(target, queue);
+            debug!("Waiting for a command slot, {} waiting", queue.waiting());
+
+            receiver
+        };
+
+        match timeout(Duration::from_millis(configuration.timeout), receiver).await {
+            Ok(Ok(permit)) => {
+                debug!(
+                    "Command slot acquired after {}ms",
+                    started_at.elapsed().as_millis()
+                );
+                Ok(permit)
+            }
+            // The sender is only dropped without sending when the waiter is removed, which can
+            // only happen after the timeout below.
+            Ok(Err(_)) | Err(_) => {
+                let mut targets = self.lock_targets();
+                if let Some(queue) = targets.get_mut(target) {
+                    queue.priority_waiters.retain(|waiter| waiter.id != id);
+                    queue.waiters.retain(|waiter| waiter.id != id);
+                    self.record_status(target, queue);
+                }
+
+                let elapsed_ms = started_at.elapsed().as_millis() as u64;
+                warn!("Timeout waiting for a command slot after {}ms", elapsed_ms);
+                Err(RconError::QueueTimeout { elapsed_ms })
+            }
+        }
+    }
+
+    /// Returns a snapshot of the queues of all the known targets.
+    pub fn status(&self) -> Vec<RconQueueStatus> {
+        let configuration = &self.inner.configuration;
+        let targets = self.lock_targets();
+
+        let mut status: Vec<RconQueueStatus> = targets
+            .iter()
+            .map(|(target, queue)| RconQueueStatus {
+                target: target.clone(),
+                running: queue.running,
+                waiting: queue.waiting(),
+                concurrency: configuration.concurrency,
+                max_depth: configuration.max_depth,
+            })
+            .collect();
+        status.sort_by(|a, b| a.target.cmp(&b.target));
+
+        status
+    }
+
+    /// Gives the slot of a finished command to the next waiting one, or frees it.
+    fn release(&self, target: &str) {
+        let mut targets = self.lock_targets();
+        let Some(queue) = targets.get_mut(target) else {
+            return;
+        };
+
+        while let Some(waiter) = queue
+            .priority_waiters
+            .pop_front()
+            .or_else(|| queue.waiters.pop_front())
+        {
+            // The slot is handed over as is, so the number of running commands is unchanged.
+            match waiter
+                .sender
+                .send(RconQueuePermit::new(self.clone(), target))
+            {
+                Ok(()) => {
+                    self.record_status(target, queue);
+                    return;
+                }
+                // The waiter gave up, the permit must not release the slot again.
+                Err(mut permit) => permit.disarm(),
+            }
+        }
+
+        queue.running = queue.running.saturating_sub(1);
+        self.record_status(target, queue);
+    }
+
+    fn lock_targets(&self) -> std::sync::MutexGuard<'_, HashMap<String, RconTargetQueue>> {
+        self.inner
+            .targets
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    #[allow(unused_variables)]
+    fn record_status(&self, target: &str, queue: &RconTargetQueue) {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "metrics")] {
+                crate::metrics::RCON_QUEUE_RUNNING
+                    .with_label_values(&[target])
+                    .set(queue.running as i64);
+                crate::metrics::RCON_QUEUE_WAITING
+                    .with_label_values(&[target])
+                    .set(queue.waiting() as i64);
+            }
+        }
+    }
+}
+
+/// Slot of a command in the queue of a target, released when dropped.
+pub struct RconQueuePermit {
+    queue: Option<RconCommandQueue>,
+    target: String,
+}
+
+impl RconQueuePermit {
+    fn new(queue: RconCommandQueue, target: &str) -> Self {
+        RconQueuePermit {
+            queue: Some(queue),
+            target: String::from(target),
+        }
+    }
+
+    /// Prevents the permit from releasing its slot when dropped.
+    fn disarm(&mut self) {
+        self.queue = None;
+    }
+}
+
+impl Drop for RconQueuePermit {
+    fn drop(&mut self) {
+        if let Some(queue) = self.queue.take() {
+            queue.release(&self.target);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use crate::rcon::{RconCommandQueue, RconError, RconQueueConfiguration};
+    use std::{collections::HashSet, sync::Arc, time::Duration};
+    use tokio::sync::Mutex;
+
+    const TARGET: &str = "localhost:25575";
+
+    fn queue(concurrency: usize, max_depth: usize, timeout: u64) -> RconCommandQueue {
+        RconCommandQueue::new(RconQueueConfiguration {
+            concurrency,
+            max_depth,
+            timeout,
+            priority_tokens: HashSet::from([String::from("admin")]),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_acquire_within_concurrency() {
+        let queue = queue(2, 0, 100);
+
+        let first = queue.acquire(TARGET, false).await;
+        let second = queue.acquire(TARGET, false).await;
+        assert!(first.is_ok());
+        assert!(second.is_ok());
+
+        let status = queue.status();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].running, 2);
+        assert_eq!(status[0].waiting, 0);
+
+        drop(first);
+        drop(second);
+        assert_eq!(queue.status()[0].running, 0);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_queue_full() {
+        let queue = queue(1, 0, 100);
+
+        let _permit = queue.acquire(TARGET, false).await.unwrap();
+
+        let result = queue.acquire(TARGET, false).await;
+        assert!(matches!(result, Err(RconError::QueueFull { depth: 0 })));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_timeout() {
+        let queue = queue(1, 1, 50);
+
+        let _permit = queue.acquire(TARGET, false).await.unwrap();
+
+        let result = queue.acquire(TARGET, false).await;
+        assert!(matches!(result, Err(RconError::QueueTimeout { elapsed_ms }) if elapsed_ms >= 50));
+
+        // The timed out command left the queue.
+        assert_eq!(queue.status()[0].waiting, 0);
+        assert_eq!(queue.status()[0].running, 1);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_handover() {
+        let queue = queue(1, 1, 1000);
+
+        let permit = queue.acquire(TARGET, false).await.unwrap();
+
+        let waiting_queue = queue.clone();
+        let waiting = tokio::spawn(async move { waiting_queue.acquire(TARGET, false).await });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(queue.status()[0].waiting, 1);
+
+        drop(permit);
+        let permit = waiting.await.unwrap();
+        assert!(permit.is_ok());
+        assert_eq!(queue.status()[0].running, 1);
+        assert_eq!(queue.status()[0].waiting, 0);
+
+        drop(permit);
+        assert_eq!(queue.status()[0].running, 0);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_priority() {
+        let queue = queue(1, 2, 1000);
+        let order = Arc::new(Mutex::new(vec![]));
+
+        let permit = queue.acquire(TARGET, false).await.unwrap();
+
+        let mut handles = vec![];
+        for (name, priority) in [("regular", false), ("admin", true)] {
+            let queue = queue.clone();
+            let order = order.clone();
+            handles.push(tokio::spawn(async move {
+                let _permit = queue.acquire(TARGET, priority).await.unwrap();
+                order.lock().await.push(name);
+            }));
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        drop(permit);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(*order.lock().await, vec!["admin", "regular"]);
+    }
+
+    #[test]
+    fn test_is_priority_token() {
+        let queue = queue(1, 1, 1000);
+
+        assert!(queue.is_priority_token("admin"));
+        assert!(!queue.is_priority_token("user"));
+    }
+}
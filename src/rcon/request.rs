@@ -1,10 +1,13 @@
+use super::REDACTED;
 use rand::RngExt;
+use std::fmt;
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct RconRequest {
     pub request_id: i32,
     pub request_type: RconRequestType,
     pub request_payload: String,
+    pub idempotent: bool,
 }
 
 impl RconRequest {
@@ -23,6 +26,61 @@ impl RconRequest {
             request_id: rand::rng().random::<i32>(),
             request_type,
             request_payload,
+            idempotent: false,
+        }
+    }
+
+    /// Marks the request as safe to be sent twice to the server, allowing it to be retried after
+    /// a failure even if the server may have received it.
+    ///
+    /// # Arguments
+    ///
+    /// * `idempotent` - Whether the request is idempotent.
+    ///
+    /// # Returns
+    ///
+    /// The updated `RconRequest`.
+    pub fn with_idempotent(mut self, idempotent: bool) -> Self {
+        self.idempotent = idempotent;
+        self
+    }
+
+    /// Returns the verb of the command, i.e. its first word without the leading slash.
+    ///
+    /// # Returns
+    ///
+    /// The lowercase verb, or `None` if the request is not a command or the command is blank.
+    pub fn command_verb(&self) -> Option<String> {
+        if self.request_type != RconRequestType::ExecCommand {
+            return None;
+        }
+
+        self.request_payload
+            .split_whitespace()
+            .next()
+            .map(|verb| verb.trim_start_matches('/').to_lowercase())
+            .filter(|verb| !verb.is_empty())
+    }
+
+    /// Returns the payload of the request as it can be recorded in the traces.
+    ///
+    /// The payload of the `Auth` requests, the password, is always redacted.
+    ///
+    /// # Arguments
+    ///
+    /// * `redact_arguments` - Whether the arguments of a command are redacted, only its verb being
+    ///   kept.
+    pub fn traced_payload(&self, redact_arguments: bool) -> String {
+        match self.request_type {
+            RconRequestType::Auth => String::from(REDACTED),
+            RconRequestType::ExecCommand if redact_arguments => {
+                let payload = self.request_payload.trim();
+                match payload.split_once(char::is_whitespace) {
+                    Some((verb, _)) => format!("{verb} {REDACTED}"),
+                    None => String::from(payload),
+                }
+            }
+            _ => self.request_payload.clone(),
         }
     }
 
@@ -69,10 +127,45 @@ impl RconRequest {
     }
 }
 
+impl fmt::Debug for RconRequest {
+    /// Formats the request, the payload of the `Auth` requests being the password.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let payload: &dyn fmt::Debug = match self.request_type {
+            RconRequestType::Auth => &REDACTED,
+            _ => &self.request_payload,
+        };
+
+        f.debug_struct("RconRequest")
+            .field("request_id", &self.request_id)
+            .field("request_type", &self.request_type)
+            .field("request_payload", payload)
+            .field("idempotent", &self.idempotent)
+            .finish()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum RconRequestType {
     ExecCommand,
     Auth,
+    /// Request of an unknown type, sent after a command: the server answers the requests in
+    /// order, so its answer marks the end of the response to the command.
+    Sentinel,
+    /// Empty `RESPONSE_VALUE` request sent after a command to the Source servers, which mirror it
+    /// back once the response to the command is sent.
+    Mirror,
+}
+
+impl RconRequestType {
+    /// Returns the name of the type, as used in the telemetry.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RconRequestType::ExecCommand => "exec_command",
+            RconRequestType::Auth => "auth",
+            RconRequestType::Sentinel => "sentinel",
+            RconRequestType::Mirror => "mirror",
+        }
+    }
 }
 
 impl From<&RconRequestType> for i32 {
@@ -94,6 +187,8 @@ impl From<&RconRequestType> for i32 {
         match kind {
             RconRequestType::ExecCommand => 2,
             RconRequestType::Auth => 3,
+            RconRequestType::Sentinel => 200,
+            RconRequestType::Mirror => 0,
         }
     }
 }
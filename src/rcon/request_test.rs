@@ -1,11 +1,29 @@
 #[cfg(test)]
 mod tests {
-    use crate::rcon::request::RconRequestType;
+    use crate::rcon::request::{RconRequest, RconRequestType};
     use test_case::test_case;
 
     #[test_case(RconRequestType::ExecCommand => 2; "exec_command")]
     #[test_case(RconRequestType::Auth => 3; "auth")]
+    #[test_case(RconRequestType::Sentinel => 200; "sentinel")]
+    #[test_case(RconRequestType::Mirror => 0; "mirror")]
     fn from_request_type_to_i32(kind: RconRequestType) -> i32 {
         (&kind).into()
     }
+
+    #[test_case(RconRequestType::ExecCommand, "/Say Hello" => Some(String::from("say")); "command")]
+    #[test_case(RconRequestType::ExecCommand, "  list " => Some(String::from("list")); "untrimmed")]
+    #[test_case(RconRequestType::ExecCommand, " " => None; "blank")]
+    #[test_case(RconRequestType::Auth, "password" => None; "auth")]
+    fn command_verb(kind: RconRequestType, payload: &str) -> Option<String> {
+        RconRequest::new(kind, String::from(payload)).command_verb()
+    }
+
+    #[test_case(RconRequestType::ExecCommand, "say Hello world", true => "say [REDACTED]"; "redacted")]
+    #[test_case(RconRequestType::ExecCommand, "say Hello world", false => "say Hello world"; "not_redacted")]
+    #[test_case(RconRequestType::ExecCommand, "list", true => "list"; "no_arguments")]
+    #[test_case(RconRequestType::Auth, "password", false => "[REDACTED]"; "auth")]
+    fn traced_payload(kind: RconRequestType, payload: &str, redact_arguments: bool) -> String {
+        RconRequest::new(kind, String::from(payload)).traced_payload(redact_arguments)
+    }
 }
@@ -1,21 +1,118 @@
 use std::str::from_utf8;
 use thiserror::Error;
 
+/// Minimum value of the size field of a response: the ID, the type and the two null terminators.
+pub const MIN_RCON_RESPONSE_SIZE: usize = 10;
+
+/// Maximum value of the size field of a response.
+///
+/// # Note:
+///
+/// The maximum length of the payload is 4096 bytes.
+/// The request ID is 4 bytes.
+/// The request type is 4 bytes.
+/// The null terminator for the payload is 1 byte.
+/// The null terminator for the packet is 1 byte.
+///
+/// Total: 4096 + 4 + 4 + 1 + 1 = 4106, the 4 bytes of the size field excluded.
+///
+/// - [Fragmentation](https://minecraft.wiki/w/RCON#Fragmentation)
+/// - [Packet format](https://minecraft.wiki/w/RCON#Packet_format)
+pub const MAX_RCON_RESPONSE_SIZE: usize = 4106;
+
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum RconResponseError {
-    #[error("Failed to decode RCON response: {cause}")]
-    Decode { cause: String },
+    #[error("Failed to decode RCON response: {length} bytes are too short for a packet")]
+    TooShort { length: usize },
+    #[error("Failed to decode RCON response: invalid packet size {size}")]
+    InvalidSize { size: i32 },
+    #[error("Failed to decode RCON response: expected {expected} bytes but received {received}")]
+    Truncated { expected: usize, received: usize },
+    #[error("Failed to decode RCON response: missing null terminators")]
+    MissingTerminator,
+    #[error("Failed to decode RCON response: unknown response type {code}")]
+    UnknownType { code: i32 },
+    #[error("Failed to decode RCON response payload: {cause}")]
+    InvalidPayload { cause: String },
+}
+
+/// Strategy used to decode the payloads of the responses to text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RconPayloadEncoding {
+    /// Strict UTF-8, invalid payloads are rejected.
+    #[default]
+    Utf8,
+    /// UTF-8, invalid sequences being replaced with `U+FFFD`.
+    Utf8Lossy,
+    /// ISO-8859-1, each byte being a character.
+    Latin1,
+    /// Windows-1252, the Latin-1 superset used by most Windows servers.
+    Windows1252,
+}
+
+impl TryFrom<&str> for RconPayloadEncoding {
+    type Error = String;
+
+    /// Parses an encoding from its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The name of the encoding, e.g. `utf8-lossy`.
+    ///
+    /// # Returns
+    ///
+    /// A `RconPayloadEncoding` if the parsing was successful, otherwise a `String`.
+    fn try_from(value: &str) -> Result<RconPayloadEncoding, Self::Error> {
+        match value.trim().to_lowercase().as_str() {
+            "utf8" | "utf-8" => Ok(RconPayloadEncoding::Utf8),
+            "utf8-lossy" | "utf-8-lossy" => Ok(RconPayloadEncoding::Utf8Lossy),
+            "latin1" | "iso-8859-1" => Ok(RconPayloadEncoding::Latin1),
+            "windows-1252" | "cp1252" => Ok(RconPayloadEncoding::Windows1252),
+            _ => Err(format!("Unknown RCON payload encoding '{value}'")),
+        }
+    }
 }
 
+impl RconPayloadEncoding {
+    /// Decodes a payload to text.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - The payload to decode.
+    ///
+    /// # Returns
+    ///
+    /// The text of the payload, otherwise a `RconResponseError::InvalidPayload`.
+    pub fn decode(&self, bytes: &[u8]) -> Result<String, RconResponseError> {
+        match self {
+            RconPayloadEncoding::Utf8 => from_utf8(bytes).map(String::from).map_err(|err| {
+                RconResponseError::InvalidPayload {
+                    cause: err.to_string(),
+                }
+            }),
+            RconPayloadEncoding::Utf8Lossy => Ok(String::from_utf8_lossy(bytes).into_owned()),
+            RconPayloadEncoding::Latin1 => Ok(bytes.iter().map(|&byte| char::from(byte)).collect()),
+            RconPayloadEncoding::Windows1252 => Ok(encoding_rs::WINDOWS_1252
+                .decode_without_bom_handling(bytes)
+                .0
+                .into_owned()),
+        }
+    }
+}
+
+/// Single packet received from the server, a response being split in several packets when its
+/// payload is too long.
 #[derive(Debug, Clone)]
-pub struct RconResponse {
+pub struct RconResponsePacket {
     pub response_id: i32,
     pub response_type: RconResponseType,
-    pub response_payload: String,
+    pub payload: Vec<u8>,
 }
 
-impl RconResponse {
-    /// Attempts to create an `RconResponse` from a byte slice.
+impl RconResponsePacket {
+    /// Attempts to create an `RconResponsePacket` from a byte slice.
+    ///
+    /// The slice must start with a whole packet, any byte after it is ignored.
     ///
     /// # Arguments
     ///
@@ -23,58 +120,190 @@ impl RconResponse {
     ///
     /// # Returns
     ///
-    /// An `RconResponse` if the decoding was successful, otherwise a `RconResponseError`.
+    /// An `RconResponsePacket` if the decoding was successful, otherwise a `RconResponseError`.
+    pub fn try_from_rcon_bytes(rcon_bytes: &[u8]) -> Result<Self, RconResponseError> {
+        Self::try_from_rcon_bytes_with_max_size(rcon_bytes, MAX_RCON_RESPONSE_SIZE)
+    }
+
+    /// Attempts to create an `RconResponsePacket` from a byte slice, accepting packets up to a
+    /// given size.
+    ///
+    /// # Arguments
+    ///
+    /// * `rcon_bytes` - The byte slice to attempt to decode.
+    /// * `max_size` - The maximum value of the size field, depending on the dialect of the server.
+    ///
+    /// # Returns
+    ///
+    /// An `RconResponsePacket` if the decoding was successful, otherwise a `RconResponseError`.
     #[tracing::instrument(
         name = "RconResponse.Decode",
-        skip(rcon_bytes),
+        skip(rcon_bytes, max_size),
         fields(
             app.component = "rcon",
         )
     )]
-    pub fn try_from_rcon_bytes(rcon_bytes: &[u8]) -> Result<Self, RconResponseError> {
-        // Remember to use little endian
-        let response_size: i32 = i32::from_le_bytes(rcon_bytes[0..4].try_into().map_err(|_| {
-            RconResponseError::Decode {
-                cause: String::from("Failed to decode response size"),
-            }
-        })?);
+    pub fn try_from_rcon_bytes_with_max_size(
+        rcon_bytes: &[u8],
+        max_size: usize,
+    ) -> Result<Self, RconResponseError> {
+        if rcon_bytes.len() < 4 + MIN_RCON_RESPONSE_SIZE {
+            return Err(RconResponseError::TooShort {
+                length: rcon_bytes.len(),
+            });
+        }
 
-        let response_id: i32 = i32::from_le_bytes(rcon_bytes[4..8].try_into().map_err(|_| {
-            RconResponseError::Decode {
-                cause: String::from("Failed to decode response id"),
-            }
-        })?);
+        let response_size = Self::validate_size(read_i32(rcon_bytes, 0), max_size)?;
+        let packet = rcon_bytes
+            .get(4..4 + response_size)
+            .ok_or(RconResponseError::Truncated {
+                expected: response_size,
+                received: rcon_bytes.len() - 4,
+            })?;
+
+        let response_id = read_i32(packet, 0);
 
+        let response_code = read_i32(packet, 4);
         let response_type: RconResponseType =
-            i32::from_le_bytes(rcon_bytes[8..12].try_into().map_err(|_| {
-                RconResponseError::Decode {
-                    cause: String::from("Failed to decode response type"),
-                }
-            })?)
-            .try_into()
-            .map_err(|e| RconResponseError::Decode { cause: e })?;
-
-        let last_payload_byte = response_size as usize
-            // Response size padding
-            + 4
-            // Null terminator for the packet
-            - 1
-            // Null terminator for the payload
-            - 1;
-
-        let response_payload: String =
-            String::from(from_utf8(&rcon_bytes[12..last_payload_byte]).map_err(|_| {
-                RconResponseError::Decode {
-                    cause: String::from("Failed to decode response payload"),
-                }
-            })?);
+            response_code
+                .try_into()
+                .map_err(|_| RconResponseError::UnknownType {
+                    code: response_code,
+                })?;
 
-        Ok(RconResponse {
+        // The payload is followed by its null terminator and the one of the packet.
+        let (payload, terminators) = packet[8..].split_at(response_size - 8 - 2);
+        if terminators != [0, 0] {
+            return Err(RconResponseError::MissingTerminator);
+        }
+
+        Ok(RconResponsePacket {
             response_id,
             response_type,
-            response_payload,
+            payload: payload.to_vec(),
         })
     }
+
+    /// Validates the size field of a packet.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The size field, the number of bytes of the packet following it.
+    /// * `max_size` - The maximum value of the size field.
+    ///
+    /// # Returns
+    ///
+    /// The size if it is in the valid range, otherwise a `RconResponseError::InvalidSize`.
+    pub fn validate_size(size: i32, max_size: usize) -> Result<usize, RconResponseError> {
+        usize::try_from(size)
+            .ok()
+            .filter(|size| (MIN_RCON_RESPONSE_SIZE..=max_size).contains(size))
+            .ok_or(RconResponseError::InvalidSize { size })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RconResponse {
+    pub response_id: i32,
+    pub response_type: RconResponseType,
+    pub response_payload: String,
+    /// The payload as received, before being decoded to text.
+    pub response_bytes: Vec<u8>,
+}
+
+impl RconResponse {
+    /// Attempts to create an `RconResponse` from a byte slice holding a single UTF-8 packet.
+    ///
+    /// # Arguments
+    ///
+    /// * `rcon_bytes` - The byte slice to attempt to decode.
+    ///
+    /// # Returns
+    ///
+    /// An `RconResponse` if the decoding was successful, otherwise a `RconResponseError`.
+    #[allow(unused)]
+    pub fn try_from_rcon_bytes(rcon_bytes: &[u8]) -> Result<Self, RconResponseError> {
+        let packet = RconResponsePacket::try_from_rcon_bytes(rcon_bytes)?;
+        Self::try_from_fragments(vec![packet], RconPayloadEncoding::Utf8)
+    }
+
+    /// Attempts to create an `RconResponse` from the packets of a response.
+    ///
+    /// The payloads are joined before being decoded, so that a character split between two
+    /// packets is decoded properly.
+    ///
+    /// # Arguments
+    ///
+    /// * `fragments` - The packets of the response, in order.
+    /// * `encoding` - The encoding of the payload.
+    ///
+    /// # Returns
+    ///
+    /// An `RconResponse` if the decoding was successful, otherwise a `RconResponseError`.
+    pub fn try_from_fragments(
+        fragments: Vec<RconResponsePacket>,
+        encoding: RconPayloadEncoding,
+    ) -> Result<Self, RconResponseError> {
+        let mut fragments = fragments.into_iter();
+        let first = fragments
+            .next()
+            .ok_or(RconResponseError::TooShort { length: 0 })?;
+
+        let mut response_bytes = first.payload;
+        for fragment in fragments {
+            response_bytes.extend_from_slice(&fragment.payload);
+        }
+
+        Ok(RconResponse {
+            response_id: first.response_id,
+            response_type: first.response_type,
+            response_payload: encoding.decode(&response_bytes)?,
+            response_bytes,
+        })
+    }
+
+    /// Joins the responses to the commands a command was split in.
+    ///
+    /// The non-empty payloads are separated by a new line, the ID and the type being the ones of
+    /// the first response.
+    ///
+    /// # Arguments
+    ///
+    /// * `responses` - The responses, in order.
+    ///
+    /// # Returns
+    ///
+    /// The joined response, `None` if there is no response.
+    pub fn join(responses: Vec<RconResponse>) -> Option<RconResponse> {
+        let mut responses = responses.into_iter();
+        let mut joined = responses.next()?;
+
+        for response in responses {
+            if response.response_bytes.is_empty() {
+                continue;
+            }
+
+            if !joined.response_bytes.is_empty() {
+                joined.response_payload.push('\n');
+                joined.response_bytes.push(b'\n');
+            }
+            joined.response_payload.push_str(&response.response_payload);
+            joined
+                .response_bytes
+                .extend_from_slice(&response.response_bytes);
+        }
+
+        Some(joined)
+    }
+}
+
+/// Reads a little endian `i32` at an offset, the slice being known to be long enough.
+fn read_i32(bytes: &[u8], offset: usize) -> i32 {
+    let mut buffer = [0u8; 4];
+    buffer.copy_from_slice(&bytes[offset..offset + 4]);
+
+    // Remember to use little endian
+    i32::from_le_bytes(buffer)
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -83,6 +312,16 @@ pub enum RconResponseType {
     ResponseValue,
 }
 
+impl RconResponseType {
+    /// Returns the name of the type, as used in the telemetry.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RconResponseType::AuthResponse => "auth_response",
+            RconResponseType::ResponseValue => "response_value",
+        }
+    }
+}
+
 impl TryFrom<i32> for RconResponseType {
     type Error = String;
 
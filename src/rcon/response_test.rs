@@ -1,12 +1,237 @@
 #[cfg(test)]
 mod tests {
-    use crate::rcon::response::RconResponseType;
+    use crate::rcon::response::{
+        MAX_RCON_RESPONSE_SIZE, RconPayloadEncoding, RconResponse, RconResponseError,
+        RconResponsePacket, RconResponseType,
+    };
+    use proptest::prelude::*;
     use test_case::test_case;
 
+    /// Encodes a response packet, the size field being computed from the payload.
+    fn encode(id: i32, code: i32, payload: &[u8], terminators: &[u8]) -> Vec<u8> {
+        let size = (4 + 4 + payload.len() + terminators.len()) as i32;
+
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&size.to_le_bytes());
+        bytes.extend_from_slice(&id.to_le_bytes());
+        bytes.extend_from_slice(&code.to_le_bytes());
+        bytes.extend_from_slice(payload);
+        bytes.extend_from_slice(terminators);
+        bytes
+    }
+
     #[test_case(0 => matches Ok(RconResponseType::ResponseValue); "response_value")]
     #[test_case(2 => matches Ok(RconResponseType::AuthResponse); "auth_response")]
     #[test_case(1 => matches Err(_); "invalid")]
     fn try_from_i32_to_reponse_type(code: i32) -> Result<RconResponseType, String> {
         code.try_into()
     }
+
+    #[test]
+    fn test_decode() {
+        let bytes = encode(42, 0, b"There are 0 of a max of 20 players online", &[0, 0]);
+
+        let response = RconResponse::try_from_rcon_bytes(&bytes).unwrap();
+        assert_eq!(response.response_id, 42);
+        assert_eq!(response.response_type, RconResponseType::ResponseValue);
+        assert_eq!(
+            response.response_payload,
+            "There are 0 of a max of 20 players online"
+        );
+    }
+
+    #[test]
+    fn test_decode_ignores_trailing_bytes() {
+        let mut bytes = encode(-1, 2, b"", &[0, 0]);
+        bytes.extend_from_slice(&[0u8; 64]);
+
+        let response = RconResponse::try_from_rcon_bytes(&bytes).unwrap();
+        assert_eq!(response.response_id, -1);
+        assert_eq!(response.response_type, RconResponseType::AuthResponse);
+        assert_eq!(response.response_payload, "");
+    }
+
+    #[test_case(&[] => RconResponseError::TooShort { length: 0 }; "empty")]
+    #[test_case(&[10, 0, 0, 0, 1, 0, 0, 0] => RconResponseError::TooShort { length: 8 }; "too_short")]
+    fn test_decode_too_short(bytes: &[u8]) -> RconResponseError {
+        RconResponse::try_from_rcon_bytes(bytes).unwrap_err()
+    }
+
+    #[test_case(-1; "negative")]
+    #[test_case(9; "below_minimum")]
+    #[test_case(MAX_RCON_RESPONSE_SIZE as i32 + 1; "above_maximum")]
+    #[test_case(i32::MAX; "overflowing")]
+    fn test_decode_invalid_size(size: i32) {
+        let mut bytes = encode(1, 0, b"", &[0, 0]);
+        bytes[0..4].copy_from_slice(&size.to_le_bytes());
+
+        assert_eq!(
+            RconResponse::try_from_rcon_bytes(&bytes).unwrap_err(),
+            RconResponseError::InvalidSize { size }
+        );
+    }
+
+    #[test]
+    fn test_decode_with_max_size() {
+        let payload = vec![b'a'; MAX_RCON_RESPONSE_SIZE];
+        let bytes = encode(1, 0, &payload, &[0, 0]);
+
+        assert!(RconResponsePacket::try_from_rcon_bytes(&bytes).is_err());
+
+        let packet = RconResponsePacket::try_from_rcon_bytes_with_max_size(
+            &bytes,
+            2 * MAX_RCON_RESPONSE_SIZE,
+        )
+        .unwrap();
+        assert_eq!(packet.payload, payload);
+    }
+
+    #[test]
+    fn test_decode_truncated() {
+        let mut bytes = encode(1, 0, b"Hello", &[0, 0]);
+        bytes.truncate(bytes.len() - 3);
+
+        assert_eq!(
+            RconResponse::try_from_rcon_bytes(&bytes).unwrap_err(),
+            RconResponseError::Truncated {
+                expected: 15,
+                received: 12,
+            }
+        );
+    }
+
+    #[test_case(&[b'!', 0]; "missing_payload_terminator")]
+    #[test_case(&[0, b'!']; "missing_packet_terminator")]
+    fn test_decode_missing_terminator(terminators: &[u8]) {
+        let bytes = encode(1, 0, b"Hello", terminators);
+
+        assert_eq!(
+            RconResponse::try_from_rcon_bytes(&bytes).unwrap_err(),
+            RconResponseError::MissingTerminator
+        );
+    }
+
+    #[test]
+    fn test_decode_unknown_type() {
+        let bytes = encode(1, 3, b"", &[0, 0]);
+
+        assert_eq!(
+            RconResponse::try_from_rcon_bytes(&bytes).unwrap_err(),
+            RconResponseError::UnknownType { code: 3 }
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_payload() {
+        let bytes = encode(1, 0, &[0xff, 0xfe], &[0, 0]);
+
+        assert!(matches!(
+            RconResponse::try_from_rcon_bytes(&bytes).unwrap_err(),
+            RconResponseError::InvalidPayload { .. }
+        ));
+    }
+
+    #[test_case("utf8" => Ok(RconPayloadEncoding::Utf8); "utf8")]
+    #[test_case("UTF-8" => Ok(RconPayloadEncoding::Utf8); "utf8_uppercase")]
+    #[test_case("utf8-lossy" => Ok(RconPayloadEncoding::Utf8Lossy); "utf8_lossy")]
+    #[test_case("latin1" => Ok(RconPayloadEncoding::Latin1); "latin1")]
+    #[test_case("windows-1252" => Ok(RconPayloadEncoding::Windows1252); "windows_1252")]
+    #[test_case("ebcdic" => matches Err(_); "unknown")]
+    fn try_from_str_to_payload_encoding(value: &str) -> Result<RconPayloadEncoding, String> {
+        RconPayloadEncoding::try_from(value)
+    }
+
+    #[test_case(RconPayloadEncoding::Utf8, b"Caf\xc3\xa9" => Ok(String::from("Café")); "utf8")]
+    #[test_case(RconPayloadEncoding::Utf8, b"Caf\xe9" => matches Err(RconResponseError::InvalidPayload { .. }); "utf8_invalid")]
+    #[test_case(RconPayloadEncoding::Utf8Lossy, b"Caf\xe9" => Ok(String::from("Caf\u{fffd}")); "utf8_lossy")]
+    #[test_case(RconPayloadEncoding::Latin1, b"Caf\xe9" => Ok(String::from("Café")); "latin1")]
+    #[test_case(RconPayloadEncoding::Latin1, b"\x80" => Ok(String::from("\u{80}")); "latin1_control")]
+    #[test_case(RconPayloadEncoding::Windows1252, b"\x80 Caf\xe9" => Ok(String::from("€ Café")); "windows_1252")]
+    fn test_decode_payload(
+        encoding: RconPayloadEncoding,
+        bytes: &[u8],
+    ) -> Result<String, RconResponseError> {
+        encoding.decode(bytes)
+    }
+
+    #[test]
+    fn test_decode_fragments_split_character() {
+        let fragments = vec![
+            RconResponsePacket::try_from_rcon_bytes(&encode(7, 0, b"Caf\xc3", &[0, 0])).unwrap(),
+            RconResponsePacket::try_from_rcon_bytes(&encode(7, 0, b"\xa9 au lait", &[0, 0]))
+                .unwrap(),
+        ];
+
+        let response =
+            RconResponse::try_from_fragments(fragments, RconPayloadEncoding::Utf8).unwrap();
+        assert_eq!(response.response_id, 7);
+        assert_eq!(response.response_payload, "Café au lait");
+        assert_eq!(response.response_bytes, "Café au lait".as_bytes());
+    }
+
+    #[test]
+    fn test_decode_no_fragments() {
+        assert!(RconResponse::try_from_fragments(vec![], RconPayloadEncoding::Utf8).is_err());
+    }
+
+    #[test]
+    fn test_join() {
+        let response = |id: i32, payload: &str| RconResponse {
+            response_id: id,
+            response_type: RconResponseType::ResponseValue,
+            response_payload: String::from(payload),
+            response_bytes: payload.as_bytes().to_vec(),
+        };
+
+        let joined = RconResponse::join(vec![
+            response(1, "First"),
+            response(2, ""),
+            response(3, "Third"),
+        ])
+        .unwrap();
+
+        assert_eq!(joined.response_id, 1);
+        assert_eq!(joined.response_payload, "First\nThird");
+        assert_eq!(joined.response_bytes, b"First\nThird");
+        assert!(RconResponse::join(vec![]).is_none());
+    }
+
+    proptest! {
+        #[test]
+        fn test_decode_arbitrary_bytes(bytes in proptest::collection::vec(any::<u8>(), 0..64)) {
+            // Must never panic, whatever the input.
+            let _ = RconResponse::try_from_rcon_bytes(&bytes);
+        }
+
+        #[test]
+        fn test_decode_arbitrary_size(
+            size in any::<i32>(),
+            body in proptest::collection::vec(any::<u8>(), 0..256),
+        ) {
+            let mut bytes = size.to_le_bytes().to_vec();
+            bytes.extend_from_slice(&body);
+
+            // Must never panic, whatever the size field.
+            let _ = RconResponse::try_from_rcon_bytes(&bytes);
+        }
+
+        #[test]
+        fn test_decode_roundtrip(
+            id in any::<i32>(),
+            auth in any::<bool>(),
+            payload in "[^\u{0}]{0,1000}",
+        ) {
+            prop_assume!(payload.len() <= MAX_RCON_RESPONSE_SIZE - 10);
+            let code = if auth { 2 } else { 0 };
+            let bytes = encode(id, code, payload.as_bytes(), &[0, 0]);
+
+            let response = RconResponse::try_from_rcon_bytes(&bytes).unwrap();
+            prop_assert_eq!(response.response_id, id);
+            prop_assert_eq!(
+                response.response_type,
+                if auth { RconResponseType::AuthResponse } else { RconResponseType::ResponseValue }
+            );
+            prop_assert_eq!(response.response_payload, payload);
+        }
+    }
 }
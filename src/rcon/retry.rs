@@ -0,0 +1,153 @@
+use super::RconError;
+use rand::RngExt;
+use std::{collections::HashSet, env, time::Duration};
+use tracing::debug;
+
+/// Default number of attempts for a command, including the first one.
+pub const DEFAULT_RCON_RETRY_MAX_ATTEMPTS: u32 = 3;
+
+/// Default delay before the first retry, in milliseconds.
+pub const DEFAULT_RCON_RETRY_BASE_DELAY: u64 = 200;
+
+/// Default maximum delay between two attempts, in milliseconds.
+pub const DEFAULT_RCON_RETRY_MAX_DELAY: u64 = 2000;
+
+/// Default kinds of errors worth retrying, see `RconError::kind`.
+pub const DEFAULT_RCON_RETRY_ON: &str = "connection,connect_timeout,send,write_timeout,timeout";
+
+/// Default commands that can safely be sent twice to the server.
+pub const DEFAULT_RCON_RETRY_IDEMPOTENT_COMMANDS: &str =
+    "help,list,seed,banlist,version,tps,plugins";
+
+#[derive(Debug, Clone)]
+pub struct RconRetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: u64,
+    pub max_delay: u64,
+    pub jitter: bool,
+    pub retry_on: HashSet<String>,
+    pub idempotent_commands: HashSet<String>,
+}
+
+impl RconRetryPolicy {
+    /// Returns the retry policy from environment variables.
+    ///
+    /// # Returns:
+    ///
+    /// The retry policy.
+    pub fn try_new() -> Result<RconRetryPolicy, RconError> {
+        debug!("Attempting to create RCON retry policy from environment variables...");
+
+        let max_attempts = env::var("RCON_RETRY_MAX_ATTEMPTS")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u32>())
+            .unwrap_or(Ok(DEFAULT_RCON_RETRY_MAX_ATTEMPTS))
+            .ok()
+            .filter(|max_attempts| *max_attempts > 0)
+            .ok_or_else(|| RconError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RCON_RETRY_MAX_ATTEMPTS' is not a valid positive number",
+                ),
+            })?;
+        let base_delay = env::var("RCON_RETRY_BASE_DELAY")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u64>())
+            .unwrap_or(Ok(DEFAULT_RCON_RETRY_BASE_DELAY))
+            .map_err(|_| RconError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RCON_RETRY_BASE_DELAY' is not a valid number",
+                ),
+            })?;
+        let max_delay = env::var("RCON_RETRY_MAX_DELAY")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u64>())
+            .unwrap_or(Ok(DEFAULT_RCON_RETRY_MAX_DELAY))
+            .map_err(|_| RconError::Configuration {
+                cause: String::from(
+                    "Environment variable 'RCON_RETRY_MAX_DELAY' is not a valid number",
+                ),
+            })?;
+        let jitter = env::var("RCON_RETRY_JITTER")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<bool>())
+            .unwrap_or(Ok(true))
+            .map_err(|_| RconError::Configuration {
+                cause: String::from("Environment variable 'RCON_RETRY_JITTER' is not a boolean"),
+            })?;
+        let retry_on = Self::parse_list(
+            &env::var("RCON_RETRY_ON").unwrap_or_else(|_| String::from(DEFAULT_RCON_RETRY_ON)),
+        );
+        let idempotent_commands = Self::parse_list(
+            &env::var("RCON_RETRY_IDEMPOTENT_COMMANDS")
+                .unwrap_or_else(|_| String::from(DEFAULT_RCON_RETRY_IDEMPOTENT_COMMANDS)),
+        );
+
+        Ok(RconRetryPolicy {
+            max_attempts,
+            base_delay,
+            max_delay,
+            jitter,
+            retry_on,
+            idempotent_commands,
+        })
+    }
+
+    /// Returns `true` if the command can safely be sent twice to the server.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command, only its first word is considered.
+    pub fn is_idempotent_command(&self, command: &str) -> bool {
+        command
+            .split_whitespace()
+            .next()
+            .map(|verb| verb.trim_start_matches('/').to_lowercase())
+            .is_some_and(|verb| self.idempotent_commands.contains(&verb))
+    }
+
+    /// Returns `true` if a failed attempt should be retried.
+    ///
+    /// # Arguments
+    ///
+    /// * `error` - The error of the failed attempt.
+    /// * `attempt` - The number of the failed attempt, starting at 1.
+    /// * `replayable` - Whether the command can be sent again, either because it was not sent yet
+    ///   or because it is idempotent.
+    pub fn should_retry(&self, error: &RconError, attempt: u32, replayable: bool) -> bool {
+        attempt < self.max_attempts && replayable && self.retry_on.contains(error.kind())
+    }
+
+    /// Returns the delay to wait after a failed attempt.
+    ///
+    /// The delay doubles after each attempt, up to the maximum delay. With jitter, a random delay
+    /// between the half and the full computed delay is used, so that clients do not retry in sync.
+    ///
+    /// # Arguments
+    ///
+    /// * `attempt` - The number of the failed attempt, starting at 1.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay);
+
+        if self.jitter && delay > 1 {
+            Duration::from_millis(rand::rng().random_range(delay / 2..=delay))
+        } else {
+            Duration::from_millis(delay)
+        }
+    }
+
+    /// Parses a comma-separated list of lowercase values.
+    fn parse_list(value: &str) -> HashSet<String> {
+        value
+            .split(',')
+            .map(|item| item.trim().to_lowercase())
+            .filter(|item| !item.is_empty())
+            .collect()
+    }
+}
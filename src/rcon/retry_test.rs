@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use crate::rcon::{RconError, RconRetryPolicy};
+    use std::{collections::HashSet, time::Duration};
+    use test_case::test_case;
+
+    fn policy(jitter: bool) -> RconRetryPolicy {
+        RconRetryPolicy {
+            max_attempts: 3,
+            base_delay: 100,
+            max_delay: 300,
+            jitter,
+            retry_on: HashSet::from([String::from("connection"), String::from("timeout")]),
+            idempotent_commands: HashSet::from([String::from("list")]),
+        }
+    }
+
+    #[test_case("list" => true; "idempotent")]
+    #[test_case("/list uuids" => true; "idempotent_with_slash_and_arguments")]
+    #[test_case("LIST" => true; "idempotent_uppercase")]
+    #[test_case("say hello" => false; "not_idempotent")]
+    #[test_case("" => false; "empty")]
+    fn test_is_idempotent_command(command: &str) -> bool {
+        policy(false).is_idempotent_command(command)
+    }
+
+    #[test_case(1 => Duration::from_millis(100); "first_attempt")]
+    #[test_case(2 => Duration::from_millis(200); "second_attempt")]
+    #[test_case(3 => Duration::from_millis(300); "capped")]
+    #[test_case(64 => Duration::from_millis(300); "overflow")]
+    fn test_delay_without_jitter(attempt: u32) -> Duration {
+        policy(false).delay(attempt)
+    }
+
+    #[test]
+    fn test_delay_with_jitter() {
+        let policy = policy(true);
+
+        for _ in 0..100 {
+            let delay = policy.delay(2);
+            assert!(delay >= Duration::from_millis(100));
+            assert!(delay <= Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn test_should_retry() {
+        let policy = policy(false);
+        let connection = RconError::Connection {
+            cause: String::from("Connection refused"),
+        };
+        let receive = RconError::Receive {
+            cause: String::from("Connection reset"),
+        };
+
+        assert!(policy.should_retry(&connection, 1, true));
+        assert!(policy.should_retry(&connection, 2, true));
+        // Last attempt
+        assert!(!policy.should_retry(&connection, 3, true));
+        // Not replayable
+        assert!(!policy.should_retry(&connection, 1, false));
+        // Not a retryable kind
+        assert!(!policy.should_retry(&receive, 1, true));
+    }
+}
@@ -0,0 +1,44 @@
+use serde::Deserialize;
+use std::fmt;
+
+/// Placeholder written instead of the value of a secret.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Secret value, such as a password, never written in the logs nor in the spans.
+///
+/// The value is only available through `Secret::expose`, its `Debug` and `Display`
+/// implementations write a placeholder instead.
+#[derive(Clone, PartialEq, Eq, Deserialize)]
+#[serde(from = "String")]
+pub struct Secret(String);
+
+impl Secret {
+    /// Returns the secret value, to be used only where it is actually needed.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Secret(value)
+    }
+}
+
+impl PartialEq<&str> for Secret {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod tests {
+    use crate::rcon::{RconRequest, RconRequestType, Secret};
+
+    #[test]
+    fn test_secret_redacted() {
+        let secret = Secret::from(String::from("hunter2"));
+
+        assert_eq!(format!("{secret}"), "[REDACTED]");
+        assert_eq!(format!("{secret:?}"), "[REDACTED]");
+        assert_eq!(secret.expose(), "hunter2");
+        assert_eq!(secret, "hunter2");
+    }
+
+    #[test]
+    fn test_auth_request_redacted() {
+        let request = RconRequest::new(RconRequestType::Auth, String::from("hunter2"));
+
+        let debug = format!("{request:?}");
+        assert!(!debug.contains("hunter2"));
+        assert!(debug.contains("[REDACTED]"));
+    }
+
+    #[test]
+    fn test_command_request_not_redacted() {
+        let request = RconRequest::new(RconRequestType::ExecCommand, String::from("list"));
+
+        assert!(format!("{request:?}").contains("list"));
+    }
+}
@@ -0,0 +1,158 @@
+use super::RconError;
+
+/// Smallest room left for the text of a `say` command to be split, so that any character fits.
+const MIN_SPLIT_TEXT_SIZE: usize = 4;
+
+/// Prepares a command to be sent to the server, in one or several requests.
+///
+/// When the splitting is enabled, only the commands known to be safely splittable are split:
+/// - The text of a `say` command is split in several `say` commands, one per line and as many
+///   as needed for the long lines.
+/// - Multi-line content, e.g. the content of a function, is sent one line at a time, skipping
+///   the empty lines and the comments.
+///
+/// # Arguments
+///
+/// * `command` - The command to send.
+/// * `max_size` - The maximum size of the payload of a request, in bytes.
+/// * `split_long_commands` - Whether the command can be split in several ones.
+///
+/// # Returns
+///
+/// The commands to send in order, otherwise an `RconError::RequestTooLarge` error if the
+/// command does not fit in a request and cannot be split.
+pub fn split_command(
+    command: &str,
+    max_size: usize,
+    split_long_commands: bool,
+) -> Result<Vec<String>, RconError> {
+    if !split_long_commands || (!command.contains('\n') && command.len() <= max_size) {
+        return check_size(command, max_size).map(|_| vec![String::from(command)]);
+    }
+
+    if let Some((prefix, text)) = split_say(command) {
+        let commands =
+            split_say_text(prefix, text, max_size).ok_or(RconError::RequestTooLarge {
+                size: command.len(),
+                max_size,
+            })?;
+
+        // Nothing to split, the text is blank.
+        if commands.is_empty() {
+            return check_size(command, max_size).map(|_| vec![String::from(command)]);
+        }
+
+        return Ok(commands);
+    }
+
+    let lines: Vec<&str> = command
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .collect();
+
+    if lines.is_empty() {
+        return check_size(command, max_size).map(|_| vec![String::from(command)]);
+    }
+
+    let mut commands = Vec::new();
+    for line in lines {
+        if line.len() <= max_size {
+            commands.push(String::from(line));
+            continue;
+        }
+
+        let chunks = split_say(line)
+            .and_then(|(prefix, text)| split_say_text(prefix, text, max_size))
+            .ok_or(RconError::RequestTooLarge {
+                size: line.len(),
+                max_size,
+            })?;
+        commands.extend(chunks);
+    }
+
+    Ok(commands)
+}
+
+/// Returns an `RconError::RequestTooLarge` error if the command does not fit in a request.
+fn check_size(command: &str, max_size: usize) -> Result<(), RconError> {
+    if command.len() > max_size {
+        return Err(RconError::RequestTooLarge {
+            size: command.len(),
+            max_size,
+        });
+    }
+
+    Ok(())
+}
+
+/// Splits a `say` command between its verb, with a trailing space, and its text.
+fn split_say(command: &str) -> Option<(&str, &str)> {
+    let command = command.trim_start();
+    let (verb, text) = command.split_once(char::is_whitespace)?;
+
+    verb.trim_start_matches('/')
+        .eq_ignore_ascii_case("say")
+        .then(|| (&command[..verb.len() + 1], text))
+}
+
+/// Splits the text of a `say` command in as many `say` commands as needed, one per line at least.
+fn split_say_text(prefix: &str, text: &str, max_size: usize) -> Option<Vec<String>> {
+    let limit = max_size.checked_sub(prefix.len())?;
+    if limit < MIN_SPLIT_TEXT_SIZE {
+        return None;
+    }
+
+    let commands = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .flat_map(|line| split_text(line, limit))
+        .map(|chunk| format!("{prefix}{chunk}"))
+        .collect();
+
+    Some(commands)
+}
+
+/// Splits a text in chunks of at most `limit` bytes, between the words when possible.
+fn split_text(text: &str, limit: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word = word;
+
+        // Words longer than a chunk are split between their characters.
+        while word.len() > limit {
+            if !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+            }
+
+            let cut = (1..=limit)
+                .rev()
+                .find(|index| word.is_char_boundary(*index))
+                .unwrap_or(limit);
+            chunks.push(String::from(&word[..cut]));
+            word = &word[cut..];
+        }
+
+        if word.is_empty() {
+            continue;
+        }
+
+        if current.is_empty() {
+            current.push_str(word);
+        } else if current.len() + 1 + word.len() <= limit {
+            current.push(' ');
+            current.push_str(word);
+        } else {
+            chunks.push(std::mem::replace(&mut current, String::from(word)));
+        }
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
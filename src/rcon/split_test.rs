@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use crate::rcon::{RconError, split_command};
+
+    #[test]
+    fn test_split_command_fitting() {
+        let commands = split_command("say Hello world", 1446, true).unwrap();
+
+        assert_eq!(commands, vec!["say Hello world"]);
+    }
+
+    #[test]
+    fn test_split_command_disabled() {
+        let command = format!("say {}", "a".repeat(20));
+
+        assert_eq!(
+            split_command(&command, 16, false).unwrap_err(),
+            RconError::RequestTooLarge {
+                size: 24,
+                max_size: 16,
+            }
+        );
+    }
+
+    #[test]
+    fn test_split_command_disabled_keeps_lines() {
+        let commands = split_command("say Hello\nworld", 1446, false).unwrap();
+
+        assert_eq!(commands, vec!["say Hello\nworld"]);
+    }
+
+    #[test]
+    fn test_split_command_not_splittable() {
+        let command = format!("give @p minecraft:stone{}", "{}".repeat(20));
+
+        assert_eq!(
+            split_command(&command, 32, true).unwrap_err(),
+            RconError::RequestTooLarge {
+                size: command.len(),
+                max_size: 32,
+            }
+        );
+    }
+
+    #[test]
+    fn test_split_command_long_say() {
+        let commands = split_command("say The quick brown fox jumps over the lazy dog", 20, true);
+
+        assert_eq!(
+            commands.unwrap(),
+            vec![
+                "say The quick brown",
+                "say fox jumps over",
+                "say the lazy dog",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_command_long_word() {
+        let commands = split_command(&format!("/say {}", "é".repeat(10)), 12, true).unwrap();
+
+        assert_eq!(commands, vec!["/say ééé", "/say ééé", "/say ééé", "/say é"]);
+        assert!(commands.iter().all(|command| command.len() <= 12));
+    }
+
+    #[test]
+    fn test_split_command_multi_line_say() {
+        let commands = split_command("say Hello\n\nworld\n", 1446, true).unwrap();
+
+        assert_eq!(commands, vec!["say Hello", "say world"]);
+    }
+
+    #[test]
+    fn test_split_command_function_content() {
+        let content = "# Setup\ntime set day\n\nweather clear\nsay Ready to go";
+        let commands = split_command(content, 14, true).unwrap();
+
+        assert_eq!(
+            commands,
+            vec!["time set day", "weather clear", "say Ready to", "say go"]
+        );
+    }
+
+    #[test]
+    fn test_split_command_function_content_too_large() {
+        let content = "time set day\nweather clear";
+
+        assert_eq!(
+            split_command(content, 12, true).unwrap_err(),
+            RconError::RequestTooLarge {
+                size: 13,
+                max_size: 12,
+            }
+        );
+    }
+}
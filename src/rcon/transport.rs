@@ -0,0 +1,100 @@
+use super::{ConnectedRconClient, ProcessRconConnection, RconError, RconRequest, RconResponse};
+
+/// Way the requests reach the server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RconTransportType {
+    /// The RCON protocol, over a TCP connection to the server.
+    #[default]
+    Tcp,
+    /// The console of a server process launched by the application, the commands being written
+    /// to its standard input and the responses read from its standard output.
+    Process,
+}
+
+impl TryFrom<&str> for RconTransportType {
+    type Error = String;
+
+    /// Parses a transport type from its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The name of the transport type, e.g. `process`.
+    ///
+    /// # Returns
+    ///
+    /// An `RconTransportType` if the parsing was successful, otherwise a `String`.
+    fn try_from(value: &str) -> Result<RconTransportType, Self::Error> {
+        match value.trim().to_lowercase().as_str() {
+            "tcp" => Ok(RconTransportType::Tcp),
+            "process" => Ok(RconTransportType::Process),
+            _ => Err(format!("Unknown RCON transport '{value}'")),
+        }
+    }
+}
+
+impl RconTransportType {
+    /// Returns the name of the transport type, as used in the configuration and the telemetry.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RconTransportType::Tcp => "tcp",
+            RconTransportType::Process => "process",
+        }
+    }
+}
+
+/// Connection to a server, able to execute requests on it whatever the way they reach it.
+pub trait RconTransport {
+    /// Logs in to the server.
+    ///
+    /// # Returns
+    ///
+    /// Whether the login was accepted.
+    fn login(&mut self) -> impl Future<Output = Result<bool, RconError>> + Send;
+
+    /// Sends a request to the server and receives its response.
+    ///
+    /// # Parameters
+    ///
+    /// - `request`: The request to send to the server.
+    ///
+    /// # Returns
+    ///
+    /// The response from the server.
+    fn request(
+        &mut self,
+        request: &RconRequest,
+    ) -> impl Future<Output = Result<RconResponse, RconError>> + Send;
+
+    /// Closes the connection to the server.
+    fn disconnect(&mut self) -> impl Future<Output = Result<(), RconError>> + Send;
+}
+
+/// Connection to a server over one of the supported transports.
+#[derive(Debug)]
+pub enum RconConnection {
+    Tcp(ConnectedRconClient),
+    Process(ProcessRconConnection),
+}
+
+impl RconTransport for RconConnection {
+    async fn login(&mut self) -> Result<bool, RconError> {
+        match self {
+            RconConnection::Tcp(connection) => connection.login().await,
+            RconConnection::Process(connection) => connection.login().await,
+        }
+    }
+
+    async fn request(&mut self, request: &RconRequest) -> Result<RconResponse, RconError> {
+        match self {
+            RconConnection::Tcp(connection) => connection.request(request).await,
+            RconConnection::Process(connection) => connection.request(request).await,
+        }
+    }
+
+    async fn disconnect(&mut self) -> Result<(), RconError> {
+        match self {
+            RconConnection::Tcp(connection) => connection.disconnect().await,
+            RconConnection::Process(connection) => connection.disconnect().await,
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use super::ServerError;
+use std::{env, time::Duration};
+use tracing::debug;
+
+/// Default number of lines of the server output kept in memory.
+pub const DEFAULT_SERVER_LOG_BUFFER_SIZE: usize = 1000;
+
+/// Default time the server is given to stop after the `stop` command, in milliseconds.
+///
+/// Generous, as the worlds are saved before the server stops.
+pub const DEFAULT_SERVER_STOP_TIMEOUT: u64 = 60000;
+
+/// Default time the server is given to stop after each signal, in milliseconds.
+pub const DEFAULT_SERVER_KILL_TIMEOUT: u64 = 10000;
+
+/// Default delay before the first restart of a crashed server, in milliseconds.
+pub const DEFAULT_SERVER_RESTART_BASE_DELAY: u64 = 1000;
+
+/// Default maximum delay before the restart of a crashed server, in milliseconds.
+pub const DEFAULT_SERVER_RESTART_MAX_DELAY: u64 = 60000;
+
+/// Default number of consecutive crashes after which the server is no longer restarted.
+pub const DEFAULT_SERVER_RESTART_MAX_ATTEMPTS: u32 = 5;
+
+#[derive(Debug, Clone)]
+pub struct ServerConfiguration {
+    /// Command line of the server, split on whitespaces.
+    pub command: String,
+    /// Working directory of the server.
+    pub working_dir: Option<String>,
+    /// Whether the server is started along with the application.
+    pub autostart: bool,
+    /// Number of lines of the server output kept in memory.
+    pub log_buffer_size: usize,
+    /// Time the server is given to stop after the `stop` command, in milliseconds.
+    pub stop_timeout: u64,
+    /// Time the server is given to stop after each signal, in milliseconds.
+    pub kill_timeout: u64,
+    /// Whether the server is restarted when it exits unexpectedly.
+    pub restart_on_crash: bool,
+    /// Delay before the first restart of a crashed server, doubled after each crash, in
+    /// milliseconds.
+    pub restart_base_delay: u64,
+    /// Maximum delay before the restart of a crashed server, in milliseconds.
+    pub restart_max_delay: u64,
+    /// Number of consecutive crashes after which the server is no longer restarted, `0` for no
+    /// limit.
+    pub restart_max_attempts: u32,
+}
+
+impl ServerConfiguration {
+    /// Returns the configuration from environment variables.
+    ///
+    /// # Returns:
+    ///
+    /// The configuration.
+    pub fn try_new() -> Result<ServerConfiguration, ServerError> {
+        debug!("Attempting to create server configuration from environment variables...");
+
+        let command = env::var("SERVER_COMMAND")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .ok_or_else(|| ServerError::Configuration {
+                cause: String::from("Environment variable 'SERVER_COMMAND' is not set"),
+            })?;
+        let working_dir = env::var("SERVER_WORKING_DIR")
+            .ok()
+            .filter(|value| !value.is_empty());
+        let autostart = Self::parse_bool("SERVER_AUTOSTART", false)?;
+        let log_buffer_size = env::var("SERVER_LOG_BUFFER_SIZE")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<usize>())
+            .unwrap_or(Ok(DEFAULT_SERVER_LOG_BUFFER_SIZE))
+            .ok()
+            .filter(|log_buffer_size| *log_buffer_size > 0)
+            .ok_or_else(|| ServerError::Configuration {
+                cause: String::from(
+                    "Environment variable 'SERVER_LOG_BUFFER_SIZE' is not a valid positive number",
+                ),
+            })?;
+        let stop_timeout = Self::parse_millis("SERVER_STOP_TIMEOUT", DEFAULT_SERVER_STOP_TIMEOUT)?;
+        let kill_timeout = Self::parse_millis("SERVER_KILL_TIMEOUT", DEFAULT_SERVER_KILL_TIMEOUT)?;
+        let restart_on_crash = Self::parse_bool("SERVER_RESTART_ON_CRASH", true)?;
+        let restart_base_delay = Self::parse_millis(
+            "SERVER_RESTART_BASE_DELAY",
+            DEFAULT_SERVER_RESTART_BASE_DELAY,
+        )?;
+        let restart_max_delay =
+            Self::parse_millis("SERVER_RESTART_MAX_DELAY", DEFAULT_SERVER_RESTART_MAX_DELAY)?;
+        let restart_max_attempts = env::var("SERVER_RESTART_MAX_ATTEMPTS")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u32>())
+            .unwrap_or(Ok(DEFAULT_SERVER_RESTART_MAX_ATTEMPTS))
+            .map_err(|_| ServerError::Configuration {
+                cause: String::from(
+                    "Environment variable 'SERVER_RESTART_MAX_ATTEMPTS' is not a valid number",
+                ),
+            })?;
+
+        Ok(ServerConfiguration {
+            command,
+            working_dir,
+            autostart,
+            log_buffer_size,
+            stop_timeout,
+            kill_timeout,
+            restart_on_crash,
+            restart_base_delay,
+            restart_max_delay,
+            restart_max_attempts,
+        })
+    }
+
+    /// Returns whether the server supervisor is enabled, with the `SERVER_COMMAND` environment
+    /// variable.
+    pub fn is_set() -> bool {
+        env::var("SERVER_COMMAND").is_ok_and(|value| !value.trim().is_empty())
+    }
+
+    /// Returns the delay before restarting the server after a number of consecutive crashes.
+    ///
+    /// # Arguments
+    ///
+    /// * `crashes` - The number of consecutive crashes, starting at 1.
+    pub fn restart_delay(&self, crashes: u32) -> Duration {
+        let factor = 2u64.saturating_pow(crashes.saturating_sub(1));
+
+        Duration::from_millis(
+            self.restart_base_delay
+                .saturating_mul(factor)
+                .min(self.restart_max_delay),
+        )
+    }
+
+    /// Parses a duration in milliseconds from an environment variable, falling back to a default
+    /// value if the variable is not set or empty.
+    fn parse_millis(variable: &str, default: u64) -> Result<u64, ServerError> {
+        env::var(variable)
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u64>())
+            .unwrap_or(Ok(default))
+            .map_err(|_| ServerError::Configuration {
+                cause: format!("Environment variable '{variable}' is not a valid number"),
+            })
+    }
+
+    /// Parses a boolean from an environment variable, falling back to a default value if the
+    /// variable is not set or empty.
+    fn parse_bool(variable: &str, default: bool) -> Result<bool, ServerError> {
+        env::var(variable)
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<bool>())
+            .unwrap_or(Ok(default))
+            .map_err(|_| ServerError::Configuration {
+                cause: format!("Environment variable '{variable}' is not a boolean"),
+            })
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use crate::server::{
+        DEFAULT_SERVER_KILL_TIMEOUT, DEFAULT_SERVER_LOG_BUFFER_SIZE,
+        DEFAULT_SERVER_RESTART_MAX_ATTEMPTS, DEFAULT_SERVER_STOP_TIMEOUT, ServerConfiguration,
+        ServerError,
+    };
+    use serial_test::serial;
+    use std::time::Duration;
+    use temp_env::with_vars;
+    use test_case::test_case;
+
+    fn configuration() -> ServerConfiguration {
+        ServerConfiguration {
+            command: String::from("java -jar server.jar nogui"),
+            working_dir: None,
+            autostart: false,
+            log_buffer_size: 10,
+            stop_timeout: 1000,
+            kill_timeout: 1000,
+            restart_on_crash: true,
+            restart_base_delay: 100,
+            restart_max_delay: 300,
+            restart_max_attempts: 3,
+        }
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration() {
+        with_vars(
+            [
+                ("SERVER_COMMAND", Some("java -Xmx4G -jar server.jar nogui")),
+                ("SERVER_WORKING_DIR", None),
+                ("SERVER_AUTOSTART", None),
+                ("SERVER_LOG_BUFFER_SIZE", None),
+                ("SERVER_STOP_TIMEOUT", None),
+                ("SERVER_KILL_TIMEOUT", None),
+                ("SERVER_RESTART_ON_CRASH", None),
+                ("SERVER_RESTART_MAX_ATTEMPTS", None),
+            ],
+            || {
+                let configuration = ServerConfiguration::try_new().unwrap();
+                assert_eq!(configuration.command, "java -Xmx4G -jar server.jar nogui");
+                assert_eq!(configuration.working_dir, None);
+                assert!(!configuration.autostart);
+                assert_eq!(
+                    configuration.log_buffer_size,
+                    DEFAULT_SERVER_LOG_BUFFER_SIZE
+                );
+                assert_eq!(configuration.stop_timeout, DEFAULT_SERVER_STOP_TIMEOUT);
+                assert_eq!(configuration.kill_timeout, DEFAULT_SERVER_KILL_TIMEOUT);
+                assert!(configuration.restart_on_crash);
+                assert_eq!(
+                    configuration.restart_max_attempts,
+                    DEFAULT_SERVER_RESTART_MAX_ATTEMPTS
+                );
+                assert!(ServerConfiguration::is_set());
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_custom() {
+        with_vars(
+            [
+                ("SERVER_COMMAND", Some("./start.sh")),
+                ("SERVER_WORKING_DIR", Some("/srv/minecraft")),
+                ("SERVER_AUTOSTART", Some("true")),
+                ("SERVER_LOG_BUFFER_SIZE", Some("50")),
+                ("SERVER_STOP_TIMEOUT", Some("5000")),
+                ("SERVER_KILL_TIMEOUT", Some("2000")),
+                ("SERVER_RESTART_ON_CRASH", Some("false")),
+                ("SERVER_RESTART_MAX_ATTEMPTS", Some("0")),
+            ],
+            || {
+                let configuration = ServerConfiguration::try_new().unwrap();
+                assert_eq!(configuration.working_dir.as_deref(), Some("/srv/minecraft"));
+                assert!(configuration.autostart);
+                assert_eq!(configuration.log_buffer_size, 50);
+                assert_eq!(configuration.stop_timeout, 5000);
+                assert_eq!(configuration.kill_timeout, 2000);
+                assert!(!configuration.restart_on_crash);
+                assert_eq!(configuration.restart_max_attempts, 0);
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_missing_command() {
+        with_vars([("SERVER_COMMAND", Some(" "))], || {
+            assert!(!ServerConfiguration::is_set());
+            assert_eq!(
+                ServerConfiguration::try_new().unwrap_err(),
+                ServerError::Configuration {
+                    cause: String::from("Environment variable 'SERVER_COMMAND' is not set")
+                }
+            );
+        });
+    }
+
+    #[test_case("SERVER_LOG_BUFFER_SIZE", "0", "Environment variable 'SERVER_LOG_BUFFER_SIZE' is not a valid positive number"; "empty buffer")]
+    #[test_case("SERVER_STOP_TIMEOUT", "soon", "Environment variable 'SERVER_STOP_TIMEOUT' is not a valid number"; "invalid stop timeout")]
+    #[test_case("SERVER_AUTOSTART", "yes", "Environment variable 'SERVER_AUTOSTART' is not a boolean"; "invalid autostart")]
+    #[serial(rcon)]
+    fn test_get_configuration_invalid(variable: &str, value: &str, cause: &str) {
+        with_vars(
+            [
+                ("SERVER_COMMAND", Some("./start.sh")),
+                (variable, Some(value)),
+            ],
+            || {
+                assert_eq!(
+                    ServerConfiguration::try_new().unwrap_err(),
+                    ServerError::Configuration {
+                        cause: String::from(cause)
+                    }
+                );
+            },
+        );
+    }
+
+    #[test_case(1 => Duration::from_millis(100); "first crash")]
+    #[test_case(2 => Duration::from_millis(200); "second crash")]
+    #[test_case(3 => Duration::from_millis(300); "capped")]
+    #[test_case(64 => Duration::from_millis(300); "overflow")]
+    fn test_restart_delay(crashes: u32) -> Duration {
+        configuration().restart_delay(crashes)
+    }
+}
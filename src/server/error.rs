@@ -0,0 +1,28 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ServerError {
+    #[error("Invalid server configuration: {cause}")]
+    Configuration { cause: String },
+    #[error("Failed to start the Minecraft server: {cause}")]
+    Start { cause: String },
+    #[error("Failed to stop the Minecraft server: {cause}")]
+    Stop { cause: String },
+    #[error("The Minecraft server is already running")]
+    AlreadyRunning,
+    #[error("The Minecraft server is not running")]
+    NotRunning,
+}
+
+impl ServerError {
+    /// Returns the kind of the error, as used in the telemetry.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ServerError::Configuration { .. } => "configuration",
+            ServerError::Start { .. } => "start",
+            ServerError::Stop { .. } => "stop",
+            ServerError::AlreadyRunning => "already_running",
+            ServerError::NotRunning => "not_running",
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, MutexGuard},
+    time::SystemTime,
+};
+
+/// Stream of the server process a line was printed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerLogStream {
+    Stdout,
+    Stderr,
+}
+
+impl ServerLogStream {
+    /// Returns the name of the stream, as used in the API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServerLogStream::Stdout => "stdout",
+            ServerLogStream::Stderr => "stderr",
+        }
+    }
+}
+
+/// Line printed by the server process.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerLogLine {
+    pub stream: ServerLogStream,
+    pub line: String,
+    pub timestamp: SystemTime,
+}
+
+/// Ring buffer of the last lines printed by the server process, the oldest lines being dropped
+/// once it is full.
+#[derive(Debug, Clone)]
+pub struct ServerLogBuffer {
+    capacity: usize,
+    lines: Arc<Mutex<VecDeque<ServerLogLine>>>,
+}
+
+impl ServerLogBuffer {
+    /// Creates an empty buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The number of lines kept in the buffer.
+    pub fn new(capacity: usize) -> Self {
+        ServerLogBuffer {
+            capacity,
+            lines: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+        }
+    }
+
+    /// Appends a line to the buffer, dropping the oldest one if it is full.
+    pub fn push(&self, stream: ServerLogStream, line: String) {
+        let mut lines = self.lock_lines();
+        if lines.len() >= self.capacity {
+            lines.pop_front();
+        }
+
+        lines.push_back(ServerLogLine {
+            stream,
+            line,
+            timestamp: SystemTime::now(),
+        });
+    }
+
+    /// Returns the last lines of the buffer, oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - The maximum number of lines to return.
+    pub fn tail(&self, count: usize) -> Vec<ServerLogLine> {
+        let lines = self.lock_lines();
+
+        lines
+            .iter()
+            .skip(lines.len().saturating_sub(count))
+            .cloned()
+            .collect()
+    }
+
+    fn lock_lines(&self) -> MutexGuard<'_, VecDeque<ServerLogLine>> {
+        self.lines
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use crate::server::{ServerLogBuffer, ServerLogStream};
+
+    #[test]
+    fn test_tail() {
+        let logs = ServerLogBuffer::new(10);
+        logs.push(ServerLogStream::Stdout, String::from("Starting"));
+        logs.push(ServerLogStream::Stderr, String::from("Warning"));
+        logs.push(ServerLogStream::Stdout, String::from("Done"));
+
+        let lines = logs.tail(2);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].stream, ServerLogStream::Stderr);
+        assert_eq!(lines[0].line, "Warning");
+        assert_eq!(lines[1].stream, ServerLogStream::Stdout);
+        assert_eq!(lines[1].line, "Done");
+
+        assert_eq!(logs.tail(100).len(), 3);
+        assert!(logs.tail(0).is_empty());
+    }
+
+    #[test]
+    fn test_oldest_lines_dropped() {
+        let logs = ServerLogBuffer::new(3);
+        for index in 0..5 {
+            logs.push(ServerLogStream::Stdout, format!("Line {index}"));
+        }
+
+        let lines: Vec<String> = logs.tail(10).into_iter().map(|line| line.line).collect();
+        assert_eq!(lines, vec!["Line 2", "Line 3", "Line 4"]);
+    }
+}
@@ -0,0 +1,12 @@
+pub mod configuration;
+pub mod configuration_test;
+pub mod error;
+pub mod logs;
+pub mod logs_test;
+pub mod supervisor;
+pub mod supervisor_test;
+
+pub use configuration::*;
+pub use error::*;
+pub use logs::*;
+pub use supervisor::*;
@@ -0,0 +1,599 @@
+use super::{ServerConfiguration, ServerError, ServerLogBuffer, ServerLogStream};
+use crate::rcon::{CONSOLE_OUTPUT_CAPACITY, RconClient, RconError, RconRequest, RconRequestType};
+use std::{
+    process::Stdio,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, BufReader},
+    process::{Child, Command},
+    select,
+    sync::{Mutex, broadcast, mpsc, watch},
+    time::timeout,
+};
+use tracing::{Span, error, info, warn};
+
+/// State of the Minecraft server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerState {
+    /// The server is not running.
+    Stopped,
+    /// The server process is running, but the server is not ready to accept players yet.
+    Starting,
+    /// The server is ready to accept players.
+    Running,
+    /// The server is being stopped.
+    Stopping,
+    /// The server exited unexpectedly, it is restarted once the restart delay elapsed, unless it
+    /// crashed too many times in a row.
+    Crashed,
+}
+
+impl ServerState {
+    /// Returns the name of the state, as used in the API.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServerState::Stopped => "stopped",
+            ServerState::Starting => "starting",
+            ServerState::Running => "running",
+            ServerState::Stopping => "stopping",
+            ServerState::Crashed => "crashed",
+        }
+    }
+}
+
+/// Snapshot of the Minecraft server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerStatus {
+    pub state: ServerState,
+    pub pid: Option<u32>,
+    /// When the current process was started.
+    pub started_at: Option<SystemTime>,
+    /// The startup time reported by the server once ready.
+    pub startup_time: Option<Duration>,
+    /// Number of restarts after a crash since the server was started from the API.
+    pub restarts: u32,
+    /// Description of the last exit of the server, e.g. `exit status: 1`.
+    pub last_exit: Option<String>,
+    /// When the crashed server is restarted.
+    pub next_restart_at: Option<SystemTime>,
+}
+
+impl Default for ServerStatus {
+    fn default() -> Self {
+        ServerStatus {
+            state: ServerState::Stopped,
+            pid: None,
+            started_at: None,
+            startup_time: None,
+            restarts: 0,
+            last_exit: None,
+            next_restart_at: None,
+        }
+    }
+}
+
+impl ServerStatus {
+    /// Returns whether the server is running or about to be restarted.
+    pub fn is_active(&self) -> bool {
+        match self.state {
+            ServerState::Stopped => false,
+            ServerState::Crashed => self.next_restart_at.is_some(),
+            ServerState::Starting | ServerState::Running | ServerState::Stopping => true,
+        }
+    }
+}
+
+/// Returns the startup time reported by the server once ready, from a line of its output such as
+/// `[12:34:56] [Server thread/INFO]: Done (3.245s)! For help, type "help"`.
+///
+/// # Arguments
+///
+/// * `line` - The line printed by the server.
+///
+/// # Returns
+///
+/// The startup time, if the line reports that the server is ready.
+pub fn parse_startup_time(line: &str) -> Option<Duration> {
+    let (_, message) = line.split_once("]: Done (")?;
+    let (seconds, _) = message.split_once("s)!")?;
+
+    seconds
+        .parse::<f64>()
+        .ok()
+        .filter(|seconds| seconds.is_finite() && *seconds >= 0.0)
+        .map(Duration::from_secs_f64)
+}
+
+/// Signal sent to the server process when it does not stop by itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ServerSignal {
+    Terminate,
+    Kill,
+}
+
+/// Supervisor of the Minecraft server process.
+///
+/// The server is started with the configured command, its output being kept in a ring buffer and
+/// its standard input being attached to the RCON console. It is ready once it prints its `Done`
+/// line, and it is restarted with an exponential backoff when it exits unexpectedly.
+///
+/// The server is stopped with the `stop` command sent with the RCON client, so it saves the
+/// worlds, then with `SIGTERM` and `SIGKILL` if it does not stop in time.
+#[derive(Debug, Clone)]
+pub struct ServerSupervisor {
+    configuration: ServerConfiguration,
+    rcon: RconClient,
+    logs: ServerLogBuffer,
+    status: Arc<watch::Sender<ServerStatus>>,
+    /// Serializes the start, stop and restart operations.
+    operations: Arc<Mutex<()>>,
+    /// Signals to send to the server process, delivered by the task owning it.
+    signals: mpsc::UnboundedSender<ServerSignal>,
+    signals_receiver: Arc<Mutex<mpsc::UnboundedReceiver<ServerSignal>>>,
+}
+
+impl ServerSupervisor {
+    /// Creates a supervisor, the RCON console then writing to the standard input of the
+    /// supervised server instead of starting its own process.
+    ///
+    /// # Parameters
+    ///
+    /// - `configuration`: The configuration of the server.
+    /// - `rcon`: The RCON client used to stop the server.
+    pub fn new(configuration: ServerConfiguration, rcon: RconClient) -> Self {
+        rcon.console().set_supervised();
+        let (signals, signals_receiver) = mpsc::unbounded_channel();
+
+        ServerSupervisor {
+            logs: ServerLogBuffer::new(configuration.log_buffer_size),
+            configuration,
+            rcon,
+            status: Arc::new(watch::Sender::new(ServerStatus::default())),
+            operations: Arc::new(Mutex::new(())),
+            signals,
+            signals_receiver: Arc::new(Mutex::new(signals_receiver)),
+        }
+    }
+
+    /// Returns the configuration of the server.
+    pub fn configuration(&self) -> &ServerConfiguration {
+        &self.configuration
+    }
+
+    /// Returns the last lines printed by the server.
+    pub fn logs(&self) -> &ServerLogBuffer {
+        &self.logs
+    }
+
+    /// Returns a snapshot of the server.
+    pub fn status(&self) -> ServerStatus {
+        self.status.borrow().clone()
+    }
+
+    /// Starts the server.
+    ///
+    /// # Returns
+    ///
+    /// The status of the server, starting.
+    #[tracing::instrument(
+        name = "Server.Start",
+        skip(self),
+        fields(
+            app.component = "server",
+            error.type = tracing::field::Empty,
+        )
+    )]
+    pub async fn start(&self) -> Result<ServerStatus, ServerError> {
+        let _operation = self.operations.lock().await;
+        self.start_process().await.inspect_err(Self::record_error)?;
+
+        Ok(self.status())
+    }
+
+    /// Stops the server, waiting for its process to exit.
+    ///
+    /// # Returns
+    ///
+    /// The status of the server, stopped.
+    #[tracing::instrument(
+        name = "Server.Stop",
+        skip(self),
+        fields(
+            app.component = "server",
+            error.type = tracing::field::Empty,
+        )
+    )]
+    pub async fn stop(&self) -> Result<ServerStatus, ServerError> {
+        let _operation = self.operations.lock().await;
+        self.stop_process().await.inspect_err(Self::record_error)?;
+
+        Ok(self.status())
+    }
+
+    /// Stops the server if it is running, then starts it again.
+    ///
+    /// # Returns
+    ///
+    /// The status of the server, starting.
+    #[tracing::instrument(
+        name = "Server.Restart",
+        skip(self),
+        fields(
+            app.component = "server",
+            error.type = tracing::field::Empty,
+        )
+    )]
+    pub async fn restart(&self) -> Result<ServerStatus, ServerError> {
+        let _operation = self.operations.lock().await;
+        match self.stop_process().await {
+            Ok(()) | Err(ServerError::NotRunning) => {}
+            Err(error) => {
+                Self::record_error(&error);
+                return Err(error);
+            }
+        }
+        self.start_process().await.inspect_err(Self::record_error)?;
+
+        Ok(self.status())
+    }
+
+    async fn start_process(&self) -> Result<(), ServerError> {
+        if self.status.borrow().is_active() {
+            return Err(ServerError::AlreadyRunning);
+        }
+
+        let child = self.spawn().await?;
+        self.status.send_modify(|status| status.restarts = 0);
+        tokio::spawn(self.clone().supervise(child));
+
+        Ok(())
+    }
+
+    async fn stop_process(&self) -> Result<(), ServerError> {
+        let mut stopping = false;
+        self.status.send_if_modified(|status| {
+            stopping = status.is_active();
+            if stopping {
+                status.state = ServerState::Stopping;
+            }
+            stopping
+        });
+        if !stopping {
+            return Err(ServerError::NotRunning);
+        }
+
+        // Wait for the process being restarted, if any.
+        let running = self
+            .status
+            .subscribe()
+            .wait_for(|status| status.state == ServerState::Stopped || status.pid.is_some())
+            .await
+            .is_ok_and(|status| status.pid.is_some());
+
+        if running {
+            info!("Stopping the Minecraft server...");
+            let request = RconRequest::new(RconRequestType::ExecCommand, String::from("stop"));
+            let sent = match self.rcon.execute(&request).await {
+                Ok(_) => true,
+                // The server may close the connection before answering.
+                Err(RconError::Receive { .. }) | Err(RconError::Timeout { .. }) => true,
+                Err(err) => {
+                    warn!(
+                        "Failed to send the stop command to the Minecraft server: {}",
+                        err
+                    );
+                    false
+                }
+            };
+
+            if !(sent && self.wait_stopped(self.configuration.stop_timeout).await) {
+                warn!("The Minecraft server did not stop, terminating it...");
+                self.signals.send(ServerSignal::Terminate).ok();
+
+                if !self.wait_stopped(self.configuration.kill_timeout).await {
+                    warn!("The Minecraft server did not terminate, killing it...");
+                    self.signals.send(ServerSignal::Kill).ok();
+                }
+            }
+        }
+
+        // Wait for the exit to be recorded.
+        if !self.wait_stopped(self.configuration.kill_timeout).await {
+            return Err(ServerError::Stop {
+                cause: String::from("The server process did not exit after being killed"),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Waits for the server to stop.
+    ///
+    /// # Returns
+    ///
+    /// Whether the server stopped before the timeout, in milliseconds.
+    async fn wait_stopped(&self, timeout_ms: u64) -> bool {
+        let mut status = self.status.subscribe();
+
+        timeout(
+            Duration::from_millis(timeout_ms),
+            status.wait_for(|status| status.state == ServerState::Stopped),
+        )
+        .await
+        .is_ok()
+    }
+
+    /// Spawns the server process, attaching its standard input to the RCON console and
+    /// forwarding its output to the ring buffer.
+    ///
+    /// # Returns
+    ///
+    /// The server process.
+    async fn spawn(&self) -> Result<Child, ServerError> {
+        let mut arguments = self.configuration.command.split_whitespace();
+        let program = arguments.next().ok_or_else(|| ServerError::Configuration {
+            cause: String::from("Environment variable 'SERVER_COMMAND' is not set"),
+        })?;
+
+        let mut command = Command::new(program);
+        command
+            .args(arguments)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(working_dir) = &self.configuration.working_dir {
+            command.current_dir(working_dir);
+        }
+
+        let mut child = command.spawn().map_err(|err| ServerError::Start {
+            cause: format!("Failed to run '{program}': {err}"),
+        })?;
+        let pid = child.id();
+        info!(
+            "Started the Minecraft server, PID {}",
+            pid.unwrap_or_default()
+        );
+
+        let (output, _) = broadcast::channel(CONSOLE_OUTPUT_CAPACITY);
+        if let Some(stdin) = child.stdin.take() {
+            self.rcon.console().attach(stdin, output.downgrade()).await;
+        }
+        if let Some(stdout) = child.stdout.take() {
+            tokio::spawn(self.clone().forward_output(
+                stdout,
+                ServerLogStream::Stdout,
+                Some(output),
+            ));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(
+                self.clone()
+                    .forward_output(stderr, ServerLogStream::Stderr, None),
+            );
+        }
+
+        self.status.send_modify(|status| {
+            // The server may be stopped while it is being restarted.
+            if status.state != ServerState::Stopping {
+                status.state = ServerState::Starting;
+            }
+            status.pid = pid;
+            status.started_at = Some(SystemTime::now());
+            status.startup_time = None;
+            status.next_restart_at = None;
+        });
+
+        Ok(child)
+    }
+
+    /// Reads the output of the server line by line until it is closed, keeping the lines in the
+    /// ring buffer and watching for the line telling that the server is ready.
+    ///
+    /// # Parameters
+    ///
+    /// - `reader`: The standard output or error of the server.
+    /// - `stream`: The stream read.
+    /// - `console`: The RCON console the lines are forwarded to.
+    async fn forward_output<R: AsyncRead + Unpin>(
+        self,
+        reader: R,
+        stream: ServerLogStream,
+        console: Option<broadcast::Sender<Vec<u8>>>,
+    ) {
+        let mut reader = BufReader::new(reader);
+        let mut line = Vec::new();
+
+        loop {
+            line.clear();
+            match reader.read_until(b'\n', &mut line).await {
+                Ok(0) => break,
+                Ok(_) => {
+                    while line
+                        .last()
+                        .is_some_and(|byte| *byte == b'\n' || *byte == b'\r')
+                    {
+                        line.pop();
+                    }
+
+                    let text = String::from_utf8_lossy(&line).into_owned();
+                    if stream == ServerLogStream::Stdout
+                        && let Some(startup_time) = parse_startup_time(&text)
+                    {
+                        self.mark_ready(startup_time);
+                    }
+                    if let Some(console) = &console {
+                        // Nobody is waiting for a response when there is no subscriber.
+                        console.send(line.clone()).ok();
+                    }
+                    self.logs.push(stream, text);
+                }
+                Err(err) => {
+                    warn!("Failed to read the output of the Minecraft server: {}", err);
+                    break;
+                }
+            }
+        }
+    }
+
+    fn mark_ready(&self, startup_time: Duration) {
+        self.status.send_if_modified(|status| {
+            if status.state != ServerState::Starting {
+                return false;
+            }
+
+            info!(
+                "The Minecraft server is ready, started in {:.3}s",
+                startup_time.as_secs_f64()
+            );
+            status.state = ServerState::Running;
+            status.startup_time = Some(startup_time);
+            true
+        });
+    }
+
+    /// Waits for the server process to exit, restarting it after a crash.
+    ///
+    /// The consecutive crashes are counted from the last time the server was ready, the delay
+    /// before restarting it doubling after each of them.
+    async fn supervise(self, child: Child) {
+        let mut process: Result<Child, ServerError> = Ok(child);
+        let mut crashes = 0;
+
+        let mut signals = self.signals_receiver.lock().await;
+        // Forget the signals meant for a previous process.
+        while signals.try_recv().is_ok() {}
+
+        loop {
+            let exit = match &mut process {
+                Ok(child) => Self::wait(child, &mut signals).await,
+                Err(error) => error.to_string(),
+            };
+
+            let mut stopped = false;
+            let mut was_ready = false;
+            self.status.send_modify(|status| {
+                stopped = status.state == ServerState::Stopping;
+                // Cleared so that a restart failing to spawn is not seen as ready.
+                was_ready = status.startup_time.take().is_some();
+                status.state = if stopped {
+                    ServerState::Stopped
+                } else {
+                    ServerState::Crashed
+                };
+                status.pid = None;
+                status.last_exit = Some(exit.clone());
+                status.next_restart_at = None;
+            });
+            if stopped {
+                info!("The Minecraft server stopped, {}", exit);
+                return;
+            }
+
+            crashes = if was_ready { 1 } else { crashes + 1 };
+            let max_attempts = self.configuration.restart_max_attempts;
+            if !self.configuration.restart_on_crash || (max_attempts > 0 && crashes > max_attempts)
+            {
+                error!("The Minecraft server crashed, {}, giving up", exit);
+                return;
+            }
+
+            let delay = self.configuration.restart_delay(crashes);
+            warn!(
+                "The Minecraft server crashed, {}, restarting it in {}ms...",
+                exit,
+                delay.as_millis()
+            );
+            self.status.send_modify(|status| {
+                status.next_restart_at = Some(SystemTime::now() + delay);
+            });
+
+            // Restart the server after the delay, unless it is stopped meanwhile.
+            let mut status = self.status.subscribe();
+            if timeout(
+                delay,
+                status.wait_for(|status| status.state == ServerState::Stopping),
+            )
+            .await
+            .is_ok()
+            {
+                self.status.send_modify(|status| {
+                    status.state = ServerState::Stopped;
+                    status.next_restart_at = None;
+                });
+                info!("The Minecraft server stopped while waiting to be restarted");
+                return;
+            }
+
+            process = self.spawn().await;
+            self.status.send_modify(|status| status.restarts += 1);
+        }
+    }
+
+    /// Waits for the server process to exit, delivering the signals sent to it meanwhile.
+    ///
+    /// # Returns
+    ///
+    /// The description of the exit of the process.
+    async fn wait(
+        child: &mut Child,
+        signals: &mut mpsc::UnboundedReceiver<ServerSignal>,
+    ) -> String {
+        loop {
+            select! {
+                result = child.wait() => {
+                    return match result {
+                        Ok(exit_status) => exit_status.to_string(),
+                        Err(err) => err.to_string(),
+                    };
+                }
+                Some(signal) = signals.recv() => Self::send_signal(child, signal),
+            }
+        }
+    }
+
+    /// Sends a signal to the server process.
+    fn send_signal(child: &mut Child, signal: ServerSignal) {
+        let result = match signal {
+            ServerSignal::Kill => child.start_kill(),
+            ServerSignal::Terminate => Self::terminate(child),
+        };
+
+        if let Err(err) = result {
+            warn!(
+                "Failed to send {:?} to the Minecraft server: {}",
+                signal, err
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    fn terminate(child: &Child) -> std::io::Result<()> {
+        // The PID is only known until the process is reaped.
+        let Some(pid) = child.id() else {
+            return Ok(());
+        };
+
+        // SAFETY: `kill` does not access memory. The process is only reaped by `Child::wait`,
+        // which cannot run meanwhile as the child is borrowed, so its PID cannot be reused.
+        if unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    fn terminate(_child: &Child) -> std::io::Result<()> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "signals are not supported on this platform",
+        ))
+    }
+
+    fn record_error(error: &ServerError) {
+        Span::current().record("error.type", error.kind());
+    }
+}
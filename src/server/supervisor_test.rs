@@ -0,0 +1,294 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        rcon::{RconClient, RconRequest, RconRequestType},
+        server::{
+            ServerConfiguration, ServerError, ServerLogStream, ServerState, ServerStatus,
+            ServerSupervisor, parse_startup_time,
+        },
+    };
+    use serial_test::serial;
+    use std::{fs, os::unix::fs::PermissionsExt, path::PathBuf, time::Duration};
+    use temp_env::async_with_vars;
+    use test_case::test_case;
+    use tokio::time::{sleep, timeout};
+
+    /// Stand-in of a Minecraft server, stopping on the `stop` command and crashing on the `crash`
+    /// one.
+    const SERVER_SCRIPT: &str = r#"echo "[12:00:00] [Server thread/INFO]: Starting minecraft server version 1.21.4"
+echo "[12:00:00] [ServerMain/WARN]: Stand-in warning" >&2
+echo "[12:00:01] [Server thread/INFO]: Done (1.234s)! For help, type \"help\""
+while read -r line; do
+    case "$line" in
+        stop)
+            echo "[12:00:02] [Server thread/INFO]: Stopping the server"
+            exit 0
+            ;;
+        crash) exit 3 ;;
+        *) echo "[12:00:02] [Server thread/INFO]: Unknown or incomplete command" ;;
+    esac
+done
+"#;
+
+    /// Stand-in of a Minecraft server ignoring the `stop` command.
+    const STUCK_SERVER_SCRIPT: &str = r#"echo "[12:00:01] [Server thread/INFO]: Done (0.5s)! For help, type \"help\""
+while read -r line; do
+    :
+done
+"#;
+
+    /// Stand-in of a Minecraft server deleting its own executable before crashing, so that it
+    /// cannot be restarted.
+    const VANISHING_SERVER_SCRIPT: &str = r#"#!/bin/sh
+echo "[12:00:01] [Server thread/INFO]: Done (0.5s)! For help, type \"help\""
+sleep 0.2
+rm -f "$0"
+exit 3
+"#;
+
+    /// Writes a stand-in of the server.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the script, unique to the test.
+    /// * `content` - The content of the script.
+    ///
+    /// # Returns
+    ///
+    /// The path of the script and the command running it.
+    fn write_server_script(name: &str, content: &str) -> (PathBuf, String) {
+        let path = std::env::temp_dir().join(format!("{name}-{}.sh", std::process::id()));
+        fs::write(&path, content).unwrap();
+        let command = format!("sh {}", path.display());
+
+        (path, command)
+    }
+
+    /// Waits for the status of the server to match a predicate.
+    async fn wait_for_status(
+        supervisor: &ServerSupervisor,
+        predicate: impl Fn(&ServerStatus) -> bool,
+    ) -> ServerStatus {
+        timeout(Duration::from_secs(5), async {
+            loop {
+                let status = supervisor.status();
+                if predicate(&status) {
+                    return status;
+                }
+                sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Creates a supervisor from the environment variables, along with the RCON client it stops
+    /// the server with.
+    fn supervisor() -> (ServerSupervisor, RconClient) {
+        let client = RconClient::default();
+        let supervisor =
+            ServerSupervisor::new(ServerConfiguration::try_new().unwrap(), client.clone());
+
+        (supervisor, client)
+    }
+
+    #[test_case("[12:34:56] [Server thread/INFO]: Done (3.245s)! For help, type \"help\"" => Some(Duration::from_millis(3245)); "vanilla")]
+    #[test_case("[12:34:56 INFO]: Done (12.5s)! For help, type \"help\"" => Some(Duration::from_millis(12500)); "paper")]
+    #[test_case("[12:34:56] [Server thread/INFO]: <Steve> Done (1s)!" => None; "chat")]
+    #[test_case("[12:34:56] [Server thread/INFO]: Done (soon)!" => None; "invalid time")]
+    #[test_case("[12:34:56] [Server thread/INFO]: Preparing spawn area: 83%" => None; "other line")]
+    fn test_parse_startup_time(line: &str) -> Option<Duration> {
+        parse_startup_time(line)
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_start_and_stop() {
+        let (script, command) = write_server_script("server-lifecycle", SERVER_SCRIPT);
+
+        async_with_vars(
+            [
+                ("SERVER_COMMAND", Some(command.as_str())),
+                ("RCON_TRANSPORT", Some("process")),
+                ("RCON_PROCESS_RESPONSE_IDLE", Some("100")),
+            ],
+            async {
+                let (supervisor, client) = supervisor();
+
+                let status = supervisor.start().await.unwrap();
+                assert_eq!(status.state, ServerState::Starting);
+                assert!(status.pid.is_some());
+                assert_eq!(supervisor.start().await, Err(ServerError::AlreadyRunning));
+
+                let status =
+                    wait_for_status(&supervisor, |status| status.state == ServerState::Running)
+                        .await;
+                assert_eq!(status.startup_time, Some(Duration::from_millis(1234)));
+
+                // The commands are written to the console of the supervised server.
+                let request = RconRequest::new(RconRequestType::ExecCommand, "seed".into());
+                let response = client.execute(&request).await.unwrap();
+                assert!(
+                    response
+                        .response_payload
+                        .ends_with("Unknown or incomplete command")
+                );
+
+                let status = supervisor.stop().await.unwrap();
+                assert_eq!(status.state, ServerState::Stopped);
+                assert_eq!(status.pid, None);
+                assert_eq!(status.last_exit.as_deref(), Some("exit status: 0"));
+                assert_eq!(supervisor.stop().await, Err(ServerError::NotRunning));
+
+                let logs = supervisor.logs().tail(10);
+                assert!(
+                    logs.iter()
+                        .any(|line| line.stream == ServerLogStream::Stderr
+                            && line.line.ends_with("Stand-in warning"))
+                );
+                assert!(
+                    logs.iter()
+                        .any(|line| line.line.ends_with("Stopping the server"))
+                );
+            },
+        )
+        .await;
+
+        fs::remove_file(script).ok();
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_restart_after_crash() {
+        let (script, command) = write_server_script("server-crash", SERVER_SCRIPT);
+
+        async_with_vars(
+            [
+                ("SERVER_COMMAND", Some(command.as_str())),
+                ("SERVER_RESTART_BASE_DELAY", Some("50")),
+                ("RCON_TRANSPORT", Some("process")),
+                ("RCON_PROCESS_RESPONSE_IDLE", Some("100")),
+                ("RCON_RETRY_MAX_ATTEMPTS", Some("1")),
+            ],
+            async {
+                let (supervisor, client) = supervisor();
+
+                supervisor.start().await.unwrap();
+                wait_for_status(&supervisor, |status| status.state == ServerState::Running).await;
+
+                let request = RconRequest::new(RconRequestType::ExecCommand, "crash".into());
+                client.execute(&request).await.ok();
+
+                let status = wait_for_status(&supervisor, |status| {
+                    status.restarts == 1 && status.state == ServerState::Running
+                })
+                .await;
+                assert_eq!(status.last_exit.as_deref(), Some("exit status: 3"));
+
+                supervisor.stop().await.unwrap();
+            },
+        )
+        .await;
+
+        fs::remove_file(script).ok();
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_give_up_after_crashes() {
+        async_with_vars(
+            [
+                ("SERVER_COMMAND", Some("false")),
+                ("SERVER_RESTART_BASE_DELAY", Some("10")),
+                ("SERVER_RESTART_MAX_ATTEMPTS", Some("2")),
+            ],
+            async {
+                let (supervisor, _) = supervisor();
+                supervisor.start().await.unwrap();
+
+                let status = wait_for_status(&supervisor, |status| !status.is_active()).await;
+                assert_eq!(status.state, ServerState::Crashed);
+                assert_eq!(status.restarts, 2);
+                assert_eq!(status.last_exit.as_deref(), Some("exit status: 1"));
+                assert_eq!(supervisor.stop().await, Err(ServerError::NotRunning));
+
+                // The server can be started again from the API.
+                let status = supervisor.start().await.unwrap();
+                assert_eq!(status.restarts, 0);
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_give_up_after_failed_restarts() {
+        let (script, _) = write_server_script("server-vanishing", VANISHING_SERVER_SCRIPT);
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+
+        async_with_vars(
+            [
+                ("SERVER_COMMAND", script.to_str()),
+                ("SERVER_RESTART_BASE_DELAY", Some("10")),
+                ("SERVER_RESTART_MAX_ATTEMPTS", Some("2")),
+            ],
+            async {
+                let (supervisor, _) = supervisor();
+                supervisor.start().await.unwrap();
+
+                // The ready run is followed by restarts failing to spawn, which count as crashes.
+                let status = wait_for_status(&supervisor, |status| !status.is_active()).await;
+                assert_eq!(status.state, ServerState::Crashed);
+                assert_eq!(status.restarts, 2);
+                assert_eq!(status.startup_time, None);
+                assert!(status.last_exit.unwrap().contains("Failed to run"));
+            },
+        )
+        .await;
+
+        fs::remove_file(script).ok();
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_stop_with_signal() {
+        let (script, command) = write_server_script("server-stuck", STUCK_SERVER_SCRIPT);
+
+        async_with_vars(
+            [
+                ("SERVER_COMMAND", Some(command.as_str())),
+                ("SERVER_STOP_TIMEOUT", Some("200")),
+                ("RCON_TRANSPORT", Some("process")),
+                ("RCON_PROCESS_RESPONSE_IDLE", Some("100")),
+            ],
+            async {
+                let (supervisor, _) = supervisor();
+                supervisor.start().await.unwrap();
+                wait_for_status(&supervisor, |status| status.state == ServerState::Running).await;
+
+                let status = supervisor.stop().await.unwrap();
+                assert_eq!(status.state, ServerState::Stopped);
+                assert!(status.last_exit.unwrap().contains("signal: 15"));
+            },
+        )
+        .await;
+
+        fs::remove_file(script).ok();
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_start_invalid_command() {
+        async_with_vars(
+            [("SERVER_COMMAND", Some("/nonexistent/minecraft-server"))],
+            async {
+                let (supervisor, _) = supervisor();
+
+                let result = supervisor.start().await;
+                assert!(matches!(result, Err(ServerError::Start { .. })));
+                assert_eq!(supervisor.status().state, ServerState::Stopped);
+            },
+        )
+        .await;
+    }
+}
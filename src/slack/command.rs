@@ -0,0 +1,222 @@
+use super::SlackError;
+use crate::{slp::Motd, webhooks::verify_hmac_sha256_hex};
+use rocket::{FromForm, form::Form, http::RawStr};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Header giving the time Slack signed the request at, in seconds since the Unix epoch.
+pub const SLACK_TIMESTAMP_HEADER: &str = "X-Slack-Request-Timestamp";
+
+/// Header giving the signature of the request, as `v0=<hex>`.
+pub const SLACK_SIGNATURE_HEADER: &str = "X-Slack-Signature";
+
+/// Version of the signatures of Slack.
+const SLACK_SIGNATURE_VERSION: &str = "v0";
+
+/// Visibility of a reply in the channel the command was typed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlackResponseType {
+    /// Only the user who typed the command sees the reply.
+    Ephemeral,
+    /// Everyone in the channel sees the command and the reply.
+    InChannel,
+}
+
+impl SlackResponseType {
+    /// Returns the name of the response type, as sent to Slack.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SlackResponseType::Ephemeral => "ephemeral",
+            SlackResponseType::InChannel => "in_channel",
+        }
+    }
+}
+
+impl TryFrom<&str> for SlackResponseType {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "ephemeral" => Ok(SlackResponseType::Ephemeral),
+            "in_channel" => Ok(SlackResponseType::InChannel),
+            _ => Err(format!("Unknown Slack response type '{value}'")),
+        }
+    }
+}
+
+/// Slash command sent by Slack, as a form-encoded body.
+///
+/// - [Slash commands](https://api.slack.com/interactivity/slash-commands)
+#[derive(Debug, Clone, FromForm)]
+pub struct SlackCommand {
+    /// The slash command, e.g. `/mc`.
+    pub command: String,
+    /// The text following the slash command, e.g. `kick Steve griefing`.
+    #[field(default = String::new())]
+    pub text: String,
+    /// The ID of the user who typed the command.
+    #[field(default = String::new())]
+    pub user_id: String,
+    /// The name of the user who typed the command.
+    #[field(default = String::new())]
+    pub user_name: String,
+}
+
+impl SlackCommand {
+    /// Parses a slash command from the form-encoded body of the request, decoding its fields.
+    pub fn parse(body: &str) -> Result<SlackCommand, SlackError> {
+        Form::<SlackCommand>::parse_encoded(RawStr::new(body)).map_err(|errors| {
+            SlackError::InvalidCommand {
+                cause: errors.to_string(),
+            }
+        })
+    }
+
+    /// Returns the RCON command typed after the slash command.
+    ///
+    /// # Arguments
+    ///
+    /// * `allowed_commands` - The verbs of the commands that can be executed from Slack.
+    ///
+    /// # Returns
+    ///
+    /// The command, or a `SlackError::InvalidCommand` if it is blank, not allowed, or contains
+    /// control characters such as line breaks, which could smuggle other commands in.
+    pub fn rcon_command(&self, allowed_commands: &HashSet<String>) -> Result<String, SlackError> {
+        let command = self.text.trim().trim_start_matches('/');
+        if command.chars().any(char::is_control) {
+            return Err(SlackError::InvalidCommand {
+                cause: String::from("The command must be a single line"),
+            });
+        }
+
+        let Some(verb) = command.split_whitespace().next() else {
+            return Err(SlackError::InvalidCommand {
+                cause: format!(
+                    "Usage: {} <command>, with one of the commands {}",
+                    self.command,
+                    Self::allowed_list(allowed_commands)
+                ),
+            });
+        };
+
+        if !allowed_commands.contains(&verb.to_lowercase()) {
+            return Err(SlackError::InvalidCommand {
+                cause: format!(
+                    "The command '{verb}' is not allowed, use one of the commands {}",
+                    Self::allowed_list(allowed_commands)
+                ),
+            });
+        }
+
+        Ok(String::from(command))
+    }
+
+    fn allowed_list(allowed_commands: &HashSet<String>) -> String {
+        let mut allowed: Vec<&str> = allowed_commands.iter().map(String::as_str).collect();
+        allowed.sort();
+
+        allowed.join(", ")
+    }
+}
+
+/// Returns the message signed by Slack for a request.
+fn signed_message(timestamp: u64, body: &[u8]) -> Vec<u8> {
+    let mut message = format!("{SLACK_SIGNATURE_VERSION}:{timestamp}:").into_bytes();
+    message.extend_from_slice(body);
+
+    message
+}
+
+/// Signs a request the way Slack does, to replay slash commands in the tests.
+///
+/// # Returns
+///
+/// The value of the `X-Slack-Signature` header, `v0=<hex>`.
+#[cfg(test)]
+pub fn sign_slack_request(signing_secret: &str, timestamp: u64, body: &[u8]) -> String {
+    format!(
+        "{SLACK_SIGNATURE_VERSION}={}",
+        crate::webhooks::hmac_sha256_hex(
+            signing_secret.as_bytes(),
+            &signed_message(timestamp, body)
+        )
+    )
+}
+
+/// Verifies the signature of a request sent by Slack.
+///
+/// The `X-Slack-Signature` header is `v0=` followed by the hexadecimal HMAC-SHA256 of
+/// `v0:<timestamp>:<body>` with the signing secret of the app.
+///
+/// - [Verifying requests from Slack](https://api.slack.com/authentication/verifying-requests-from-slack)
+///
+/// # Arguments
+///
+/// * `signing_secret` - The signing secret of the Slack app.
+/// * `timestamp` - The value of the `X-Slack-Request-Timestamp` header.
+/// * `signature` - The value of the `X-Slack-Signature` header.
+/// * `body` - The raw body of the request.
+/// * `now` - The current time, in seconds since the Unix epoch.
+/// * `tolerance` - The maximum difference between the timestamp and the current time, in seconds.
+pub fn verify_slack_request(
+    signing_secret: &str,
+    timestamp: Option<&str>,
+    signature: Option<&str>,
+    body: &[u8],
+    now: u64,
+    tolerance: u64,
+) -> Result<(), SlackError> {
+    let (Some(timestamp), Some(signature)) = (timestamp, signature) else {
+        return Err(SlackError::InvalidSignature {
+            cause: String::from(
+                "The 'X-Slack-Request-Timestamp' and 'X-Slack-Signature' headers are required",
+            ),
+        });
+    };
+    let timestamp = timestamp
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|timestamp| timestamp.abs_diff(now) <= tolerance)
+        .ok_or_else(|| SlackError::InvalidSignature {
+            cause: format!("The timestamp is not within {tolerance} seconds of the current time"),
+        })?;
+
+    let message = signed_message(timestamp, body);
+    let valid = signature
+        .trim()
+        .strip_prefix(SLACK_SIGNATURE_VERSION)
+        .and_then(|signature| signature.strip_prefix('='))
+        .is_some_and(|signature| {
+            verify_hmac_sha256_hex(signing_secret.as_bytes(), &message, signature)
+        });
+
+    if valid {
+        Ok(())
+    } else {
+        Err(SlackError::InvalidSignature {
+            cause: String::from("The signature does not match the body"),
+        })
+    }
+}
+
+/// Formats the response of a command for Slack.
+///
+/// The `§` formatting codes are stripped, and the characters with a meaning in the Slack
+/// formatting are escaped, the response being shown as a code block.
+pub fn format_slack_text(payload: &str) -> String {
+    let text = Motd::parse(&Value::String(String::from(payload))).text;
+    let text = text.trim();
+
+    if text.is_empty() {
+        return String::from("_The command returned no output._");
+    }
+
+    let escaped = text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+
+    format!("```\n{escaped}\n```")
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod tests {
+    use crate::slack::{
+        SlackCommand, SlackError, SlackResponseType, format_slack_text, sign_slack_request,
+        verify_slack_request,
+    };
+    use std::collections::HashSet;
+    use test_case::test_case;
+
+    const NOW: u64 = 1700000000;
+    const SECRET: &str = "8f742231b10e8888abcd99yyyzzz85a5";
+    const BODY: &str = "command=%2Fmc&text=kick+Steve+griefing&user_id=U2147483697&user_name=alex";
+
+    fn allowed_commands() -> HashSet<String> {
+        HashSet::from([String::from("list"), String::from("kick")])
+    }
+
+    #[test_case("ephemeral", Some(SlackResponseType::Ephemeral) ; "ephemeral")]
+    #[test_case(" In_Channel ", Some(SlackResponseType::InChannel) ; "in channel")]
+    #[test_case("channel", None ; "unknown")]
+    fn test_response_type(value: &str, expected: Option<SlackResponseType>) {
+        assert_eq!(SlackResponseType::try_from(value).ok(), expected);
+    }
+
+    #[test]
+    fn test_parse_command() {
+        let command = SlackCommand::parse(BODY).unwrap();
+        assert_eq!(command.command, "/mc");
+        assert_eq!(command.text, "kick Steve griefing");
+        assert_eq!(command.user_id, "U2147483697");
+        assert_eq!(command.user_name, "alex");
+
+        let command = SlackCommand::parse("command=%2Fmc&channel_id=C2147483705").unwrap();
+        assert_eq!(command.text, "");
+        assert_eq!(command.user_name, "");
+    }
+
+    #[test]
+    fn test_parse_command_missing() {
+        assert!(matches!(
+            SlackCommand::parse("text=list"),
+            Err(SlackError::InvalidCommand { .. })
+        ));
+    }
+
+    #[test_case("list", Some("list") ; "allowed")]
+    #[test_case("  /KICK Steve griefing ", Some("KICK Steve griefing") ; "slash and case")]
+    #[test_case("stop", None ; "not allowed")]
+    #[test_case("listing", None ; "prefix of allowed")]
+    #[test_case("   ", None ; "blank")]
+    fn test_rcon_command(text: &str, expected: Option<&str>) {
+        let command = SlackCommand {
+            command: String::from("/mc"),
+            text: String::from(text),
+            user_id: String::new(),
+            user_name: String::new(),
+        };
+
+        match expected {
+            Some(expected) => {
+                assert_eq!(command.rcon_command(&allowed_commands()).unwrap(), expected)
+            }
+            None => {
+                let Err(SlackError::InvalidCommand { cause }) =
+                    command.rcon_command(&allowed_commands())
+                else {
+                    panic!("Expected an invalid command");
+                };
+                assert!(cause.contains("kick, list"), "{cause}");
+            }
+        }
+    }
+
+    #[test_case("list\nop Mallory" ; "line feed")]
+    #[test_case("list\r\nop Mallory" ; "carriage return")]
+    #[test_case("kick Steve\u{0}op Mallory" ; "null")]
+    fn test_rcon_command_control_characters(text: &str) {
+        let command = SlackCommand {
+            command: String::from("/mc"),
+            text: String::from(text),
+            user_id: String::new(),
+            user_name: String::new(),
+        };
+
+        assert!(matches!(
+            command.rcon_command(&allowed_commands()),
+            Err(SlackError::InvalidCommand { .. })
+        ));
+    }
+
+    #[test]
+    fn test_sign_slack_request() {
+        // Example of the Slack documentation
+        let body = "token=xyzz0WbapA4vBCDEFasx0q6G&team_id=T1DC2JH3J&team_domain=testteamnow&channel_id=G8PSS9T3V&channel_name=foobar&user_id=U2CERLKJA&user_name=roadrunner&command=%2Fwebhook-collect&text=&response_url=https%3A%2F%2Fhooks.slack.com%2Fcommands%2FT1DC2JH3J%2F397700885554%2F96rGlfmibIGlgcZRskXaIFfN&trigger_id=398738663015.47445629121.803a0bc887a14d10d2c447fce8b6703c";
+
+        assert_eq!(
+            sign_slack_request(SECRET, 1531420618, body.as_bytes()),
+            "v0=a2114d57b48eac39b9ad189dd8316235a7b4a8d21a10bd27519666489c69b503"
+        );
+    }
+
+    #[test]
+    fn test_verify_slack_request() {
+        let signature = sign_slack_request(SECRET, NOW, BODY.as_bytes());
+
+        assert_eq!(
+            verify_slack_request(
+                SECRET,
+                Some(&NOW.to_string()),
+                Some(&signature),
+                BODY.as_bytes(),
+                NOW + 10,
+                300
+            ),
+            Ok(())
+        );
+    }
+
+    #[test_case(None, Some("valid") ; "missing timestamp")]
+    #[test_case(Some("1700000000"), None ; "missing signature")]
+    #[test_case(Some("1699999000"), Some("valid") ; "expired")]
+    #[test_case(Some("now"), Some("valid") ; "invalid timestamp")]
+    #[test_case(Some("1700000000"), Some("v0=00") ; "mismatch")]
+    #[test_case(Some("1700000000"), Some("v1=00") ; "unknown version")]
+    fn test_verify_slack_request_invalid(timestamp: Option<&str>, signature: Option<&str>) {
+        // "valid" stands for the signature of the body at the current time
+        let valid = sign_slack_request(SECRET, NOW, BODY.as_bytes());
+        let signature = signature.map(|signature| match signature {
+            "valid" => valid.as_str(),
+            signature => signature,
+        });
+
+        assert!(matches!(
+            verify_slack_request(SECRET, timestamp, signature, BODY.as_bytes(), NOW, 300),
+            Err(SlackError::InvalidSignature { .. })
+        ));
+    }
+
+    #[test_case("There are 2 of a max of 20 players online: Steve, Alex", "```\nThere are 2 of a max of 20 players online: Steve, Alex\n```" ; "plain")]
+    #[test_case("§6Seed: §a[-42]", "```\nSeed: [-42]\n```" ; "formatting codes")]
+    #[test_case("Kicked <Steve> & co", "```\nKicked &lt;Steve&gt; &amp; co\n```" ; "escaped")]
+    #[test_case(" \n", "_The command returned no output._" ; "empty")]
+    fn test_format_slack_text(payload: &str, expected: &str) {
+        assert_eq!(format_slack_text(payload), expected);
+    }
+}
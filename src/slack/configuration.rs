@@ -0,0 +1,87 @@
+use super::{SlackError, SlackResponseType};
+use crate::rcon::Secret;
+use std::{collections::HashSet, env};
+use tracing::debug;
+
+/// Default commands that can be executed from Slack.
+pub const DEFAULT_SLACK_ALLOWED_COMMANDS: &str = "list,help,seed,kick";
+
+/// Default maximum difference between the timestamp of a request and the current time, in
+/// seconds, as recommended by Slack.
+pub const DEFAULT_SLACK_TIMESTAMP_TOLERANCE: u64 = 300;
+
+#[derive(Debug, Clone)]
+pub struct SlackConfiguration {
+    /// Signing secret of the Slack app, from its basic information page.
+    pub signing_secret: Secret,
+    /// Commands that can be executed from Slack, by their verb.
+    pub allowed_commands: HashSet<String>,
+    /// Maximum difference between the timestamp of a request and the current time, in seconds.
+    pub timestamp_tolerance: u64,
+    /// Visibility of the responses of the commands in the channel.
+    pub response_type: SlackResponseType,
+}
+
+impl SlackConfiguration {
+    /// Returns the configuration from environment variables.
+    ///
+    /// # Returns:
+    ///
+    /// The configuration.
+    pub fn try_new() -> Result<SlackConfiguration, SlackError> {
+        debug!("Attempting to create Slack configuration from environment variables...");
+
+        let signing_secret = Self::signing_secret().ok_or_else(|| SlackError::Configuration {
+            cause: String::from("Environment variable 'SLACK_SIGNING_SECRET' is not set"),
+        })?;
+        let allowed_commands = env::var("SLACK_ALLOWED_COMMANDS")
+            .unwrap_or_else(|_| String::from(DEFAULT_SLACK_ALLOWED_COMMANDS))
+            .split(',')
+            .map(|command| command.trim().trim_start_matches('/').to_lowercase())
+            .filter(|command| !command.is_empty())
+            .collect();
+        let timestamp_tolerance = env::var("SLACK_TIMESTAMP_TOLERANCE")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u64>())
+            .unwrap_or(Ok(DEFAULT_SLACK_TIMESTAMP_TOLERANCE))
+            .ok()
+            .filter(|timestamp_tolerance| *timestamp_tolerance > 0)
+            .ok_or_else(|| SlackError::Configuration {
+                cause: String::from(
+                    "Environment variable 'SLACK_TIMESTAMP_TOLERANCE' is not a valid positive number",
+                ),
+            })?;
+        let response_type = env::var("SLACK_RESPONSE_TYPE")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| SlackResponseType::try_from(value.as_str()))
+            .unwrap_or(Ok(SlackResponseType::Ephemeral))
+            .map_err(|_| SlackError::Configuration {
+                cause: String::from(
+                    "Environment variable 'SLACK_RESPONSE_TYPE' is not 'ephemeral' or 'in_channel'",
+                ),
+            })?;
+
+        Ok(SlackConfiguration {
+            signing_secret,
+            allowed_commands,
+            timestamp_tolerance,
+            response_type,
+        })
+    }
+
+    /// Returns whether the Slack command is enabled, with the `SLACK_SIGNING_SECRET` environment
+    /// variable.
+    pub fn is_set() -> bool {
+        Self::signing_secret().is_some()
+    }
+
+    /// Returns the signing secret from the environment variables.
+    fn signing_secret() -> Option<Secret> {
+        env::var("SLACK_SIGNING_SECRET")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .map(Secret::from)
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use crate::slack::{
+        DEFAULT_SLACK_TIMESTAMP_TOLERANCE, SlackConfiguration, SlackError, SlackResponseType,
+    };
+    use serial_test::serial;
+    use std::collections::HashSet;
+    use temp_env::with_vars;
+    use test_case::test_case;
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_not_set() {
+        with_vars([("SLACK_SIGNING_SECRET", None::<&str>)], || {
+            assert!(!SlackConfiguration::is_set());
+            assert!(matches!(
+                SlackConfiguration::try_new(),
+                Err(SlackError::Configuration { .. })
+            ));
+        });
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_defaults() {
+        with_vars(
+            [
+                ("SLACK_SIGNING_SECRET", Some("s3cr3t")),
+                ("SLACK_ALLOWED_COMMANDS", None),
+                ("SLACK_TIMESTAMP_TOLERANCE", None),
+                ("SLACK_RESPONSE_TYPE", None),
+            ],
+            || {
+                assert!(SlackConfiguration::is_set());
+
+                let configuration = SlackConfiguration::try_new().unwrap();
+                assert_eq!(configuration.signing_secret.expose(), "s3cr3t");
+                assert_eq!(
+                    configuration.allowed_commands,
+                    HashSet::from(["list", "help", "seed", "kick"].map(String::from))
+                );
+                assert_eq!(
+                    configuration.timestamp_tolerance,
+                    DEFAULT_SLACK_TIMESTAMP_TOLERANCE
+                );
+                assert_eq!(configuration.response_type, SlackResponseType::Ephemeral);
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration() {
+        with_vars(
+            [
+                ("SLACK_SIGNING_SECRET", Some("s3cr3t")),
+                ("SLACK_ALLOWED_COMMANDS", Some(" List, /WHITELIST ,,")),
+                ("SLACK_TIMESTAMP_TOLERANCE", Some("60")),
+                ("SLACK_RESPONSE_TYPE", Some("in_channel")),
+            ],
+            || {
+                let configuration = SlackConfiguration::try_new().unwrap();
+                assert_eq!(
+                    configuration.allowed_commands,
+                    HashSet::from(["list", "whitelist"].map(String::from))
+                );
+                assert_eq!(configuration.timestamp_tolerance, 60);
+                assert_eq!(configuration.response_type, SlackResponseType::InChannel);
+            },
+        );
+    }
+
+    #[test_case("SLACK_TIMESTAMP_TOLERANCE", "0" ; "zero tolerance")]
+    #[test_case("SLACK_TIMESTAMP_TOLERANCE", "five" ; "invalid tolerance")]
+    #[test_case("SLACK_RESPONSE_TYPE", "channel" ; "invalid response type")]
+    #[serial(rcon)]
+    fn test_get_configuration_invalid(name: &str, value: &str) {
+        with_vars(
+            [
+                ("SLACK_SIGNING_SECRET", Some("s3cr3t")),
+                ("SLACK_TIMESTAMP_TOLERANCE", None),
+                ("SLACK_RESPONSE_TYPE", None),
+                (name, Some(value)),
+            ],
+            || {
+                assert!(matches!(
+                    SlackConfiguration::try_new(),
+                    Err(SlackError::Configuration { .. })
+                ));
+            },
+        );
+    }
+}
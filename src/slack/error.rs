@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SlackError {
+    #[error("Invalid Slack configuration: {cause}")]
+    Configuration { cause: String },
+    #[error("Invalid Slack signature: {cause}")]
+    InvalidSignature { cause: String },
+    #[error("Invalid Slack command: {cause}")]
+    InvalidCommand { cause: String },
+}
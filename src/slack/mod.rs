@@ -0,0 +1,9 @@
+pub mod command;
+pub mod command_test;
+pub mod configuration;
+pub mod configuration_test;
+pub mod error;
+
+pub use command::*;
+pub use configuration::*;
+pub use error::*;
@@ -0,0 +1,145 @@
+use super::{
+    SLP_PING_PACKET_ID, SLP_STATUS_PACKET_ID, SlpConfiguration, SlpError, SlpPacket, SlpStatus,
+    SlpStatusResponse, read_packet,
+};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::{io::AsyncWriteExt, net::TcpStream, time::timeout};
+use tracing::{Span, debug, info, warn};
+
+#[derive(Default, Debug, Clone)]
+pub struct SlpClient;
+
+impl SlpClient {
+    /// Gets the status of the server: connects, sends the handshake and the status request, reads
+    /// the status response, then measures the latency with a ping.
+    ///
+    /// The latency is missing if the server does not answer the ping, as some proxies close the
+    /// connection right after the status response.
+    ///
+    /// # Returns:
+    ///
+    /// The status of the server.
+    #[tracing::instrument(
+        name = "Slp.Status",
+        skip(self),
+        fields(
+            app.component = "slp",
+            server.address = tracing::field::Empty,
+            server.port = tracing::field::Empty,
+            error.type = tracing::field::Empty,
+        )
+    )]
+    pub async fn status(&self) -> Result<SlpStatus, SlpError> {
+        let configuration = SlpConfiguration::try_new()?;
+
+        let span = Span::current();
+        span.record("server.address", configuration.host.as_str());
+        span.record("server.port", configuration.port);
+
+        Self::status_with(&configuration)
+            .await
+            .inspect_err(|error| {
+                span.record("error.type", error.kind());
+            })
+    }
+
+    async fn status_with(configuration: &SlpConfiguration) -> Result<SlpStatus, SlpError> {
+        let address = configuration.address();
+        let deadline = Duration::from_millis(configuration.timeout);
+
+        info!("Getting the status of '{}'...", address);
+        let started_at = Instant::now();
+        let mut stream = timeout(deadline, TcpStream::connect(&address))
+            .await
+            .map_err(|_| SlpError::ConnectTimeout {
+                elapsed_ms: started_at.elapsed().as_millis() as u64,
+            })?
+            .map_err(|err| SlpError::Connection {
+                cause: err.to_string(),
+            })?;
+
+        let remaining = deadline.saturating_sub(started_at.elapsed());
+        let status = timeout(remaining, Self::exchange(&mut stream, configuration))
+            .await
+            .map_err(|_| SlpError::Timeout {
+                elapsed_ms: started_at.elapsed().as_millis() as u64,
+            })?;
+
+        stream.shutdown().await.ok();
+        status
+    }
+
+    /// Exchanges the status and ping packets on a connected stream.
+    async fn exchange(
+        stream: &mut TcpStream,
+        configuration: &SlpConfiguration,
+    ) -> Result<SlpStatus, SlpError> {
+        let mut request = SlpPacket::handshake(
+            configuration.protocol_version,
+            &configuration.host,
+            configuration.port,
+        )
+        .to_bytes();
+        request.extend(SlpPacket::status_request().to_bytes());
+        Self::send(stream, &request).await?;
+
+        let packet = read_packet(stream).await.map_err(|err| SlpError::Receive {
+            cause: err.to_string(),
+        })?;
+        if packet.packet_id != SLP_STATUS_PACKET_ID {
+            return Err(SlpError::InvalidResponse {
+                cause: format!("unexpected packet ID {}", packet.packet_id),
+            });
+        }
+        let json = packet
+            .status_json()
+            .map_err(|err| SlpError::InvalidResponse {
+                cause: err.to_string(),
+            })?;
+        let response: SlpStatusResponse =
+            serde_json::from_str(&json).map_err(|err| SlpError::InvalidResponse {
+                cause: err.to_string(),
+            })?;
+        debug!("Received the status response: {} bytes", json.len());
+
+        let latency_ms = Self::ping(stream)
+            .await
+            .inspect_err(|error| warn!("Failed to measure the latency: {}", error))
+            .ok();
+
+        Ok(SlpStatus::new(response, latency_ms))
+    }
+
+    /// Sends a ping and waits for the pong with the same payload.
+    ///
+    /// # Returns:
+    ///
+    /// The round trip time in milliseconds.
+    async fn ping(stream: &mut TcpStream) -> Result<u64, SlpError> {
+        let payload = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or_default();
+
+        let started_at = Instant::now();
+        Self::send(stream, &SlpPacket::ping(payload).to_bytes()).await?;
+
+        let packet = read_packet(stream).await.map_err(|err| SlpError::Receive {
+            cause: err.to_string(),
+        })?;
+        let elapsed_ms = started_at.elapsed().as_millis() as u64;
+
+        match packet.pong_payload() {
+            Ok(pong) if packet.packet_id == SLP_PING_PACKET_ID && pong == payload => Ok(elapsed_ms),
+            _ => Err(SlpError::InvalidResponse {
+                cause: String::from("pong does not match the ping"),
+            }),
+        }
+    }
+
+    async fn send(stream: &mut TcpStream, bytes: &[u8]) -> Result<(), SlpError> {
+        stream.write_all(bytes).await.map_err(|err| SlpError::Send {
+            cause: err.to_string(),
+        })
+    }
+}
@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod tests {
+    use crate::slp::{
+        SLP_PING_PACKET_ID, SLP_STATUS_PACKET_ID, SlpClient, SlpError, SlpPacket, read_packet,
+        read_string, read_varint, write_string,
+    };
+    use serial_test::serial;
+    use temp_env::async_with_vars;
+    use tokio::{
+        io::AsyncWriteExt,
+        net::{TcpListener, TcpStream},
+    };
+
+    const STATUS_JSON: &str = r#"{
+        "version": { "name": "1.21.4", "protocol": 769 },
+        "players": {
+            "max": 20,
+            "online": 2,
+            "sample": [{ "name": "Alex", "id": "ec561538-f3fd-461d-aff5-086b22154bce" }]
+        },
+        "description": { "text": "§6Fake §lServer" },
+        "favicon": "data:image/png;base64,AAAA"
+    }"#;
+
+    /// Behavior of the fake Server List Ping responder after the status response.
+    #[derive(Clone, Copy)]
+    enum Pong {
+        Answer,
+        Close,
+    }
+
+    /// Starts a fake Server List Ping responder answering a single status exchange.
+    ///
+    /// # Returns
+    ///
+    /// The port of the responder.
+    async fn start_responder(status: &'static str, pong: Pong) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port().to_string();
+
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            answer(&mut stream, status, pong).await;
+        });
+
+        port
+    }
+
+    async fn answer(stream: &mut TcpStream, status: &str, pong: Pong) {
+        let handshake = read_packet(stream).await.unwrap();
+        assert_eq!(handshake.packet_id, SLP_STATUS_PACKET_ID);
+        let (protocol_version, size) = read_varint(&handshake.data).unwrap();
+        assert_eq!(protocol_version, -1);
+        let (host, _) = read_string(&handshake.data[size..]).unwrap();
+        assert_eq!(host, "127.0.0.1");
+        assert_eq!(handshake.data.last(), Some(&1));
+
+        let status_request = read_packet(stream).await.unwrap();
+        assert_eq!(status_request, SlpPacket::status_request());
+
+        let mut data = Vec::new();
+        write_string(&mut data, status);
+        let response = SlpPacket {
+            packet_id: SLP_STATUS_PACKET_ID,
+            data,
+        };
+        stream.write_all(&response.to_bytes()).await.unwrap();
+
+        match pong {
+            Pong::Answer => {
+                let ping = read_packet(stream).await.unwrap();
+                assert_eq!(ping.packet_id, SLP_PING_PACKET_ID);
+                stream.write_all(&ping.to_bytes()).await.unwrap();
+            }
+            Pong::Close => stream.shutdown().await.unwrap(),
+        }
+    }
+
+    async fn status_from(port: &str) -> Result<crate::slp::SlpStatus, SlpError> {
+        async_with_vars(
+            [
+                ("SLP_HOST", Some("127.0.0.1")),
+                ("SLP_PORT", Some(port)),
+                ("SLP_TIMEOUT", Some("1000")),
+            ],
+            SlpClient.status(),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_status() {
+        let port = start_responder(STATUS_JSON, Pong::Answer).await;
+
+        let status = status_from(&port).await.unwrap();
+
+        assert_eq!(status.version.name, "1.21.4");
+        assert_eq!(status.version.protocol, 769);
+        assert_eq!(status.players.max, 20);
+        assert_eq!(status.players.online, 2);
+        assert_eq!(status.players.sample[0].name, "Alex");
+        assert_eq!(status.motd.text, "Fake Server");
+        assert_eq!(status.motd.segments.len(), 2);
+        assert_eq!(status.motd.segments[1].color.as_deref(), Some("gold"));
+        assert!(status.motd.segments[1].bold);
+        assert_eq!(
+            status.favicon.as_deref(),
+            Some("data:image/png;base64,AAAA")
+        );
+        assert!(status.latency_ms.is_some());
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_status_without_pong() {
+        let port = start_responder(STATUS_JSON, Pong::Close).await;
+
+        let status = status_from(&port).await.unwrap();
+
+        assert_eq!(status.players.online, 2);
+        assert_eq!(status.latency_ms, None);
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_status_invalid_json() {
+        let port = start_responder("{ not json", Pong::Close).await;
+
+        let result = status_from(&port).await;
+
+        assert!(matches!(result, Err(SlpError::InvalidResponse { .. })));
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_status_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port().to_string();
+        tokio::spawn(async move {
+            // Accepts the connection but never answers.
+            let _stream = listener.accept().await.unwrap();
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        });
+
+        let result = status_from(&port).await;
+
+        assert!(matches!(result, Err(SlpError::Timeout { .. })));
+    }
+
+    #[tokio::test]
+    #[serial(rcon)]
+    async fn test_status_connection_refused() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port().to_string();
+        drop(listener);
+
+        let result = status_from(&port).await;
+
+        assert!(matches!(result, Err(SlpError::Connection { .. })));
+    }
+}
@@ -0,0 +1,86 @@
+use super::SlpError;
+use std::env;
+use tracing::debug;
+
+/// Default port of the Minecraft server.
+pub const DEFAULT_SLP_PORT: u16 = 25565;
+
+/// Default timeout of the status exchange, in milliseconds.
+pub const DEFAULT_SLP_TIMEOUT: u64 = 5000;
+
+/// Default protocol version sent in the handshake.
+///
+/// The `-1` version is the one to send when pinging a server to determine its version, the server
+/// answering with its own version whatever the one sent.
+///
+/// - [Handshake](https://minecraft.wiki/w/Java_Edition_protocol/Server_List_Ping#Handshake)
+pub const DEFAULT_SLP_PROTOCOL_VERSION: i32 = -1;
+
+#[derive(Debug, Clone)]
+pub struct SlpConfiguration {
+    pub host: String,
+    pub port: u16,
+    /// Timeout of the whole status exchange, in milliseconds.
+    pub timeout: u64,
+    /// Protocol version sent in the handshake.
+    pub protocol_version: i32,
+}
+
+impl SlpConfiguration {
+    /// Returns the configuration from environment variables.
+    ///
+    /// The host defaults to the one of the RCON server, as both are usually the same.
+    ///
+    /// # Returns:
+    ///
+    /// The configuration.
+    pub fn try_new() -> Result<SlpConfiguration, SlpError> {
+        debug!("Attempting to create Server List Ping configuration from environment variables...");
+
+        let host = env::var("SLP_HOST")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .or_else(|| env::var("RCON_HOST").ok())
+            .ok_or_else(|| SlpError::Configuration {
+                cause: String::from("Environment variable 'SLP_HOST' is not set"),
+            })?;
+        let port = env::var("SLP_PORT")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u16>())
+            .unwrap_or(Ok(DEFAULT_SLP_PORT))
+            .map_err(|_| SlpError::Configuration {
+                cause: String::from("Environment variable 'SLP_PORT' is not a valid number"),
+            })?;
+        let timeout = env::var("SLP_TIMEOUT")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u64>())
+            .unwrap_or(Ok(DEFAULT_SLP_TIMEOUT))
+            .map_err(|_| SlpError::Configuration {
+                cause: String::from("Environment variable 'SLP_TIMEOUT' is not a valid number"),
+            })?;
+        let protocol_version = env::var("SLP_PROTOCOL_VERSION")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<i32>())
+            .unwrap_or(Ok(DEFAULT_SLP_PROTOCOL_VERSION))
+            .map_err(|_| SlpError::Configuration {
+                cause: String::from(
+                    "Environment variable 'SLP_PROTOCOL_VERSION' is not a valid number",
+                ),
+            })?;
+
+        Ok(SlpConfiguration {
+            host,
+            port,
+            timeout,
+            protocol_version,
+        })
+    }
+
+    /// Returns the address of the Minecraft server, in the `<host>:<port>` format.
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+}
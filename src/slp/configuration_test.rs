@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use crate::slp::{
+        DEFAULT_SLP_PORT, DEFAULT_SLP_PROTOCOL_VERSION, DEFAULT_SLP_TIMEOUT, SlpConfiguration,
+        SlpError,
+    };
+    use serial_test::serial;
+    use temp_env::with_vars;
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration() {
+        with_vars(
+            [
+                ("SLP_HOST", None),
+                ("RCON_HOST", Some("minecraft")),
+                ("SLP_PORT", None),
+                ("SLP_TIMEOUT", None),
+                ("SLP_PROTOCOL_VERSION", None),
+            ],
+            || {
+                let configuration = SlpConfiguration::try_new().unwrap();
+                assert_eq!(configuration.host, "minecraft");
+                assert_eq!(configuration.port, DEFAULT_SLP_PORT);
+                assert_eq!(configuration.timeout, DEFAULT_SLP_TIMEOUT);
+                assert_eq!(configuration.protocol_version, DEFAULT_SLP_PROTOCOL_VERSION);
+                assert_eq!(configuration.address(), "minecraft:25565");
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_custom() {
+        with_vars(
+            [
+                ("SLP_HOST", Some("play.example.com")),
+                ("RCON_HOST", Some("minecraft")),
+                ("SLP_PORT", Some("25566")),
+                ("SLP_TIMEOUT", Some("1000")),
+                ("SLP_PROTOCOL_VERSION", Some("767")),
+            ],
+            || {
+                let configuration = SlpConfiguration::try_new().unwrap();
+                assert_eq!(configuration.host, "play.example.com");
+                assert_eq!(configuration.port, 25566);
+                assert_eq!(configuration.timeout, 1000);
+                assert_eq!(configuration.protocol_version, 767);
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_missing_host() {
+        with_vars([("SLP_HOST", None::<&str>), ("RCON_HOST", None)], || {
+            assert_eq!(
+                SlpConfiguration::try_new().unwrap_err(),
+                SlpError::Configuration {
+                    cause: String::from("Environment variable 'SLP_HOST' is not set")
+                }
+            );
+        });
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_invalid_port() {
+        with_vars(
+            [("SLP_HOST", Some("minecraft")), ("SLP_PORT", Some("65536"))],
+            || {
+                assert_eq!(
+                    SlpConfiguration::try_new().unwrap_err(),
+                    SlpError::Configuration {
+                        cause: String::from(
+                            "Environment variable 'SLP_PORT' is not a valid number"
+                        )
+                    }
+                );
+            },
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SlpError {
+    #[error("Invalid Server List Ping configuration: {cause}")]
+    Configuration { cause: String },
+    #[error("Failed to connect to the Minecraft server: {cause}")]
+    Connection { cause: String },
+    #[error("Failed to send data to the Minecraft server: {cause}")]
+    Send { cause: String },
+    #[error("Failed to receive data from the Minecraft server: {cause}")]
+    Receive { cause: String },
+    #[error("Invalid status response from the Minecraft server: {cause}")]
+    InvalidResponse { cause: String },
+    #[error("Timeout connecting to the Minecraft server, elapsed time: {elapsed_ms}ms")]
+    ConnectTimeout { elapsed_ms: u64 },
+    #[error("Timeout waiting for the Minecraft server status, elapsed time: {elapsed_ms}ms")]
+    Timeout { elapsed_ms: u64 },
+}
+
+impl SlpError {
+    /// Returns the kind of the error, as used in the telemetry.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SlpError::Configuration { .. } => "configuration",
+            SlpError::Connection { .. } => "connection",
+            SlpError::Send { .. } => "send",
+            SlpError::Receive { .. } => "receive",
+            SlpError::InvalidResponse { .. } => "invalid_response",
+            SlpError::ConnectTimeout { .. } => "connect_timeout",
+            SlpError::Timeout { .. } => "timeout",
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use thiserror::Error;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Maximum number of bytes of a VarInt.
+pub const MAX_VARINT_SIZE: usize = 5;
+
+/// Maximum size of a packet, the largest value of a 3 bytes VarInt.
+///
+/// - [Packet format](https://minecraft.wiki/w/Java_Edition_protocol/Packets#Packet_format)
+pub const MAX_SLP_PACKET_SIZE: usize = 2097151;
+
+/// ID of the handshake packet, and of the status request and response packets.
+pub const SLP_STATUS_PACKET_ID: i32 = 0x00;
+
+/// ID of the ping request and pong response packets.
+pub const SLP_PING_PACKET_ID: i32 = 0x01;
+
+/// State requested by the handshake to get the status of the server.
+const SLP_STATUS_NEXT_STATE: i32 = 1;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SlpPacketError {
+    #[error("VarInt is longer than {MAX_VARINT_SIZE} bytes")]
+    VarIntTooLong,
+    #[error("Packet is truncated")]
+    Truncated,
+    #[error("Invalid packet size: {size} bytes")]
+    InvalidSize { size: i32 },
+    #[error("Invalid string: {cause}")]
+    InvalidString { cause: String },
+    #[error("{cause}")]
+    Io { cause: String },
+}
+
+/// Packet of the Server List Ping protocol.
+///
+/// - [Server List Ping](https://minecraft.wiki/w/Java_Edition_protocol/Server_List_Ping)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlpPacket {
+    pub packet_id: i32,
+    pub data: Vec<u8>,
+}
+
+impl SlpPacket {
+    /// Creates the handshake packet, switching the connection to the status state.
+    ///
+    /// # Arguments
+    ///
+    /// * `protocol_version` - The protocol version of the client.
+    /// * `host` - The host used to connect to the server.
+    /// * `port` - The port used to connect to the server.
+    pub fn handshake(protocol_version: i32, host: &str, port: u16) -> Self {
+        let mut data = Vec::new();
+        write_varint(&mut data, protocol_version);
+        write_string(&mut data, host);
+        data.extend_from_slice(&port.to_be_bytes());
+        write_varint(&mut data, SLP_STATUS_NEXT_STATE);
+
+        SlpPacket {
+            packet_id: SLP_STATUS_PACKET_ID,
+            data,
+        }
+    }
+
+    /// Creates the status request packet.
+    pub fn status_request() -> Self {
+        SlpPacket {
+            packet_id: SLP_STATUS_PACKET_ID,
+            data: Vec::new(),
+        }
+    }
+
+    /// Creates the ping request packet, the server answering with the same payload.
+    pub fn ping(payload: i64) -> Self {
+        SlpPacket {
+            packet_id: SLP_PING_PACKET_ID,
+            data: payload.to_be_bytes().to_vec(),
+        }
+    }
+
+    /// Converts the packet to bytes, prefixed by its length.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut body = Vec::new();
+        write_varint(&mut body, self.packet_id);
+        body.extend_from_slice(&self.data);
+
+        let mut bytes = Vec::new();
+        write_varint(&mut bytes, body.len() as i32);
+        bytes.extend_from_slice(&body);
+
+        bytes
+    }
+
+    /// Reads the JSON string of a status response packet.
+    pub fn status_json(&self) -> Result<String, SlpPacketError> {
+        read_string(&self.data).map(|(json, _)| json)
+    }
+
+    /// Reads the payload of a pong response packet.
+    pub fn pong_payload(&self) -> Result<i64, SlpPacketError> {
+        self.data
+            .get(0..8)
+            .and_then(|bytes| bytes.try_into().ok())
+            .map(i64::from_be_bytes)
+            .ok_or(SlpPacketError::Truncated)
+    }
+}
+
+/// Reads a whole packet, using its length prefix to know where it ends.
+///
+/// # Arguments
+///
+/// * `reader` - The stream to read the packet from.
+pub async fn read_packet<R: AsyncRead + Unpin>(
+    reader: &mut R,
+) -> Result<SlpPacket, SlpPacketError> {
+    let mut length_bytes = Vec::with_capacity(MAX_VARINT_SIZE);
+    loop {
+        let byte = reader.read_u8().await.map_err(|err| SlpPacketError::Io {
+            cause: err.to_string(),
+        })?;
+        length_bytes.push(byte);
+
+        if byte & 0x80 == 0 {
+            break;
+        }
+        if length_bytes.len() >= MAX_VARINT_SIZE {
+            return Err(SlpPacketError::VarIntTooLong);
+        }
+    }
+
+    let (length, _) = read_varint(&length_bytes)?;
+    if length <= 0 || length as usize > MAX_SLP_PACKET_SIZE {
+        return Err(SlpPacketError::InvalidSize { size: length });
+    }
+
+    let mut body = vec![0u8; length as usize];
+    reader
+        .read_exact(&mut body)
+        .await
+        .map_err(|err| SlpPacketError::Io {
+            cause: err.to_string(),
+        })?;
+
+    let (packet_id, id_size) = read_varint(&body)?;
+
+    Ok(SlpPacket {
+        packet_id,
+        data: body[id_size..].to_vec(),
+    })
+}
+
+/// Writes a VarInt: 7 bits per byte, least significant group first, the most significant bit
+/// telling whether another byte follows. Negative values always take 5 bytes.
+///
+/// - [VarInt](https://minecraft.wiki/w/Java_Edition_protocol/Data_types#VarInt_and_VarLong)
+pub fn write_varint(buffer: &mut Vec<u8>, value: i32) {
+    let mut value = value as u32;
+    loop {
+        if value & !0x7f == 0 {
+            buffer.push(value as u8);
+            return;
+        }
+
+        buffer.push((value & 0x7f) as u8 | 0x80);
+        value >>= 7;
+    }
+}
+
+/// Reads a VarInt.
+///
+/// # Returns
+///
+/// The value and the number of bytes read.
+pub fn read_varint(bytes: &[u8]) -> Result<(i32, usize), SlpPacketError> {
+    let mut value: u32 = 0;
+
+    for (index, byte) in bytes.iter().take(MAX_VARINT_SIZE).enumerate() {
+        value |= ((byte & 0x7f) as u32) << (7 * index);
+
+        if byte & 0x80 == 0 {
+            return Ok((value as i32, index + 1));
+        }
+    }
+
+    if bytes.len() >= MAX_VARINT_SIZE {
+        Err(SlpPacketError::VarIntTooLong)
+    } else {
+        Err(SlpPacketError::Truncated)
+    }
+}
+
+/// Writes a string, prefixed by its length in bytes.
+pub fn write_string(buffer: &mut Vec<u8>, value: &str) {
+    write_varint(buffer, value.len() as i32);
+    buffer.extend_from_slice(value.as_bytes());
+}
+
+/// Reads a string, prefixed by its length in bytes.
+///
+/// # Returns
+///
+/// The string and the number of bytes read.
+pub fn read_string(bytes: &[u8]) -> Result<(String, usize), SlpPacketError> {
+    let (length, length_size) = read_varint(bytes)?;
+    if length < 0 {
+        return Err(SlpPacketError::InvalidString {
+            cause: format!("negative length {length}"),
+        });
+    }
+
+    let end = length_size + length as usize;
+    let value = bytes
+        .get(length_size..end)
+        .ok_or(SlpPacketError::Truncated)?;
+    let value = String::from_utf8(value.to_vec()).map_err(|err| SlpPacketError::InvalidString {
+        cause: err.to_string(),
+    })?;
+
+    Ok((value, end))
+}
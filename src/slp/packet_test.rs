@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use crate::slp::{
+        SlpPacket, SlpPacketError, read_packet, read_string, read_varint, write_string,
+        write_varint,
+    };
+    use test_case::test_case;
+
+    // Values from the protocol documentation.
+    #[test_case(0 => vec![0x00]; "zero")]
+    #[test_case(1 => vec![0x01]; "one")]
+    #[test_case(127 => vec![0x7f]; "one_byte_max")]
+    #[test_case(128 => vec![0x80, 0x01]; "two_bytes")]
+    #[test_case(255 => vec![0xff, 0x01]; "two_bytes_255")]
+    #[test_case(25565 => vec![0xdd, 0xc7, 0x01]; "three_bytes")]
+    #[test_case(2097151 => vec![0xff, 0xff, 0x7f]; "three_bytes_max")]
+    #[test_case(i32::MAX => vec![0xff, 0xff, 0xff, 0xff, 0x07]; "max")]
+    #[test_case(-1 => vec![0xff, 0xff, 0xff, 0xff, 0x0f]; "minus_one")]
+    #[test_case(i32::MIN => vec![0x80, 0x80, 0x80, 0x80, 0x08]; "min")]
+    fn write_varint_bytes(value: i32) -> Vec<u8> {
+        let mut buffer = Vec::new();
+        write_varint(&mut buffer, value);
+        buffer
+    }
+
+    #[test_case(&[0x00] => Ok((0, 1)); "zero")]
+    #[test_case(&[0xdd, 0xc7, 0x01, 0x42] => Ok((25565, 3)); "trailing_bytes")]
+    #[test_case(&[0xff, 0xff, 0xff, 0xff, 0x0f] => Ok((-1, 5)); "minus_one")]
+    #[test_case(&[0x80, 0x80] => Err(SlpPacketError::Truncated); "truncated")]
+    #[test_case(&[] => Err(SlpPacketError::Truncated); "empty")]
+    #[test_case(&[0xff, 0xff, 0xff, 0xff, 0xff, 0x01] => Err(SlpPacketError::VarIntTooLong); "too_long")]
+    fn read_varint_bytes(bytes: &[u8]) -> Result<(i32, usize), SlpPacketError> {
+        read_varint(bytes)
+    }
+
+    #[test]
+    fn string_round_trip() {
+        let mut buffer = Vec::new();
+        write_string(&mut buffer, "§aHello");
+
+        assert_eq!(buffer[0], 8);
+        assert_eq!(read_string(&buffer), Ok((String::from("§aHello"), 9)));
+    }
+
+    #[test]
+    fn read_string_truncated() {
+        assert_eq!(
+            read_string(&[0x05, b'a', b'b']),
+            Err(SlpPacketError::Truncated)
+        );
+    }
+
+    #[test]
+    fn handshake_packet_bytes() {
+        let bytes = SlpPacket::handshake(-1, "localhost", 25565).to_bytes();
+
+        // Length, packet ID, protocol version, then host length
+        let mut expected = vec![0x13, 0x00, 0xff, 0xff, 0xff, 0xff, 0x0f, 0x09];
+        expected.extend_from_slice(b"localhost");
+        // Port, then next state
+        expected.extend_from_slice(&[0x63, 0xdd, 0x01]);
+
+        assert_eq!(bytes, expected);
+    }
+
+    #[tokio::test]
+    async fn read_packet_round_trip() {
+        let packet = SlpPacket::ping(0x0102030405060708);
+        let bytes = packet.to_bytes();
+
+        let read = read_packet(&mut bytes.as_slice()).await.unwrap();
+
+        assert_eq!(read, packet);
+        assert_eq!(read.pong_payload(), Ok(0x0102030405060708));
+    }
+
+    #[tokio::test]
+    async fn read_packet_invalid_size() {
+        let bytes = [0x00u8];
+
+        assert_eq!(
+            read_packet(&mut bytes.as_slice()).await,
+            Err(SlpPacketError::InvalidSize { size: 0 })
+        );
+    }
+
+    #[tokio::test]
+    async fn read_packet_truncated() {
+        let bytes = [0x05u8, 0x00, 0x01];
+
+        assert!(matches!(
+            read_packet(&mut bytes.as_slice()).await,
+            Err(SlpPacketError::Io { .. })
+        ));
+    }
+}
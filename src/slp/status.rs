@@ -0,0 +1,286 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Character introducing a legacy formatting code in a text.
+///
+/// - [Formatting codes](https://minecraft.wiki/w/Formatting_codes)
+const FORMATTING_CODE_PREFIX: char = '§';
+
+/// Raw status response of the server, as sent in the JSON of the status response packet.
+///
+/// - [Status response](https://minecraft.wiki/w/Java_Edition_protocol/Server_List_Ping#Status_Response)
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct SlpStatusResponse {
+    pub version: SlpVersion,
+    #[serde(default)]
+    pub players: SlpPlayers,
+    /// The MOTD, either a plain string or a chat component.
+    #[serde(default)]
+    pub description: Value,
+    /// The icon of the server, as a `data:image/png;base64,` URL.
+    pub favicon: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct SlpVersion {
+    pub name: String,
+    pub protocol: i32,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct SlpPlayers {
+    pub max: i64,
+    pub online: i64,
+    /// Some of the online players, usually up to 12.
+    #[serde(default)]
+    pub sample: Vec<SlpPlayer>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq)]
+pub struct SlpPlayer {
+    pub name: String,
+    pub id: String,
+}
+
+/// Status of the server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlpStatus {
+    pub version: SlpVersion,
+    pub players: SlpPlayers,
+    pub motd: Motd,
+    pub favicon: Option<String>,
+    /// Round trip time of the ping, missing if the server did not answer it.
+    pub latency_ms: Option<u64>,
+}
+
+impl SlpStatus {
+    /// Creates the status from the raw status response.
+    ///
+    /// # Arguments
+    ///
+    /// * `response` - The raw status response.
+    /// * `latency_ms` - The round trip time of the ping.
+    pub fn new(response: SlpStatusResponse, latency_ms: Option<u64>) -> Self {
+        SlpStatus {
+            motd: Motd::parse(&response.description),
+            version: response.version,
+            players: response.players,
+            favicon: response.favicon,
+            latency_ms,
+        }
+    }
+}
+
+/// MOTD of the server, split in segments sharing the same formatting.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Motd {
+    /// The MOTD without formatting.
+    pub text: String,
+    pub segments: Vec<MotdSegment>,
+}
+
+/// Part of the MOTD sharing the same formatting.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MotdSegment {
+    pub text: String,
+    /// Either a color name, e.g. `dark_red`, or a `#RRGGBB` hex color.
+    pub color: Option<String>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underlined: bool,
+    pub strikethrough: bool,
+    pub obfuscated: bool,
+}
+
+/// Formatting of a segment, without its text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Style {
+    color: Option<String>,
+    bold: bool,
+    italic: bool,
+    underlined: bool,
+    strikethrough: bool,
+    obfuscated: bool,
+}
+
+impl Style {
+    /// Returns the style of a chat component, inheriting the unset properties from its parent.
+    fn inherit(&self, component: &serde_json::Map<String, Value>) -> Style {
+        let flag = |name: &str, inherited: bool| {
+            component
+                .get(name)
+                .and_then(Value::as_bool)
+                .unwrap_or(inherited)
+        };
+
+        Style {
+            color: component
+                .get("color")
+                .and_then(Value::as_str)
+                .map(String::from)
+                .or_else(|| self.color.clone()),
+            bold: flag("bold", self.bold),
+            italic: flag("italic", self.italic),
+            underlined: flag("underlined", self.underlined),
+            strikethrough: flag("strikethrough", self.strikethrough),
+            obfuscated: flag("obfuscated", self.obfuscated),
+        }
+    }
+
+    /// Applies a legacy formatting code, a color resetting the formatting.
+    fn apply_code(&mut self, code: char) {
+        match code.to_ascii_lowercase() {
+            'k' => self.obfuscated = true,
+            'l' => self.bold = true,
+            'm' => self.strikethrough = true,
+            'n' => self.underlined = true,
+            'o' => self.italic = true,
+            'r' => *self = Style::default(),
+            code => {
+                if let Some(color) = legacy_color(code) {
+                    *self = Style {
+                        color: Some(String::from(color)),
+                        ..Style::default()
+                    };
+                }
+            }
+        }
+    }
+
+    fn segment(&self, text: String) -> MotdSegment {
+        MotdSegment {
+            text,
+            color: self.color.clone(),
+            bold: self.bold,
+            italic: self.italic,
+            underlined: self.underlined,
+            strikethrough: self.strikethrough,
+            obfuscated: self.obfuscated,
+        }
+    }
+}
+
+impl Motd {
+    /// Parses the MOTD from the description of the status response.
+    ///
+    /// The description is either a plain string or a chat component, with children in `extra`.
+    /// Legacy `§` formatting codes are supported in both, as many servers still use them.
+    ///
+    /// - [Text component format](https://minecraft.wiki/w/Text_component_format)
+    ///
+    /// # Arguments
+    ///
+    /// * `description` - The description of the status response.
+    pub fn parse(description: &Value) -> Motd {
+        let mut segments = Vec::new();
+        parse_component(description, &Style::default(), &mut segments);
+
+        // Merges the adjacent segments sharing the same formatting.
+        let mut merged: Vec<MotdSegment> = Vec::new();
+        for segment in segments
+            .into_iter()
+            .filter(|segment| !segment.text.is_empty())
+        {
+            match merged.last_mut() {
+                Some(last) if Motd::same_style(last, &segment) => last.text.push_str(&segment.text),
+                _ => merged.push(segment),
+            }
+        }
+
+        Motd {
+            text: merged.iter().map(|segment| segment.text.as_str()).collect(),
+            segments: merged,
+        }
+    }
+
+    fn same_style(left: &MotdSegment, right: &MotdSegment) -> bool {
+        left.color == right.color
+            && left.bold == right.bold
+            && left.italic == right.italic
+            && left.underlined == right.underlined
+            && left.strikethrough == right.strikethrough
+            && left.obfuscated == right.obfuscated
+    }
+}
+
+/// Parses a chat component and its children into segments.
+fn parse_component(component: &Value, parent: &Style, segments: &mut Vec<MotdSegment>) {
+    match component {
+        Value::String(text) => parse_legacy(text, parent, segments),
+        Value::Array(components) => {
+            // The first component of an array is the parent of the following ones.
+            let mut components = components.iter();
+            if let Some(first) = components.next() {
+                let style = match first {
+                    Value::Object(object) => parent.inherit(object),
+                    _ => parent.clone(),
+                };
+                parse_component(first, parent, segments);
+                components.for_each(|component| parse_component(component, &style, segments));
+            }
+        }
+        Value::Object(object) => {
+            let style = parent.inherit(object);
+            if let Some(text) = object.get("text").and_then(Value::as_str) {
+                parse_legacy(text, &style, segments);
+            }
+            if let Some(Value::Array(extra)) = object.get("extra") {
+                extra
+                    .iter()
+                    .for_each(|component| parse_component(component, &style, segments));
+            }
+        }
+        Value::Number(number) => segments.push(parent.segment(number.to_string())),
+        Value::Bool(value) => segments.push(parent.segment(value.to_string())),
+        Value::Null => {}
+    }
+}
+
+/// Parses a text with legacy formatting codes into segments.
+fn parse_legacy(text: &str, parent: &Style, segments: &mut Vec<MotdSegment>) {
+    let mut style = parent.clone();
+    let mut current = String::new();
+    let mut chars = text.chars();
+
+    while let Some(char) = chars.next() {
+        if char != FORMATTING_CODE_PREFIX {
+            current.push(char);
+            continue;
+        }
+
+        let Some(code) = chars.next() else {
+            break;
+        };
+        if !current.is_empty() {
+            segments.push(style.segment(std::mem::take(&mut current)));
+        }
+        style.apply_code(code);
+    }
+
+    if !current.is_empty() {
+        segments.push(style.segment(current));
+    }
+}
+
+/// Returns the name of the color of a legacy formatting code.
+fn legacy_color(code: char) -> Option<&'static str> {
+    Some(match code {
+        '0' => "black",
+        '1' => "dark_blue",
+        '2' => "dark_green",
+        '3' => "dark_aqua",
+        '4' => "dark_red",
+        '5' => "dark_purple",
+        '6' => "gold",
+        '7' => "gray",
+        '8' => "dark_gray",
+        '9' => "blue",
+        'a' => "green",
+        'b' => "aqua",
+        'c' => "red",
+        'd' => "light_purple",
+        'e' => "yellow",
+        'f' => "white",
+        _ => return None,
+    })
+}
@@ -0,0 +1,139 @@
+#[cfg(test)]
+mod tests {
+    use crate::slp::{Motd, MotdSegment, SlpStatus, SlpStatusResponse};
+    use serde_json::json;
+
+    fn segment(text: &str, color: Option<&str>) -> MotdSegment {
+        MotdSegment {
+            text: String::from(text),
+            color: color.map(String::from),
+            ..MotdSegment::default()
+        }
+    }
+
+    #[test]
+    fn parse_plain_string() {
+        let motd = Motd::parse(&json!("A Minecraft Server"));
+
+        assert_eq!(motd.text, "A Minecraft Server");
+        assert_eq!(motd.segments, vec![segment("A Minecraft Server", None)]);
+    }
+
+    #[test]
+    fn parse_legacy_codes() {
+        let motd = Motd::parse(&json!("§aHello §l§cWorld§r!"));
+
+        assert_eq!(motd.text, "Hello World!");
+        assert_eq!(
+            motd.segments,
+            vec![
+                segment("Hello ", Some("green")),
+                segment("World", Some("red")),
+                segment("!", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_legacy_formatting_after_color() {
+        let motd = Motd::parse(&json!("§c§lBold"));
+
+        assert_eq!(
+            motd.segments,
+            vec![MotdSegment {
+                bold: true,
+                ..segment("Bold", Some("red"))
+            }]
+        );
+    }
+
+    #[test]
+    fn parse_component_with_extra() {
+        let motd = Motd::parse(&json!({
+            "text": "Welcome ",
+            "color": "gold",
+            "bold": true,
+            "extra": [
+                { "text": "to the " , "bold": false },
+                { "text": "server", "color": "#ff0000", "italic": true },
+                "!"
+            ]
+        }));
+
+        assert_eq!(motd.text, "Welcome to the server!");
+        assert_eq!(
+            motd.segments,
+            vec![
+                MotdSegment {
+                    bold: true,
+                    ..segment("Welcome ", Some("gold"))
+                },
+                segment("to the ", Some("gold")),
+                MotdSegment {
+                    bold: true,
+                    italic: true,
+                    ..segment("server", Some("#ff0000"))
+                },
+                MotdSegment {
+                    bold: true,
+                    ..segment("!", Some("gold"))
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_component_array() {
+        let motd = Motd::parse(&json!([{ "text": "A", "color": "blue" }, "B", { "text": "C" }]));
+
+        assert_eq!(motd.text, "ABC");
+        assert_eq!(motd.segments, vec![segment("ABC", Some("blue"))]);
+    }
+
+    #[test]
+    fn parse_missing_description() {
+        assert_eq!(Motd::parse(&json!(null)), Motd::default());
+    }
+
+    #[test]
+    fn status_from_response() {
+        let response: SlpStatusResponse = serde_json::from_value(json!({
+            "version": { "name": "1.21.4", "protocol": 769 },
+            "players": {
+                "max": 20,
+                "online": 1,
+                "sample": [{ "name": "Steve", "id": "4566e69f-c907-48ee-8d71-d7ba5aa00d20" }]
+            },
+            "description": { "text": "Hello" },
+            "favicon": "data:image/png;base64,AAAA",
+            "enforcesSecureChat": true
+        }))
+        .unwrap();
+
+        let status = SlpStatus::new(response, Some(12));
+
+        assert_eq!(status.version.name, "1.21.4");
+        assert_eq!(status.version.protocol, 769);
+        assert_eq!(status.players.online, 1);
+        assert_eq!(status.players.sample[0].name, "Steve");
+        assert_eq!(status.motd.text, "Hello");
+        assert_eq!(
+            status.favicon.as_deref(),
+            Some("data:image/png;base64,AAAA")
+        );
+        assert_eq!(status.latency_ms, Some(12));
+    }
+
+    #[test]
+    fn status_response_without_players() {
+        let response: SlpStatusResponse = serde_json::from_value(json!({
+            "version": { "name": "Proxy", "protocol": 47 },
+            "description": "Proxy"
+        }))
+        .unwrap();
+
+        assert_eq!(response.players.max, 0);
+        assert!(response.players.sample.is_empty());
+        assert_eq!(response.favicon, None);
+    }
+}
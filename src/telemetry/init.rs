@@ -1,89 +1,325 @@
-use opentelemetry::{global, trace::TracerProvider};
-use opentelemetry_otlp::{SpanExporter, WithExportConfig};
-use opentelemetry_sdk::{Resource, propagation::TraceContextPropagator, trace::SdkTracerProvider};
 use std::env;
-use tracing_subscriber::{EnvFilter, Layer, fmt, layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{
+    EnvFilter, Layer, Registry, fmt, layer::SubscriberExt, util::SubscriberInitExt,
+};
 
-/// Initializes logs and traces for the application.
+cfg_if::cfg_if! {
+    if #[cfg(feature = "opentelemetry")] {
+        use opentelemetry::{global, trace::TracerProvider};
+        use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
+        use super::{log_exporter, metric_exporter, span_exporter, traces_sampler};
+        use tracing::error;
+        use opentelemetry_sdk::{
+            Resource, logs::SdkLoggerProvider, metrics::SdkMeterProvider,
+            propagation::TraceContextPropagator, trace::SdkTracerProvider,
+        };
+    }
+}
+
+/// Format of the logs displayed on stdout.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One JSON object per line, with the context of the current span, for log collectors.
+    #[default]
+    Json,
+    /// Human-readable multi-line logs, for development.
+    Pretty,
+}
+
+impl TryFrom<&str> for LogFormat {
+    type Error = String;
+
+    /// Parses a log format from its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The name of the format, `json` or `pretty`.
+    ///
+    /// # Returns
+    ///
+    /// A `LogFormat` if the parsing was successful, otherwise a `String`.
+    fn try_from(value: &str) -> Result<LogFormat, Self::Error> {
+        match value.trim().to_lowercase().as_str() {
+            "json" => Ok(LogFormat::Json),
+            "pretty" => Ok(LogFormat::Pretty),
+            _ => Err(format!("Unknown log format '{value}'")),
+        }
+    }
+}
+
+/// Telemetry providers to shut down gracefully when the application stops.
+#[derive(Default)]
+pub struct Telemetry {
+    /// The OTLP tracer provider, if OTLP traces are enabled.
+    #[cfg(feature = "opentelemetry")]
+    pub tracer_provider: Option<SdkTracerProvider>,
+    /// The OTLP meter provider, if OTLP metrics are enabled.
+    #[cfg(feature = "opentelemetry")]
+    pub meter_provider: Option<SdkMeterProvider>,
+    /// The OTLP logger provider, if OTLP logs are enabled.
+    #[cfg(feature = "opentelemetry")]
+    pub logger_provider: Option<SdkLoggerProvider>,
+}
+
+#[cfg(feature = "opentelemetry")]
+impl Telemetry {
+    /// Flushes and shuts down the enabled providers.
+    ///
+    /// The errors are printed on stderr, as the log subscriber may already be gone.
+    pub fn shutdown(self) {
+        if let Some(provider) = self.tracer_provider
+            && let Err(err) = provider.shutdown()
+        {
+            eprintln!("Failed to shutdown tracer provider: {err:?}");
+        }
+        if let Some(provider) = self.meter_provider
+            && let Err(err) = provider.shutdown()
+        {
+            eprintln!("Failed to shutdown meter provider: {err:?}");
+        }
+        if let Some(provider) = self.logger_provider
+            && let Err(err) = provider.shutdown()
+        {
+            eprintln!("Failed to shutdown logger provider: {err:?}");
+        }
+    }
+}
+
+/// Initializes logs, traces and metrics for the application.
 ///
-/// - Logs will be displayed in JSON format on stdout.
-/// - Traces will be pushed to the provided OTLP endpoint.
+/// - Logs will be displayed on stdout, in JSON format by default.
+/// - Traces, metrics and logs will be pushed to the OTLP endpoint, when the `opentelemetry`
+///   feature is enabled. The three signals share the same resource.
 ///
 /// # Environment Variables:
 ///
 /// - `RUST_LOG`: Configures the log level (e.g., "info", "debug", "error"). Defaults to "info" if
+///   not set. Also applies to the logs exported over OTLP.
+/// - `LOG_FORMAT`: Configures the format of the logs, `json` or `pretty`. Defaults to `json` if
 ///   not set.
 /// - `RUST_TRACES`: Configures the trace level (e.g., "info", "debug", "error"). Defaults to "info"
-///   if not set. Traces will only be exported if the `OTEL_EXPORTER_OTLP_ENDPOINT` environment
-///   variable is set.
-/// - `OTEL_EXPORTER_OTLP_ENDPOINT`: Enables OTLP tracing to the specified endpoint. If not set,
-///   traces will not be exported.
-/// - `OTEL_SERVICE_NAME`: Specifies the service name for OTLP traces. Defaults to the crate name.
+///   if not set.
+/// - `OTEL_EXPORTER_OTLP_ENDPOINT`: Enables the OTLP export of all the signals to the specified
+///   endpoint. If not set, nothing is exported.
+/// - `OTEL_EXPORTER_OTLP_TRACES_ENDPOINT`, `OTEL_EXPORTER_OTLP_METRICS_ENDPOINT`,
+///   `OTEL_EXPORTER_OTLP_LOGS_ENDPOINT`: Enable the OTLP export of a single signal, overriding
+///   the endpoint above.
+/// - `OTEL_TRACES_EXPORTER`, `OTEL_METRICS_EXPORTER`, `OTEL_LOGS_EXPORTER`: Disable the export of
+///   a signal when set to `none`.
+/// - `OTEL_METRIC_EXPORT_INTERVAL`: Interval between two exports of the metrics, in milliseconds.
+///   Defaults to 60000.
+/// - `OTEL_EXPORTER_OTLP_PROTOCOL`: The transport of the exports, `grpc` or `http/protobuf`.
+///   Defaults to `grpc`. Can be set per signal, like the endpoint.
+/// - `OTEL_EXPORTER_OTLP_HEADERS`: Headers sent with the exports, e.g. for authentication, in the
+///   `key1=value1,key2=value2` format. Can be set per signal, like the endpoint.
+/// - `OTEL_EXPORTER_OTLP_CERTIFICATE`: Path to a PEM CA certificate to trust, in addition to the
+///   public ones. Can be set per signal, like the endpoint.
+/// - `OTEL_TRACES_SAMPLER`, `OTEL_TRACES_SAMPLER_ARG`: The sampler of the traces and its ratio.
+///   Defaults to `parentbased_always_on`.
+/// - `OTEL_SERVICE_NAME`: Specifies the service name of the exported signals. Defaults to the
+///   crate name.
+/// - `OTEL_RESOURCE_ATTRIBUTES`: Extra attributes of the exported signals, in the
+///   `key1=value1,key2=value2` format.
+///
+/// A signal that cannot be exported because of its configuration is logged as an error, the
+/// application starting without exporting it.
 ///
 /// # Returns:
 ///
-/// An optional `SdkTracerProvider` instance if OTLP tracing is enabled, otherwise `None`. To be
-/// used for graceful shutdown of the tracer provider when OTLP tracing is enabled.
-/// Failing to properly shut down the tracer provider may result in lost traces.
+/// The telemetry providers, to be used for graceful shutdown when the OTLP export is enabled.
+/// Failing to properly shut down the providers may result in lost traces, metrics and logs.
 #[allow(unused)]
-pub fn init_telemetry() -> Option<SdkTracerProvider> {
+pub fn init_telemetry() -> Telemetry {
     // Configure the environment filter from the value of the `RUST_LOG` environment variable.
     let log_filter = EnvFilter::try_from_env("RUST_LOG").unwrap_or_else(|_| EnvFilter::new("info"));
-    let trace_filter =
-        EnvFilter::try_from_env("RUST_TRACES").unwrap_or_else(|_| EnvFilter::new("info"));
-
-    // Configure the formatted layer to output logs in JSON format with additional context.
-    let log_layer = fmt::layer()
-        .json()
-        .with_current_span(true)
-        .with_span_list(true)
-        .with_file(true)
-        .with_line_number(true)
-        .with_filter(log_filter);
-
-    // Check if the `OTEL_EXPORTER_OTLP_ENDPOINT` environment variable is set to determine if OTLP
-    // tracing should be enabled.
-    if let Ok(endpoint) = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT") {
-        // Endpoint is set, so we will initialize OTLP tracing.
-        global::set_text_map_propagator(TraceContextPropagator::new());
-
-        // Initialize the OTLP exporter and tracer provider.
-        let exporter = SpanExporter::builder()
-            // Enable gRPC support.
-            .with_tonic()
-            // Use the provided endpoint.
-            .with_endpoint(endpoint)
-            .build()
-            .expect("Failed to build OTLP exporter");
-        let provider = SdkTracerProvider::builder()
-            .with_batch_exporter(exporter)
-            .with_resource(
-                Resource::builder()
-                    .with_service_name(
-                        env::var("OTEL_SERVICE_NAME")
-                            .unwrap_or_else(|_| String::from(env!("CARGO_PKG_NAME"))),
-                    )
-                    .build(),
-            )
-            .build();
-        let tracer = provider.tracer(env!("CARGO_PKG_NAME"));
-
-        let otel_layer = tracing_opentelemetry::layer()
-            .with_tracer(tracer)
-            .with_filter(trace_filter);
-
-        // Initialize the tracing subscriber with both the OTLP layer and the formatted layer for
-        // stdout.
-        tracing_subscriber::registry()
-            .with(log_layer)
-            .with(otel_layer)
-            .init();
-
-        Some(provider)
-    } else {
-        // Initialize the tracing subscriber with only the formatted layer for stdout.
-        tracing_subscriber::registry().with(log_layer).init();
-
-        None
+
+    // The subscriber is not installed yet, the error can only be printed.
+    let log_format = env::var("LOG_FORMAT")
+        .ok()
+        .filter(|value| !value.is_empty())
+        .map(|value| LogFormat::try_from(value.as_str()))
+        .unwrap_or(Ok(LogFormat::default()))
+        .unwrap_or_else(|err| {
+            eprintln!("{err}, falling back to the JSON format");
+            LogFormat::default()
+        });
+
+    // Configure the formatted layer to output logs with additional context.
+    let log_layer: Box<dyn Layer<Registry> + Send + Sync> = match log_format {
+        LogFormat::Json => fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .with_file(true)
+            .with_line_number(true)
+            .with_filter(log_filter)
+            .boxed(),
+        LogFormat::Pretty => fmt::layer()
+            .pretty()
+            .with_file(true)
+            .with_line_number(true)
+            .with_filter(log_filter)
+            .boxed(),
+    };
+    let subscriber = tracing_subscriber::registry().with(log_layer);
+
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "opentelemetry")] {
+            // A misconfigured signal is not exported, the errors are logged once the subscriber
+            // is installed.
+            let mut errors: Vec<String> = Vec::new();
+            let resource = init_resource();
+            let tracer_provider = init_signal("TRACES", &mut errors, || {
+                init_tracer_provider(resource.clone())
+            });
+            let meter_provider = init_signal("METRICS", &mut errors, || {
+                init_meter_provider(resource.clone())
+            });
+            let logger_provider =
+                init_signal("LOGS", &mut errors, || init_logger_provider(resource.clone()));
+
+            let otel_layer = tracer_provider.as_ref().map(|provider| {
+                let trace_filter = EnvFilter::try_from_env("RUST_TRACES")
+                    .unwrap_or_else(|_| EnvFilter::new("info"));
+
+                tracing_opentelemetry::layer()
+                    .with_tracer(provider.tracer(env!("CARGO_PKG_NAME")))
+                    .with_filter(trace_filter)
+            });
+            let otel_log_layer = logger_provider.as_ref().map(|provider| {
+                // The events of the exporter itself are not exported, as they would feed
+                // themselves.
+                let export_filter = ["h2", "hyper", "tonic", "tower", "opentelemetry"].iter().fold(
+                    EnvFilter::try_from_env("RUST_LOG").unwrap_or_else(|_| EnvFilter::new("info")),
+                    |filter, target| filter.add_directive(format!("{target}=off").parse().unwrap()),
+                );
+
+                OpenTelemetryTracingBridge::new(provider).with_filter(export_filter)
+            });
+
+            // Initialize the tracing subscriber with the OTLP layers, if any, and the formatted
+            // layer for stdout.
+            subscriber.with(otel_layer).with(otel_log_layer).init();
+
+            for err in errors {
+                error!("{err}");
+            }
+
+            Telemetry {
+                tracer_provider,
+                meter_provider,
+                logger_provider,
+            }
+        } else {
+            // Initialize the tracing subscriber with only the formatted layer for stdout.
+            subscriber.init();
+
+            Telemetry::default()
+        }
+    }
+}
+
+/// Returns `true` if a signal must be exported over OTLP.
+///
+/// # Arguments
+///
+/// * `signal` - The signal, as named in the environment variables: `TRACES`, `METRICS` or `LOGS`.
+#[cfg(feature = "opentelemetry")]
+pub fn is_otlp_signal_enabled(signal: &str) -> bool {
+    let has_endpoint = env::var("OTEL_EXPORTER_OTLP_ENDPOINT").is_ok()
+        || env::var(format!("OTEL_EXPORTER_OTLP_{signal}_ENDPOINT")).is_ok();
+    let is_disabled = env::var(format!("OTEL_{signal}_EXPORTER"))
+        .is_ok_and(|exporter| exporter.trim().eq_ignore_ascii_case("none"));
+
+    has_endpoint && !is_disabled
+}
+
+/// Initializes the provider of a signal if it must be exported.
+///
+/// # Arguments
+///
+/// * `signal` - The signal, as named in the environment variables: `TRACES`, `METRICS` or `LOGS`.
+/// * `errors` - The errors to log once the subscriber is installed, the signal not being exported
+///   when its provider cannot be initialized.
+/// * `init` - Initializes the provider.
+#[cfg(feature = "opentelemetry")]
+fn init_signal<T>(
+    signal: &str,
+    errors: &mut Vec<String>,
+    init: impl FnOnce() -> Result<T, String>,
+) -> Option<T> {
+    if !is_otlp_signal_enabled(signal) {
+        return None;
     }
+
+    init()
+        .inspect_err(|err| {
+            errors.push(format!(
+                "{err}, {} will not be exported",
+                signal.to_lowercase()
+            ))
+        })
+        .ok()
+}
+
+/// Returns the resource describing the application, shared by all the signals.
+///
+/// The attributes of the `OTEL_RESOURCE_ATTRIBUTES` environment variable are added to the
+/// resource, the service name coming from `OTEL_SERVICE_NAME`.
+#[cfg(feature = "opentelemetry")]
+pub fn init_resource() -> Resource {
+    Resource::builder()
+        .with_service_name(
+            env::var("OTEL_SERVICE_NAME").unwrap_or_else(|_| String::from(env!("CARGO_PKG_NAME"))),
+        )
+        .build()
+}
+
+/// Initializes the OTLP exporter and tracer provider.
+///
+/// # Arguments
+///
+/// * `resource` - The resource describing the application.
+#[cfg(feature = "opentelemetry")]
+fn init_tracer_provider(resource: Resource) -> Result<SdkTracerProvider, String> {
+    let sampler = traces_sampler()?;
+    let exporter = span_exporter()?;
+
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(sampler)
+        .with_resource(resource)
+        .build())
+}
+
+/// Initializes the OTLP exporter and meter provider, and makes it the global meter provider used
+/// by the instruments of the application.
+///
+/// # Arguments
+///
+/// * `resource` - The resource describing the application.
+#[cfg(feature = "opentelemetry")]
+fn init_meter_provider(resource: Resource) -> Result<SdkMeterProvider, String> {
+    let provider = SdkMeterProvider::builder()
+        .with_periodic_exporter(metric_exporter()?)
+        .with_resource(resource)
+        .build();
+    global::set_meter_provider(provider.clone());
+
+    Ok(provider)
+}
+
+/// Initializes the OTLP exporter and logger provider.
+///
+/// # Arguments
+///
+/// * `resource` - The resource describing the application.
+#[cfg(feature = "opentelemetry")]
+fn init_logger_provider(resource: Resource) -> Result<SdkLoggerProvider, String> {
+    Ok(SdkLoggerProvider::builder()
+        .with_batch_exporter(log_exporter()?)
+        .with_resource(resource)
+        .build())
 }
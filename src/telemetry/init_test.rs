@@ -0,0 +1,34 @@
+#[cfg(test)]
+mod tests {
+    use crate::telemetry::LogFormat;
+    use test_case::test_case;
+
+    #[test_case("json" => Ok(LogFormat::Json); "json")]
+    #[test_case("pretty" => Ok(LogFormat::Pretty); "pretty")]
+    #[test_case(" Pretty " => Ok(LogFormat::Pretty); "pretty_untrimmed")]
+    #[test_case("xml" => matches Err(_); "unknown")]
+    fn try_from_str_to_log_format(value: &str) -> Result<LogFormat, String> {
+        LogFormat::try_from(value)
+    }
+
+    #[cfg(feature = "opentelemetry")]
+    #[test_case(None, None, None => false; "no_endpoint")]
+    #[test_case(Some("http://localhost:4317"), None, None => true; "endpoint")]
+    #[test_case(None, Some("http://localhost:4317"), None => true; "signal_endpoint")]
+    #[test_case(Some("http://localhost:4317"), None, Some("none") => false; "disabled")]
+    #[test_case(Some("http://localhost:4317"), None, Some("otlp") => true; "otlp_exporter")]
+    fn is_otlp_signal_enabled(
+        endpoint: Option<&str>,
+        metrics_endpoint: Option<&str>,
+        metrics_exporter: Option<&str>,
+    ) -> bool {
+        temp_env::with_vars(
+            [
+                ("OTEL_EXPORTER_OTLP_ENDPOINT", endpoint),
+                ("OTEL_EXPORTER_OTLP_METRICS_ENDPOINT", metrics_endpoint),
+                ("OTEL_METRICS_EXPORTER", metrics_exporter),
+            ],
+            || crate::telemetry::is_otlp_signal_enabled("METRICS"),
+        )
+    }
+}
@@ -0,0 +1,53 @@
+use crate::rcon::RconError;
+use opentelemetry::{
+    KeyValue, global,
+    metrics::{Counter, Histogram},
+};
+use std::{sync::LazyLock, time::Duration};
+
+/// Duration of the RCON commands, by target and error type.
+///
+/// The instruments are created from the global meter provider, which must be set beforehand to
+/// export them: the instruments of a missing provider do nothing.
+pub static RCON_COMMAND_DURATION: LazyLock<Histogram<f64>> = LazyLock::new(|| {
+    global::meter(env!("CARGO_PKG_NAME"))
+        .f64_histogram("rcon.client.command.duration")
+        .with_unit("s")
+        .with_description("Duration of the RCON commands")
+        .build()
+});
+
+/// Number of failed RCON commands, by target and error type.
+pub static RCON_COMMAND_ERRORS: LazyLock<Counter<u64>> = LazyLock::new(|| {
+    global::meter(env!("CARGO_PKG_NAME"))
+        .u64_counter("rcon.client.command.errors")
+        .with_description("Number of failed RCON commands")
+        .build()
+});
+
+/// Duration of the HTTP requests, by method, route and status code.
+pub static HTTP_SERVER_REQUEST_DURATION: LazyLock<Histogram<f64>> = LazyLock::new(|| {
+    global::meter(env!("CARGO_PKG_NAME"))
+        .f64_histogram("http.server.request.duration")
+        .with_unit("s")
+        .with_description("Duration of the HTTP requests")
+        .build()
+});
+
+/// Records the outcome of an RCON command.
+///
+/// # Arguments
+///
+/// * `target` - The address of the RCON server.
+/// * `elapsed` - The duration of the command.
+/// * `error` - The error of the command, if it failed.
+pub fn record_rcon_command(target: &str, elapsed: Duration, error: Option<&RconError>) {
+    let mut attributes = vec![KeyValue::new("server.address", String::from(target))];
+
+    if let Some(error) = error {
+        attributes.push(KeyValue::new("error.type", error.kind()));
+        RCON_COMMAND_ERRORS.add(1, &attributes);
+    }
+
+    RCON_COMMAND_DURATION.record(elapsed.as_secs_f64(), &attributes);
+}
@@ -1,16 +1,24 @@
+mod init;
+mod init_test;
+mod request_id;
+mod request_id_test;
 mod request_span;
 mod request_tracing_guard;
 
+#[allow(unused)]
+pub use init::*;
+pub use request_id::*;
 pub use request_span::*;
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "opentelemetry")] {
-        mod init;
+        mod metrics;
+        mod otlp;
+        mod otlp_test;
         mod request_tracing_fairing;
 
+        pub use metrics::*;
+        pub use otlp::*;
         pub use request_tracing_fairing::*;
-
-        #[allow(unused)]
-        pub use init::*;
     }
 }
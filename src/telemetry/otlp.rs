@@ -0,0 +1,200 @@
+use opentelemetry_otlp::{
+    LogExporter, MetricExporter, SpanExporter, WithHttpConfig, WithTonicConfig,
+    tonic_types::transport::{Certificate, ClientTlsConfig},
+};
+use opentelemetry_sdk::trace::Sampler;
+use std::{env, fs, time::Duration};
+
+/// Default timeout of the OTLP exports, in milliseconds, as defined by the specification.
+const DEFAULT_OTLP_TIMEOUT: u64 = 10000;
+
+/// Transport protocol of the OTLP exporters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OtlpProtocol {
+    /// Protobuf over gRPC, on port 4317 by default.
+    #[default]
+    Grpc,
+    /// Protobuf over HTTP, on port 4318 by default.
+    HttpProtobuf,
+}
+
+impl TryFrom<&str> for OtlpProtocol {
+    type Error = String;
+
+    /// Parses an OTLP protocol from its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The name of the protocol, `grpc` or `http/protobuf`.
+    ///
+    /// # Returns
+    ///
+    /// An `OtlpProtocol` if the parsing was successful, otherwise a `String`.
+    fn try_from(value: &str) -> Result<OtlpProtocol, Self::Error> {
+        match value.trim().to_lowercase().as_str() {
+            "grpc" => Ok(OtlpProtocol::Grpc),
+            "http/protobuf" => Ok(OtlpProtocol::HttpProtobuf),
+            _ => Err(format!("Unknown OTLP protocol '{value}'")),
+        }
+    }
+}
+
+/// Returns the value of an OTLP exporter environment variable for a signal, falling back to the
+/// value shared by all the signals.
+///
+/// # Arguments
+///
+/// * `signal` - The signal, as named in the environment variables: `TRACES`, `METRICS` or `LOGS`.
+/// * `name` - The name of the setting, e.g. `PROTOCOL` for `OTEL_EXPORTER_OTLP_PROTOCOL`.
+pub fn otlp_variable(signal: &str, name: &str) -> Option<String> {
+    env::var(format!("OTEL_EXPORTER_OTLP_{signal}_{name}"))
+        .or_else(|_| env::var(format!("OTEL_EXPORTER_OTLP_{name}")))
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+}
+
+/// Returns the transport protocol of a signal, from the `OTEL_EXPORTER_OTLP_PROTOCOL` environment
+/// variables.
+pub fn otlp_protocol(signal: &str) -> Result<OtlpProtocol, String> {
+    otlp_variable(signal, "PROTOCOL")
+        .map(|value| OtlpProtocol::try_from(value.as_str()))
+        .unwrap_or(Ok(OtlpProtocol::default()))
+}
+
+/// Returns the sampler of the traces, from the `OTEL_TRACES_SAMPLER` and `OTEL_TRACES_SAMPLER_ARG`
+/// environment variables.
+///
+/// The traces are sampled when their parent is by default, as the requests may come from a proxy
+/// that already made the sampling decision.
+pub fn traces_sampler() -> Result<Sampler, String> {
+    let name = env::var("OTEL_TRACES_SAMPLER")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| String::from("parentbased_always_on"));
+    let ratio = || {
+        env::var("OTEL_TRACES_SAMPLER_ARG")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .map(|value| value.trim().parse::<f64>())
+            .unwrap_or(Ok(1.0))
+            .ok()
+            .filter(|ratio| (0.0..=1.0).contains(ratio))
+            .ok_or_else(|| {
+                String::from(
+                    "Environment variable 'OTEL_TRACES_SAMPLER_ARG' is not a valid ratio between 0 and 1",
+                )
+            })
+    };
+
+    match name.trim().to_lowercase().as_str() {
+        "always_on" => Ok(Sampler::AlwaysOn),
+        "always_off" => Ok(Sampler::AlwaysOff),
+        "traceidratio" => Ok(Sampler::TraceIdRatioBased(ratio()?)),
+        "parentbased_always_on" => Ok(Sampler::ParentBased(Box::new(Sampler::AlwaysOn))),
+        "parentbased_always_off" => Ok(Sampler::ParentBased(Box::new(Sampler::AlwaysOff))),
+        "parentbased_traceidratio" => Ok(Sampler::ParentBased(Box::new(
+            Sampler::TraceIdRatioBased(ratio()?),
+        ))),
+        _ => Err(format!("Unknown traces sampler '{name}'")),
+    }
+}
+
+/// Returns the TLS configuration of the gRPC transport, trusting the CA certificate from the
+/// `OTEL_EXPORTER_OTLP_CERTIFICATE` environment variables in addition to the public ones.
+///
+/// No configuration is needed for plain-text endpoints without certificate.
+fn tonic_tls_config(signal: &str) -> Result<Option<ClientTlsConfig>, String> {
+    let certificate = read_certificate(signal)?;
+    let is_https = otlp_variable(signal, "ENDPOINT")
+        .is_some_and(|endpoint| endpoint.trim().to_lowercase().starts_with("https://"));
+
+    if certificate.is_none() && !is_https {
+        return Ok(None);
+    }
+
+    let tls_config = ClientTlsConfig::new().with_enabled_roots();
+    Ok(Some(match certificate {
+        Some(certificate) => tls_config.ca_certificate(Certificate::from_pem(certificate)),
+        None => tls_config,
+    }))
+}
+
+/// Returns the client of the HTTP transport, trusting the CA certificate from the
+/// `OTEL_EXPORTER_OTLP_CERTIFICATE` environment variables in addition to the public ones.
+///
+/// The default client of the exporter is used when there is no certificate.
+fn http_client(signal: &str) -> Result<Option<reqwest::blocking::Client>, String> {
+    let Some(certificate) = read_certificate(signal)? else {
+        return Ok(None);
+    };
+    let certificate = reqwest::Certificate::from_pem(&certificate)
+        .map_err(|err| format!("Invalid OTLP CA certificate: {err}"))?;
+    let timeout = otlp_variable(signal, "TIMEOUT")
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_OTLP_TIMEOUT);
+
+    // The blocking client starts its own runtime, which cannot be done from an async context.
+    std::thread::spawn(move || {
+        reqwest::blocking::Client::builder()
+            .timeout(Duration::from_millis(timeout))
+            .add_root_certificate(certificate)
+            .build()
+    })
+    .join()
+    .map_err(|_| String::from("Failed to build the OTLP HTTP client"))?
+    .map(Some)
+    .map_err(|err| format!("Failed to build the OTLP HTTP client: {err}"))
+}
+
+/// Reads the PEM CA certificate of a signal, if any.
+fn read_certificate(signal: &str) -> Result<Option<Vec<u8>>, String> {
+    otlp_variable(signal, "CERTIFICATE")
+        .map(|path| {
+            fs::read(path.trim())
+                .map_err(|err| format!("Failed to read the OTLP CA certificate '{path}': {err}"))
+        })
+        .transpose()
+}
+
+/// Builds the exporter of a signal with the configured transport.
+macro_rules! build_exporter {
+    ($exporter:ty, $signal:expr) => {{
+        let signal = $signal;
+
+        match otlp_protocol(signal)? {
+            OtlpProtocol::Grpc => {
+                let builder = <$exporter>::builder().with_tonic();
+                match tonic_tls_config(signal)? {
+                    Some(tls_config) => builder.with_tls_config(tls_config).build(),
+                    None => builder.build(),
+                }
+            }
+            OtlpProtocol::HttpProtobuf => {
+                let builder = <$exporter>::builder().with_http();
+                match http_client(signal)? {
+                    Some(client) => builder.with_http_client(client).build(),
+                    None => builder.build(),
+                }
+            }
+        }
+        .map_err(|err| format!("Failed to build the OTLP exporter: {err}"))
+    }};
+}
+
+/// Builds the OTLP exporter of the traces.
+///
+/// The endpoint, headers, compression and timeout are read by the exporter from the
+/// `OTEL_EXPORTER_OTLP_*` environment variables.
+pub fn span_exporter() -> Result<SpanExporter, String> {
+    build_exporter!(SpanExporter, "TRACES")
+}
+
+/// Builds the OTLP exporter of the metrics.
+pub fn metric_exporter() -> Result<MetricExporter, String> {
+    build_exporter!(MetricExporter, "METRICS")
+}
+
+/// Builds the OTLP exporter of the logs.
+pub fn log_exporter() -> Result<LogExporter, String> {
+    build_exporter!(LogExporter, "LOGS")
+}
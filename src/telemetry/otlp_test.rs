@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use crate::telemetry::{
+        OtlpProtocol, init_resource, otlp_protocol, span_exporter, traces_sampler,
+    };
+    use opentelemetry::{Key, Value};
+    use test_case::test_case;
+
+    #[test_case("grpc" => Ok(OtlpProtocol::Grpc); "grpc")]
+    #[test_case("http/protobuf" => Ok(OtlpProtocol::HttpProtobuf); "http_protobuf")]
+    #[test_case(" HTTP/Protobuf " => Ok(OtlpProtocol::HttpProtobuf); "http_protobuf_untrimmed")]
+    #[test_case("http/json" => matches Err(_); "unsupported")]
+    fn try_from_str_to_otlp_protocol(value: &str) -> Result<OtlpProtocol, String> {
+        OtlpProtocol::try_from(value)
+    }
+
+    #[test_case(None, None => Ok(OtlpProtocol::Grpc); "default")]
+    #[test_case(Some("http/protobuf"), None => Ok(OtlpProtocol::HttpProtobuf); "shared")]
+    #[test_case(Some("http/protobuf"), Some("grpc") => Ok(OtlpProtocol::Grpc); "signal_override")]
+    #[test_case(Some("udp"), None => matches Err(_); "invalid")]
+    fn otlp_protocol_from_env(
+        protocol: Option<&str>,
+        traces_protocol: Option<&str>,
+    ) -> Result<OtlpProtocol, String> {
+        temp_env::with_vars(
+            [
+                ("OTEL_EXPORTER_OTLP_PROTOCOL", protocol),
+                ("OTEL_EXPORTER_OTLP_TRACES_PROTOCOL", traces_protocol),
+            ],
+            || otlp_protocol("TRACES"),
+        )
+    }
+
+    #[test_case(None, None => Ok(String::from("ParentBased(AlwaysOn)")); "default")]
+    #[test_case(Some("always_off"), None => Ok(String::from("AlwaysOff")); "always_off")]
+    #[test_case(Some("traceidratio"), Some("0.25") => Ok(String::from("TraceIdRatioBased(0.25)")); "ratio")]
+    #[test_case(Some("parentbased_traceidratio"), Some("0.1") => Ok(String::from("ParentBased(TraceIdRatioBased(0.1))")); "parent_based_ratio")]
+    #[test_case(Some("parentbased_traceidratio"), None => Ok(String::from("ParentBased(TraceIdRatioBased(1.0))")); "parent_based_default_ratio")]
+    #[test_case(Some("traceidratio"), Some("2") => matches Err(_); "ratio_out_of_range")]
+    #[test_case(Some("traceidratio"), Some("half") => matches Err(_); "ratio_invalid")]
+    #[test_case(Some("sometimes"), None => matches Err(_); "unknown")]
+    fn traces_sampler_from_env(sampler: Option<&str>, arg: Option<&str>) -> Result<String, String> {
+        temp_env::with_vars(
+            [
+                ("OTEL_TRACES_SAMPLER", sampler),
+                ("OTEL_TRACES_SAMPLER_ARG", arg),
+            ],
+            || traces_sampler().map(|sampler| format!("{sampler:?}")),
+        )
+    }
+
+    #[test]
+    fn span_exporter_invalid_configuration() {
+        temp_env::with_vars(
+            [
+                ("OTEL_EXPORTER_OTLP_ENDPOINT", Some("http://localhost:4317")),
+                ("OTEL_EXPORTER_OTLP_PROTOCOL", Some("grpc")),
+                ("OTEL_EXPORTER_OTLP_TRACES_PROTOCOL", None),
+                (
+                    "OTEL_EXPORTER_OTLP_CERTIFICATE",
+                    Some("/nonexistent/ca.pem"),
+                ),
+                ("OTEL_EXPORTER_OTLP_TRACES_CERTIFICATE", None),
+            ],
+            || {
+                let error = span_exporter().err().unwrap();
+                assert!(error.starts_with("Failed to read the OTLP CA certificate"));
+            },
+        );
+    }
+
+    #[test]
+    fn init_resource_attributes() {
+        temp_env::with_vars(
+            [
+                ("OTEL_SERVICE_NAME", Some("rcon-test")),
+                (
+                    "OTEL_RESOURCE_ATTRIBUTES",
+                    Some("deployment.environment.name=staging,service.namespace=minecraft"),
+                ),
+            ],
+            || {
+                let resource = init_resource();
+
+                assert_eq!(
+                    resource.get(&Key::from_static_str("service.name")),
+                    Some(Value::from("rcon-test"))
+                );
+                assert_eq!(
+                    resource.get(&Key::from_static_str("deployment.environment.name")),
+                    Some(Value::from("staging"))
+                );
+                assert_eq!(
+                    resource.get(&Key::from_static_str("service.namespace")),
+                    Some(Value::from("minecraft"))
+                );
+            },
+        );
+    }
+}
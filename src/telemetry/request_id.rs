@@ -0,0 +1,93 @@
+use rand::RngExt;
+use rocket::{
+    Data, Request, Response,
+    fairing::{Fairing, Info, Kind},
+    http::Header,
+    request::{FromRequest, Outcome},
+};
+use std::{convert::Infallible, fmt};
+
+/// Header carrying the ID of a request, both in the requests and in the responses.
+pub const REQUEST_ID_HEADER: &str = "X-Request-Id";
+
+/// Maximum length of an incoming request ID, longer ones being replaced.
+const MAX_REQUEST_ID_LENGTH: usize = 128;
+
+/// ID of an HTTP request, to correlate the responses with the logs and the traces.
+///
+/// The ID provided by the client or the reverse proxy in the `X-Request-Id` header is honored
+/// when it is a short printable string, a random ID is generated otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// Returns the ID of a request, resolving it on the first call.
+    pub fn of<'r>(request: &'r Request<'_>) -> &'r RequestId {
+        request.local_cache(|| {
+            request
+                .headers()
+                .get_one(REQUEST_ID_HEADER)
+                .and_then(RequestId::parse)
+                .unwrap_or_else(RequestId::generate)
+        })
+    }
+
+    /// Parses an incoming request ID, rejecting the empty, long or non-printable ones.
+    pub fn parse(value: &str) -> Option<RequestId> {
+        let value = value.trim();
+        let is_valid = !value.is_empty()
+            && value.len() <= MAX_REQUEST_ID_LENGTH
+            && value.chars().all(|c| c.is_ascii_graphic());
+
+        is_valid.then(|| RequestId(String::from(value)))
+    }
+
+    /// Generates a random request ID.
+    pub fn generate() -> RequestId {
+        RequestId(format!("{:032x}", rand::rng().random::<u128>()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for &'r RequestId {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(RequestId::of(request))
+    }
+}
+
+/// Rocket fairing resolving the ID of each request and returning it in the `X-Request-Id` header
+/// of the response.
+pub struct RequestIdFairing;
+
+#[rocket::async_trait]
+impl Fairing for RequestIdFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Request ID",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        RequestId::of(request);
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        response.set_header(Header::new(
+            REQUEST_ID_HEADER,
+            RequestId::of(request).to_string(),
+        ));
+    }
+}
@@ -0,0 +1,25 @@
+#[cfg(test)]
+mod tests {
+    use crate::telemetry::RequestId;
+    use test_case::test_case;
+
+    #[test_case("abc-123" => Some(String::from("abc-123")); "valid")]
+    #[test_case(" 4bf92f35 " => Some(String::from("4bf92f35")); "untrimmed")]
+    #[test_case("" => None; "empty")]
+    #[test_case("with space" => None; "space")]
+    #[test_case("caf\u{e9}" => None; "non_ascii")]
+    #[test_case(&"a".repeat(129) => None; "too_long")]
+    fn parse_request_id(value: &str) -> Option<String> {
+        RequestId::parse(value).map(|id| id.to_string())
+    }
+
+    #[test]
+    fn generate_request_id() {
+        let first = RequestId::generate();
+        let second = RequestId::generate();
+
+        assert_eq!(first.as_str().len(), 32);
+        assert!(first.as_str().chars().all(|c| c.is_ascii_hexdigit()));
+        assert_ne!(first, second);
+    }
+}
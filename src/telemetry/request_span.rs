@@ -1,3 +1,4 @@
+use super::RequestId;
 use rocket::Request;
 use std::ops::Deref;
 use tracing::{Span, info_span};
@@ -54,6 +55,8 @@ impl RequestSpan {
 
             url.path = %request.uri().path(),
 
+            http.request.id = %RequestId::of(request),
+
             http.request.method = %request.method(),
             http.route = tracing::field::Empty,
             http.response.status_code = tracing::field::Empty,
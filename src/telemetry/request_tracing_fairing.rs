@@ -1,10 +1,15 @@
-use super::RequestSpan;
-use opentelemetry::{global, propagation::Extractor};
+use super::{HTTP_SERVER_REQUEST_DURATION, RequestSpan};
+use opentelemetry::{KeyValue, global, propagation::Extractor, trace::TraceContextExt};
 use rocket::{
     Data, Request, Response,
     fairing::{Fairing, Info, Kind},
-    http::HeaderMap,
+    http::{Header, HeaderMap},
 };
+use std::time::Instant;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Instant the request was received, stored in Rocket's request-local cache.
+struct RequestStartedAt(Instant);
 
 /// Extracts tracing context from incoming HTTP request headers.
 struct RocketHeaderExtractor<'a, 'r>(&'a HeaderMap<'r>);
@@ -32,6 +37,11 @@ impl<'a, 'r> Extractor for RocketHeaderExtractor<'a, 'r> {
 /// - The name of the request handler.
 /// - The HTTP response status code.
 ///
+/// The duration of the request is recorded in the `http.server.request.duration` metric.
+///
+/// When the span is traced, its context is returned in the `traceresponse` header of the response,
+/// following the W3C Trace Context format, and its trace ID in the `X-Trace-Id` header.
+///
 /// The span is stored in Rocket's request-local cache, allowing it to be accessed in request
 /// handlers using the `RequestSpan` guard.
 pub struct TelemetryRequestFairing;
@@ -46,6 +56,8 @@ impl Fairing for TelemetryRequestFairing {
     }
 
     async fn on_request(&self, request: &mut Request<'_>, _data: &mut Data<'_>) {
+        request.local_cache(|| RequestStartedAt(Instant::now()));
+
         // Extract the parent tracing context from the incoming request headers.
         let parent_context = global::get_text_map_propagator(|propagator| {
             propagator.extract(&RocketHeaderExtractor(request.headers()))
@@ -59,14 +71,48 @@ impl Fairing for TelemetryRequestFairing {
     async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
         let request_span = request.local_cache(|| RequestSpan::new(request));
 
+        let route = request
+            .route()
+            .map(|route| route.uri.to_string())
+            .unwrap_or_else(|| String::from("unmatched"));
+
         if let Some(route) = request.route() {
-            request_span.record("http.route", route.uri.to_string());
             request_span.record("app.handler", route.name.as_deref().unwrap_or("unknown"));
         } else {
-            request_span.record("http.route", "unmatched");
             request_span.record("app.handler", "unmatched");
         }
 
+        request_span.record("http.route", route.as_str());
         request_span.record("http.response.status_code", response.status().code);
+
+        let span_context = request_span.context().span().span_context().clone();
+        if span_context.is_valid() {
+            response.set_header(Header::new(
+                "traceresponse",
+                format!(
+                    "00-{}-{}-{:02x}",
+                    span_context.trace_id(),
+                    span_context.span_id(),
+                    span_context.trace_flags().to_u8()
+                ),
+            ));
+            response.set_header(Header::new(
+                "X-Trace-Id",
+                span_context.trace_id().to_string(),
+            ));
+        }
+
+        let started_at = request.local_cache(|| RequestStartedAt(Instant::now()));
+        HTTP_SERVER_REQUEST_DURATION.record(
+            started_at.0.elapsed().as_secs_f64(),
+            &[
+                KeyValue::new("http.request.method", request.method().as_str()),
+                KeyValue::new("http.route", route),
+                KeyValue::new(
+                    "http.response.status_code",
+                    i64::from(response.status().code),
+                ),
+            ],
+        );
     }
 }
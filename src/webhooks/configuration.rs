@@ -0,0 +1,319 @@
+use super::{WEBHOOK_EVENT_KINDS, WebhookError, WebhookTrigger};
+use crate::rcon::Secret;
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashSet},
+    env, fs,
+    path::{Path, PathBuf},
+};
+use tracing::debug;
+
+/// Default timeout of a webhook delivery attempt, in milliseconds.
+pub const DEFAULT_WEBHOOKS_TIMEOUT: u64 = 5000;
+
+/// Default number of delivery attempts of a webhook, including the first one.
+pub const DEFAULT_WEBHOOKS_MAX_ATTEMPTS: u32 = 5;
+
+/// Default delay before the first retry of a delivery, in milliseconds.
+pub const DEFAULT_WEBHOOKS_RETRY_BASE_DELAY: u64 = 1000;
+
+/// Default maximum delay between two delivery attempts, in milliseconds.
+pub const DEFAULT_WEBHOOKS_RETRY_MAX_DELAY: u64 = 60000;
+
+/// Default maximum age of the signature of a trigger request, in seconds.
+pub const DEFAULT_WEBHOOKS_TRIGGER_TOLERANCE: u64 = 300;
+
+/// Default commands worth a notification when executed through the API.
+pub const DEFAULT_WEBHOOKS_DANGEROUS_COMMANDS: &str =
+    "stop,op,deop,ban,ban-ip,pardon,pardon-ip,whitelist,kill,reload,save-off";
+
+/// Endpoint the webhook events are posted to.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookEndpoint {
+    /// Name of the endpoint, as written in the logs and the dead-letter log.
+    pub name: String,
+    /// URL the events are posted to.
+    pub url: String,
+    /// Secret the bodies are signed with, unsigned if missing.
+    #[serde(default)]
+    pub secret: Option<Secret>,
+    /// Kinds of events posted to the endpoint, e.g. `rcon.unreachable` or `rcon.*`, all of them
+    /// if empty.
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// JSON body posted instead of the event, its strings containing `{{placeholders}}`.
+    #[serde(default)]
+    pub template: Option<Value>,
+    /// Additional headers of the requests, e.g. an `Authorization` header.
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+impl WebhookEndpoint {
+    /// Returns `true` if the events of a kind are posted to the endpoint.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of event, e.g. `rcon.unreachable`.
+    pub fn accepts(&self, kind: &str) -> bool {
+        self.events.is_empty()
+            || self
+                .events
+                .iter()
+                .any(|pattern| event_matches(pattern, kind))
+    }
+}
+
+/// Returns `true` if a kind of event matches a pattern, either `*`, a prefix ending with `*` or
+/// the kind itself.
+fn event_matches(pattern: &str, kind: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => kind.starts_with(prefix),
+        None => kind == pattern,
+    }
+}
+
+/// Content of the webhooks file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhooksFile {
+    #[serde(default)]
+    pub endpoints: Vec<WebhookEndpoint>,
+    #[serde(default)]
+    pub triggers: Vec<WebhookTrigger>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookConfiguration {
+    /// Endpoints the events are posted to, from the webhooks file.
+    pub endpoints: Vec<WebhookEndpoint>,
+    /// Timeout of a delivery attempt, in milliseconds.
+    pub timeout: u64,
+    /// Number of delivery attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry of a delivery, in milliseconds.
+    pub base_delay: u64,
+    /// Maximum delay between two delivery attempts, in milliseconds.
+    pub max_delay: u64,
+    /// File the undelivered events are appended to, as JSON lines.
+    pub dead_letter_path: Option<PathBuf>,
+    /// Commands notified as `rcon.dangerous_command` when executed through the API.
+    pub dangerous_commands: HashSet<String>,
+    /// Triggers invoked with `POST /api/hooks/<name>`, from the webhooks file.
+    pub triggers: Vec<WebhookTrigger>,
+    /// Maximum difference between the timestamp of a trigger request and the current time, in
+    /// seconds.
+    pub trigger_tolerance: u64,
+}
+
+impl WebhookConfiguration {
+    /// Returns the configuration from environment variables.
+    ///
+    /// The endpoints and the triggers are read from the JSON file given by `WEBHOOKS_PATH`, none
+    /// being configured if it is not set.
+    ///
+    /// # Returns:
+    ///
+    /// The configuration.
+    pub fn try_new() -> Result<WebhookConfiguration, WebhookError> {
+        debug!("Attempting to create webhook configuration from environment variables...");
+
+        let file = match Self::path() {
+            Some(path) => Self::read_file(&path)?,
+            None => WebhooksFile::default(),
+        };
+        let timeout = env::var("WEBHOOKS_TIMEOUT")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u64>())
+            .unwrap_or(Ok(DEFAULT_WEBHOOKS_TIMEOUT))
+            .ok()
+            .filter(|timeout| *timeout > 0)
+            .ok_or_else(|| WebhookError::Configuration {
+                cause: String::from(
+                    "Environment variable 'WEBHOOKS_TIMEOUT' is not a valid positive number",
+                ),
+            })?;
+        let max_attempts = env::var("WEBHOOKS_MAX_ATTEMPTS")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u32>())
+            .unwrap_or(Ok(DEFAULT_WEBHOOKS_MAX_ATTEMPTS))
+            .ok()
+            .filter(|max_attempts| *max_attempts > 0)
+            .ok_or_else(|| WebhookError::Configuration {
+                cause: String::from(
+                    "Environment variable 'WEBHOOKS_MAX_ATTEMPTS' is not a valid positive number",
+                ),
+            })?;
+        let base_delay = env::var("WEBHOOKS_RETRY_BASE_DELAY")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u64>())
+            .unwrap_or(Ok(DEFAULT_WEBHOOKS_RETRY_BASE_DELAY))
+            .map_err(|_| WebhookError::Configuration {
+                cause: String::from(
+                    "Environment variable 'WEBHOOKS_RETRY_BASE_DELAY' is not a valid number",
+                ),
+            })?;
+        let max_delay = env::var("WEBHOOKS_RETRY_MAX_DELAY")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u64>())
+            .unwrap_or(Ok(DEFAULT_WEBHOOKS_RETRY_MAX_DELAY))
+            .map_err(|_| WebhookError::Configuration {
+                cause: String::from(
+                    "Environment variable 'WEBHOOKS_RETRY_MAX_DELAY' is not a valid number",
+                ),
+            })?;
+        let dead_letter_path = env::var("WEBHOOKS_DEAD_LETTER_PATH")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .map(PathBuf::from);
+        let dangerous_commands = env::var("WEBHOOKS_DANGEROUS_COMMANDS")
+            .unwrap_or_else(|_| String::from(DEFAULT_WEBHOOKS_DANGEROUS_COMMANDS))
+            .split(',')
+            .map(|command| command.trim().trim_start_matches('/').to_lowercase())
+            .filter(|command| !command.is_empty())
+            .collect();
+        let trigger_tolerance = env::var("WEBHOOKS_TRIGGER_TOLERANCE")
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.parse::<u64>())
+            .unwrap_or(Ok(DEFAULT_WEBHOOKS_TRIGGER_TOLERANCE))
+            .ok()
+            .filter(|trigger_tolerance| *trigger_tolerance > 0)
+            .ok_or_else(|| WebhookError::Configuration {
+                cause: String::from(
+                    "Environment variable 'WEBHOOKS_TRIGGER_TOLERANCE' is not a valid positive number",
+                ),
+            })?;
+
+        Ok(WebhookConfiguration {
+            endpoints: file.endpoints,
+            timeout,
+            max_attempts,
+            base_delay,
+            max_delay,
+            dead_letter_path,
+            dangerous_commands,
+            triggers: file.triggers,
+            trigger_tolerance,
+        })
+    }
+
+    /// Returns whether the webhooks are enabled, with the `WEBHOOKS_PATH` environment variable.
+    pub fn is_set() -> bool {
+        Self::path().is_some()
+    }
+
+    /// Returns `true` if a command is notified when executed through the API.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The command, only its first word is considered.
+    pub fn is_dangerous_command(&self, command: &str) -> bool {
+        command
+            .split_whitespace()
+            .next()
+            .map(|verb| verb.trim_start_matches('/').to_lowercase())
+            .is_some_and(|verb| self.dangerous_commands.contains(&verb))
+    }
+
+    /// Returns the path of the webhooks file from the environment variables.
+    fn path() -> Option<PathBuf> {
+        env::var("WEBHOOKS_PATH")
+            .ok()
+            .filter(|value| !value.trim().is_empty())
+            .map(PathBuf::from)
+    }
+
+    /// Reads and checks the webhooks file.
+    fn read_file(path: &Path) -> Result<WebhooksFile, WebhookError> {
+        let content = fs::read_to_string(path).map_err(|err| WebhookError::Configuration {
+            cause: format!(
+                "Failed to read the webhooks file '{}': {err}",
+                path.display()
+            ),
+        })?;
+        let file: WebhooksFile =
+            serde_json::from_str(&content).map_err(|err| WebhookError::Configuration {
+                cause: format!("Invalid webhooks file '{}': {err}", path.display()),
+            })?;
+
+        let mut names = HashSet::new();
+        for endpoint in &file.endpoints {
+            if endpoint.name.trim().is_empty() || !names.insert(endpoint.name.as_str()) {
+                return Err(WebhookError::Configuration {
+                    cause: format!(
+                        "The name of the webhook endpoint '{}' is empty or not unique",
+                        endpoint.name
+                    ),
+                });
+            }
+            if !reqwest::Url::parse(&endpoint.url)
+                .is_ok_and(|url| matches!(url.scheme(), "http" | "https"))
+            {
+                return Err(WebhookError::Configuration {
+                    cause: format!(
+                        "The URL of the webhook endpoint '{}' is not a valid HTTP URL",
+                        endpoint.name
+                    ),
+                });
+            }
+            if let Some(pattern) = endpoint.events.iter().find(|pattern| {
+                !WEBHOOK_EVENT_KINDS
+                    .iter()
+                    .any(|kind| event_matches(pattern, kind))
+            }) {
+                return Err(WebhookError::Configuration {
+                    cause: format!(
+                        "The event '{pattern}' of the webhook endpoint '{}' matches none of {}",
+                        endpoint.name,
+                        WEBHOOK_EVENT_KINDS.join(", ")
+                    ),
+                });
+            }
+        }
+
+        let mut names = HashSet::new();
+        for trigger in &file.triggers {
+            let valid_name = !trigger.name.is_empty()
+                && trigger.name.chars().all(|character| {
+                    character.is_ascii_alphanumeric() || character == '-' || character == '_'
+                });
+            if !valid_name || !names.insert(trigger.name.as_str()) {
+                return Err(WebhookError::Configuration {
+                    cause: format!(
+                        "The name of the webhook trigger '{}' is not unique or not made of letters, digits, dashes and underscores",
+                        trigger.name
+                    ),
+                });
+            }
+            if trigger.secret.expose().is_empty() {
+                return Err(WebhookError::Configuration {
+                    cause: format!(
+                        "The secret of the webhook trigger '{}' is empty",
+                        trigger.name
+                    ),
+                });
+            }
+            if trigger.commands.is_empty()
+                || trigger
+                    .commands
+                    .iter()
+                    .any(|command| command.trim().is_empty())
+            {
+                return Err(WebhookError::Configuration {
+                    cause: format!(
+                        "The webhook trigger '{}' has no command or a blank one",
+                        trigger.name
+                    ),
+                });
+            }
+        }
+
+        Ok(file)
+    }
+}
@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod tests {
+    use crate::webhooks::{
+        DEFAULT_WEBHOOKS_MAX_ATTEMPTS, DEFAULT_WEBHOOKS_TIMEOUT,
+        DEFAULT_WEBHOOKS_TRIGGER_TOLERANCE, WebhookConfiguration, WebhookError,
+    };
+    use serial_test::serial;
+    use std::{fs, path::PathBuf};
+    use temp_env::with_vars;
+    use test_case::test_case;
+
+    /// Writes a webhooks file for a test.
+    fn webhooks_file(name: &str, content: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{name}-{}.json", std::process::id()));
+        fs::write(&path, content).unwrap();
+
+        path
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_not_set() {
+        with_vars(
+            [
+                ("WEBHOOKS_PATH", None::<&str>),
+                ("WEBHOOKS_TIMEOUT", None),
+                ("WEBHOOKS_MAX_ATTEMPTS", None),
+                ("WEBHOOKS_DANGEROUS_COMMANDS", None),
+            ],
+            || {
+                assert!(!WebhookConfiguration::is_set());
+
+                let configuration = WebhookConfiguration::try_new().unwrap();
+                assert!(configuration.endpoints.is_empty());
+                assert_eq!(configuration.timeout, DEFAULT_WEBHOOKS_TIMEOUT);
+                assert_eq!(configuration.max_attempts, DEFAULT_WEBHOOKS_MAX_ATTEMPTS);
+                assert!(configuration.is_dangerous_command("/Stop"));
+                assert!(configuration.is_dangerous_command("ban Steve griefing"));
+                assert!(!configuration.is_dangerous_command("list"));
+            },
+        );
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_file() {
+        let path = webhooks_file(
+            "webhooks-configuration",
+            r#"{
+                "endpoints": [
+                    {
+                        "name": "ops",
+                        "url": "https://chat.example.com/hooks/ops",
+                        "secret": "s3cr3t",
+                        "events": ["rcon.*"],
+                        "template": {"text": "{{message}}"},
+                        "headers": {"Authorization": "Bearer abc"}
+                    },
+                    {
+                        "name": "audit",
+                        "url": "http://localhost:8080/audit",
+                        "events": ["rcon.dangerous_command"]
+                    }
+                ],
+                "triggers": [
+                    {
+                        "name": "announce-deploy",
+                        "secret": "d3pl0y",
+                        "commands": ["say Deployed {{version}}"]
+                    }
+                ]
+            }"#,
+        );
+
+        with_vars(
+            [
+                ("WEBHOOKS_PATH", Some(path.to_str().unwrap())),
+                ("WEBHOOKS_DANGEROUS_COMMANDS", Some("stop, /Whitelist")),
+            ],
+            || {
+                assert!(WebhookConfiguration::is_set());
+
+                let configuration = WebhookConfiguration::try_new().unwrap();
+                assert_eq!(configuration.endpoints.len(), 2);
+
+                let ops = &configuration.endpoints[0];
+                assert_eq!(ops.name, "ops");
+                assert!(
+                    ops.secret
+                        .as_ref()
+                        .is_some_and(|secret| *secret == "s3cr3t")
+                );
+                assert!(ops.accepts("rcon.unreachable"));
+                assert!(ops.template.is_some());
+                assert_eq!(ops.headers["Authorization"], "Bearer abc");
+
+                let audit = &configuration.endpoints[1];
+                assert!(audit.secret.is_none());
+                assert!(audit.accepts("rcon.dangerous_command"));
+                assert!(!audit.accepts("rcon.unreachable"));
+
+                assert_eq!(configuration.triggers.len(), 1);
+                assert_eq!(configuration.triggers[0].name, "announce-deploy");
+                assert_eq!(
+                    configuration.triggers[0].commands,
+                    vec!["say Deployed {{version}}"]
+                );
+                assert_eq!(
+                    configuration.trigger_tolerance,
+                    DEFAULT_WEBHOOKS_TRIGGER_TOLERANCE
+                );
+
+                assert!(configuration.is_dangerous_command("whitelist add Steve"));
+                assert!(!configuration.is_dangerous_command("ban Steve"));
+            },
+        );
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test_case(r#"{"endpoints": [{"name": "ops", "url": "ftp://example.com"}]}"#; "invalid url")]
+    #[test_case(r#"{"endpoints": [{"name": "", "url": "http://example.com"}]}"#; "empty name")]
+    #[test_case(r#"{"endpoints": [{"name": "ops", "url": "http://a"}, {"name": "ops", "url": "http://b"}]}"#; "duplicate name")]
+    #[test_case(r#"{"endpoints": [{"name": "ops", "url": "http://a", "events": ["server.*"]}]}"#; "unknown event")]
+    #[test_case(r#"{"triggers": [{"name": "a/b", "secret": "s", "commands": ["list"]}]}"#; "invalid trigger name")]
+    #[test_case(r#"{"triggers": [{"name": "a", "secret": "s", "commands": ["list"]}, {"name": "a", "secret": "s", "commands": ["list"]}]}"#; "duplicate trigger name")]
+    #[test_case(r#"{"triggers": [{"name": "a", "secret": "", "commands": ["list"]}]}"#; "empty trigger secret")]
+    #[test_case(r#"{"triggers": [{"name": "a", "secret": "s", "commands": []}]}"#; "no trigger command")]
+    #[test_case(r#"{"triggers": [{"name": "a", "secret": "s", "commands": ["list", " "]}]}"#; "blank trigger command")]
+    #[test_case(r#"{"triggers": [{"name": "a", "commands": ["list"]}]}"#; "missing trigger secret")]
+    #[test_case(r#"{"endpoint": []}"#; "unknown field")]
+    #[test_case("not json"; "invalid json")]
+    #[serial(rcon)]
+    fn test_get_configuration_invalid_file(content: &str) {
+        let path = webhooks_file("webhooks-configuration-invalid", content);
+
+        with_vars([("WEBHOOKS_PATH", Some(path.to_str().unwrap()))], || {
+            let result = WebhookConfiguration::try_new();
+            assert!(matches!(result, Err(WebhookError::Configuration { .. })));
+        });
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    #[serial(rcon)]
+    fn test_get_configuration_missing_file() {
+        with_vars(
+            [("WEBHOOKS_PATH", Some("/nonexistent/webhooks.json"))],
+            || {
+                let result = WebhookConfiguration::try_new();
+                assert!(matches!(result, Err(WebhookError::Configuration { .. })));
+            },
+        );
+    }
+
+    #[test_case("WEBHOOKS_TIMEOUT", "0"; "zero timeout")]
+    #[test_case("WEBHOOKS_MAX_ATTEMPTS", "0"; "zero attempts")]
+    #[test_case("WEBHOOKS_RETRY_BASE_DELAY", "soon"; "invalid base delay")]
+    #[test_case("WEBHOOKS_RETRY_MAX_DELAY", "-1"; "invalid max delay")]
+    #[test_case("WEBHOOKS_TRIGGER_TOLERANCE", "0"; "zero trigger tolerance")]
+    #[serial(rcon)]
+    fn test_get_configuration_invalid(name: &str, value: &str) {
+        with_vars(
+            [("WEBHOOKS_PATH", None::<&str>), (name, Some(value))],
+            || {
+                let result = WebhookConfiguration::try_new();
+                assert!(matches!(result, Err(WebhookError::Configuration { .. })));
+            },
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use super::{
+    WEBHOOK_SIGNATURE_HEADER, WEBHOOK_TIMESTAMP_HEADER, WebhookConfiguration, WebhookEndpoint,
+    WebhookError, WebhookEvent, sign_webhook,
+};
+use reqwest::{Client, StatusCode, header::CONTENT_TYPE};
+use serde_json::json;
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{task::JoinHandle, time::sleep};
+use tracing::{debug, error, info, warn};
+
+/// Dispatcher of the webhook events to the configured endpoints.
+///
+/// Every event is delivered in the background to the endpoints accepting its kind, so that the
+/// notifications never slow down the commands. Failed deliveries are retried with an exponential
+/// backoff, then written to the dead-letter log.
+///
+/// The default dispatcher has no endpoint and drops the events.
+#[derive(Debug, Clone, Default)]
+pub struct WebhookDispatcher {
+    inner: Option<Arc<WebhookDispatcherInner>>,
+}
+
+#[derive(Debug)]
+struct WebhookDispatcherInner {
+    configuration: WebhookConfiguration,
+    client: Client,
+    /// Serializes the writes to the dead-letter log.
+    dead_letter: Mutex<()>,
+}
+
+impl WebhookDispatcher {
+    /// Creates a dispatcher from the environment variables, without endpoint unless
+    /// `WEBHOOKS_PATH` is set.
+    pub fn try_new() -> Result<Self, WebhookError> {
+        if !WebhookConfiguration::is_set() {
+            return Ok(WebhookDispatcher::default());
+        }
+
+        let configuration = WebhookConfiguration::try_new()?;
+        info!(
+            "{} webhook endpoints configured",
+            configuration.endpoints.len()
+        );
+
+        Self::new(configuration)
+    }
+
+    /// Creates a dispatcher posting the events to the endpoints of a configuration.
+    pub fn new(configuration: WebhookConfiguration) -> Result<Self, WebhookError> {
+        let client = Client::builder()
+            .timeout(Duration::from_millis(configuration.timeout))
+            .build()
+            .map_err(|err| WebhookError::Configuration {
+                cause: format!("Failed to create the HTTP client: {err}"),
+            })?;
+
+        Ok(WebhookDispatcher {
+            inner: Some(Arc::new(WebhookDispatcherInner {
+                configuration,
+                client,
+                dead_letter: Mutex::new(()),
+            })),
+        })
+    }
+
+    /// Returns the configuration of the webhooks, if enabled.
+    pub fn configuration(&self) -> Option<&WebhookConfiguration> {
+        self.inner.as_ref().map(|inner| &inner.configuration)
+    }
+
+    /// Returns `true` if a command is notified when executed through the API.
+    pub fn is_dangerous_command(&self, command: &str) -> bool {
+        self.configuration()
+            .is_some_and(|configuration| configuration.is_dangerous_command(command))
+    }
+
+    /// Delivers an event in the background to the endpoints accepting its kind.
+    ///
+    /// # Returns
+    ///
+    /// The handles of the delivery tasks, one per endpoint.
+    pub fn dispatch(&self, event: WebhookEvent) -> Vec<JoinHandle<()>> {
+        let Some(inner) = &self.inner else {
+            return Vec::new();
+        };
+
+        inner
+            .configuration
+            .endpoints
+            .iter()
+            .filter(|endpoint| endpoint.accepts(event.kind.as_str()))
+            .map(|endpoint| {
+                let dispatcher = self.clone();
+                let endpoint = endpoint.clone();
+                let event = event.clone();
+
+                tokio::spawn(async move {
+                    if let Some(inner) = &dispatcher.inner {
+                        inner.deliver(&endpoint, &event).await;
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+impl WebhookDispatcherInner {
+    /// Delivers an event to an endpoint, retrying the failed attempts.
+    async fn deliver(&self, endpoint: &WebhookEndpoint, event: &WebhookEvent) {
+        let payload = match &endpoint.template {
+            Some(template) => event.render(template),
+            None => event.to_json(),
+        };
+        let body = payload.to_string();
+
+        let mut attempt = 1;
+        loop {
+            match self.send(endpoint, event, &body).await {
+                Ok(()) => {
+                    debug!(
+                        "Webhook event '{}' delivered to '{}' after {} attempts",
+                        event.id, endpoint.name, attempt
+                    );
+                    Self::record_delivery(endpoint, "delivered");
+                    return;
+                }
+                Err((error, retryable))
+                    if retryable && attempt < self.configuration.max_attempts =>
+                {
+                    let delay = self.delay(attempt);
+                    warn!(
+                        "Webhook attempt {} to '{}' failed: {}, retrying in {}ms...",
+                        attempt,
+                        endpoint.name,
+                        error,
+                        delay.as_millis()
+                    );
+
+                    sleep(delay).await;
+                    attempt += 1;
+                }
+                Err((error, _)) => {
+                    error!(
+                        "Webhook event '{}' could not be delivered to '{}' after {} attempts: {}",
+                        event.id, endpoint.name, attempt, error
+                    );
+                    Self::record_delivery(endpoint, "failed");
+                    self.write_dead_letter(endpoint, event, attempt, &error, &body);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Posts a body to an endpoint once.
+    ///
+    /// # Returns
+    ///
+    /// Nothing if the endpoint accepted the body, otherwise the error along with whether the
+    /// attempt is worth retrying: on network errors, rate limiting and server errors.
+    async fn send(
+        &self,
+        endpoint: &WebhookEndpoint,
+        event: &WebhookEvent,
+        body: &str,
+    ) -> Result<(), (WebhookError, bool)> {
+        let mut request = self
+            .client
+            .post(&endpoint.url)
+            .header(CONTENT_TYPE, "application/json")
+            .header("X-Webhook-Id", event.id.as_str())
+            .header("X-Webhook-Event", event.kind.as_str());
+        if let Some(secret) = &endpoint.secret {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default();
+            request = request.header(WEBHOOK_TIMESTAMP_HEADER, timestamp).header(
+                WEBHOOK_SIGNATURE_HEADER,
+                sign_webhook(secret.expose(), timestamp, body.as_bytes()),
+            );
+        }
+        for (name, value) in &endpoint.headers {
+            request = request.header(name, value);
+        }
+
+        let response = request
+            .body(String::from(body))
+            .send()
+            .await
+            .map_err(|err| {
+                (
+                    WebhookError::Delivery {
+                        cause: err.to_string(),
+                    },
+                    true,
+                )
+            })?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err((
+                WebhookError::Delivery {
+                    cause: format!("The endpoint answered with the status {status}"),
+                },
+                status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS,
+            ))
+        }
+    }
+
+    /// Returns the delay to wait after a failed attempt, doubling after each attempt up to the
+    /// maximum delay.
+    ///
+    /// # Arguments
+    ///
+    /// * `attempt` - The number of the failed attempt, starting at 1.
+    fn delay(&self, attempt: u32) -> Duration {
+        Duration::from_millis(
+            self.configuration
+                .base_delay
+                .saturating_mul(2u64.saturating_pow(attempt.saturating_sub(1)))
+                .min(self.configuration.max_delay),
+        )
+    }
+
+    /// Appends an undelivered event to the dead-letter log, if configured.
+    fn write_dead_letter(
+        &self,
+        endpoint: &WebhookEndpoint,
+        event: &WebhookEvent,
+        attempts: u32,
+        error: &WebhookError,
+        body: &str,
+    ) {
+        let Some(path) = &self.configuration.dead_letter_path else {
+            return;
+        };
+
+        let line = json!({
+            "endpoint": endpoint.name,
+            "id": event.id,
+            "type": event.kind.as_str(),
+            "attempts": attempts,
+            "error": error.to_string(),
+            "failed_at": SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or_default(),
+            "body": body,
+        });
+
+        let _guard = self.lock_dead_letter();
+        let result = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| writeln!(file, "{line}"));
+        if let Err(err) = result {
+            error!(
+                "Failed to write the webhook event '{}' to the dead-letter log '{}': {}",
+                event.id,
+                path.display(),
+                err
+            );
+        }
+    }
+
+    #[allow(unused_variables)]
+    fn record_delivery(endpoint: &WebhookEndpoint, outcome: &str) {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "metrics")] {
+                crate::metrics::WEBHOOK_DELIVERIES_TOTAL
+                    .with_label_values(&[endpoint.name.as_str(), outcome])
+                    .inc();
+            }
+        }
+    }
+
+    fn lock_dead_letter(&self) -> MutexGuard<'_, ()> {
+        self.dead_letter
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,238 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        rcon::Secret,
+        webhooks::{
+            WebhookConfiguration, WebhookDispatcher, WebhookEndpoint, WebhookEvent,
+            WebhookEventKind, sign_webhook,
+        },
+    };
+    use serde_json::{Value, json};
+    use std::{
+        collections::{BTreeMap, HashMap, HashSet},
+        fs,
+        path::PathBuf,
+    };
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+        sync::mpsc::{UnboundedReceiver, unbounded_channel},
+    };
+
+    /// Request received by the test endpoint.
+    struct ReceivedRequest {
+        headers: HashMap<String, String>,
+        body: String,
+    }
+
+    /// Starts an HTTP endpoint answering with the given statuses, then with `200`.
+    async fn endpoint(statuses: Vec<u16>) -> (String, UnboundedReceiver<ReceivedRequest>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let (sender, receiver) = unbounded_channel();
+
+        tokio::spawn(async move {
+            let mut statuses = statuses.into_iter();
+            loop {
+                let Ok((stream, _)) = listener.accept().await else {
+                    return;
+                };
+                let mut reader = BufReader::new(stream);
+
+                let mut headers = HashMap::new();
+                let mut line = String::new();
+                reader.read_line(&mut line).await.unwrap();
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).await.unwrap();
+                    let Some((name, value)) = line.trim_end().split_once(": ") else {
+                        break;
+                    };
+                    headers.insert(name.to_lowercase(), String::from(value));
+                }
+                let length = headers["content-length"].parse().unwrap();
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).await.unwrap();
+
+                let status = statuses.next().unwrap_or(200);
+                reader
+                    .into_inner()
+                    .write_all(
+                        format!("HTTP/1.1 {status} Test\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                            .as_bytes(),
+                    )
+                    .await
+                    .unwrap();
+                sender
+                    .send(ReceivedRequest {
+                        headers,
+                        body: String::from_utf8(body).unwrap(),
+                    })
+                    .ok();
+            }
+        });
+
+        (url, receiver)
+    }
+
+    fn webhook_endpoint(name: &str, url: &str) -> WebhookEndpoint {
+        WebhookEndpoint {
+            name: String::from(name),
+            url: String::from(url),
+            secret: None,
+            events: Vec::new(),
+            template: None,
+            headers: BTreeMap::new(),
+        }
+    }
+
+    fn dispatcher(
+        endpoints: Vec<WebhookEndpoint>,
+        dead_letter_path: Option<PathBuf>,
+    ) -> WebhookDispatcher {
+        WebhookDispatcher::new(WebhookConfiguration {
+            endpoints,
+            timeout: 1000,
+            max_attempts: 3,
+            base_delay: 10,
+            max_delay: 20,
+            dead_letter_path,
+            dangerous_commands: HashSet::from([String::from("stop")]),
+            triggers: Vec::new(),
+            trigger_tolerance: 300,
+        })
+        .unwrap()
+    }
+
+    fn event() -> WebhookEvent {
+        WebhookEvent::new(
+            WebhookEventKind::AuthenticationFailed,
+            "The RCON login failed",
+        )
+        .with_data("target", "localhost:25575")
+    }
+
+    async fn wait(handles: Vec<tokio::task::JoinHandle<()>>) {
+        for handle in handles {
+            handle.await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_disabled() {
+        let dispatcher = WebhookDispatcher::default();
+
+        assert!(dispatcher.configuration().is_none());
+        assert!(!dispatcher.is_dangerous_command("stop"));
+        assert!(dispatcher.dispatch(event()).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_signed_template() {
+        let (url, mut received) = endpoint(Vec::new()).await;
+        let dispatcher = dispatcher(
+            vec![
+                WebhookEndpoint {
+                    secret: Some(Secret::from(String::from("s3cr3t"))),
+                    events: vec![String::from("rcon.*")],
+                    template: Some(json!({"text": "{{message}} on {{data.target}}"})),
+                    headers: BTreeMap::from([(String::from("X-Team"), String::from("ops"))]),
+                    ..webhook_endpoint("ops", &url)
+                },
+                WebhookEndpoint {
+                    events: vec![String::from("rcon.dangerous_command")],
+                    ..webhook_endpoint("audit", &url)
+                },
+            ],
+            None,
+        );
+        assert!(dispatcher.is_dangerous_command("/stop"));
+
+        let event = event();
+        let handles = dispatcher.dispatch(event.clone());
+        assert_eq!(handles.len(), 1);
+        wait(handles).await;
+
+        let request = received.recv().await.unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&request.body).unwrap(),
+            json!({"text": "The RCON login failed on localhost:25575"})
+        );
+        assert_eq!(request.headers["content-type"], "application/json");
+        assert_eq!(request.headers["x-webhook-id"], event.id);
+        assert_eq!(
+            request.headers["x-webhook-event"],
+            "rcon.authentication_failed"
+        );
+        assert_eq!(request.headers["x-team"], "ops");
+        assert_eq!(
+            request.headers["x-webhook-signature"],
+            sign_webhook(
+                "s3cr3t",
+                request.headers["x-webhook-timestamp"].parse().unwrap(),
+                request.body.as_bytes(),
+            )
+        );
+        assert!(received.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_retries() {
+        let (url, mut received) = endpoint(vec![503, 429]).await;
+        let dispatcher = dispatcher(vec![webhook_endpoint("ops", &url)], None);
+
+        wait(dispatcher.dispatch(event())).await;
+
+        for _ in 0..3 {
+            let request = received.recv().await.unwrap();
+            assert!(!request.headers.contains_key("x-webhook-signature"));
+            assert_eq!(
+                serde_json::from_str::<Value>(&request.body).unwrap()["type"],
+                "rcon.authentication_failed"
+            );
+        }
+        assert!(received.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_dead_letter() {
+        let dead_letter_path =
+            std::env::temp_dir().join(format!("webhooks-dead-letter-{}.jsonl", std::process::id()));
+        fs::remove_file(&dead_letter_path).ok();
+
+        let (url, mut received) = endpoint(vec![500, 500, 500, 400]).await;
+        let dispatcher = dispatcher(
+            vec![webhook_endpoint("ops", &url)],
+            Some(dead_letter_path.clone()),
+        );
+
+        // Every attempt fails with a server error.
+        let event = event();
+        wait(dispatcher.dispatch(event.clone())).await;
+        // The client errors are not retried.
+        wait(dispatcher.dispatch(event.clone())).await;
+
+        for _ in 0..4 {
+            received.recv().await.unwrap();
+        }
+        assert!(received.try_recv().is_err());
+
+        let lines: Vec<Value> = fs::read_to_string(&dead_letter_path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["endpoint"], "ops");
+        assert_eq!(lines[0]["id"], event.id);
+        assert_eq!(lines[0]["type"], "rcon.authentication_failed");
+        assert_eq!(lines[0]["attempts"], 3);
+        assert_eq!(lines[1]["attempts"], 1);
+        assert_eq!(
+            serde_json::from_str::<Value>(lines[1]["body"].as_str().unwrap()).unwrap(),
+            event.to_json()
+        );
+
+        fs::remove_file(dead_letter_path).ok();
+    }
+}
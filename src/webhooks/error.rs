@@ -0,0 +1,15 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum WebhookError {
+    #[error("Invalid webhook configuration: {cause}")]
+    Configuration { cause: String },
+    #[error("Failed to deliver the webhook: {cause}")]
+    Delivery { cause: String },
+    #[error("Unknown webhook trigger '{name}'")]
+    UnknownTrigger { name: String },
+    #[error("Invalid webhook signature: {cause}")]
+    InvalidSignature { cause: String },
+    #[error("Invalid webhook trigger parameters: {cause}")]
+    InvalidParameters { cause: String },
+}
@@ -0,0 +1,144 @@
+use rand::RngExt;
+use regex::{Captures, Regex};
+use serde_json::{Map, Value, json};
+use std::{
+    sync::LazyLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Placeholder of a template, e.g. `{{message}}` or `{{data.target}}`.
+pub static PLACEHOLDER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*([A-Za-z0-9_.]+)\s*\}\}").unwrap());
+
+/// Event worth a notification to the webhook endpoints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookEventKind {
+    /// The circuit of the RCON target opened after consecutive failures.
+    Unreachable,
+    /// The RCON server rejected the password.
+    AuthenticationFailed,
+    /// A command listed in `WEBHOOKS_DANGEROUS_COMMANDS` was executed through the API.
+    DangerousCommand,
+}
+
+/// Names of the kinds of webhook events, as used in the configuration and the payloads.
+pub const WEBHOOK_EVENT_KINDS: [&str; 3] = [
+    "rcon.unreachable",
+    "rcon.authentication_failed",
+    "rcon.dangerous_command",
+];
+
+impl WebhookEventKind {
+    /// Returns the name of the kind of event, as used in the configuration and the payloads.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEventKind::Unreachable => "rcon.unreachable",
+            WebhookEventKind::AuthenticationFailed => "rcon.authentication_failed",
+            WebhookEventKind::DangerousCommand => "rcon.dangerous_command",
+        }
+    }
+}
+
+/// Event posted to the webhook endpoints.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookEvent {
+    /// Random ID of the event, the same for every endpoint and attempt.
+    pub id: String,
+    pub kind: WebhookEventKind,
+    /// Human readable description of the event.
+    pub message: String,
+    /// Details of the event, depending on its kind.
+    pub data: Map<String, Value>,
+    pub timestamp: SystemTime,
+}
+
+impl WebhookEvent {
+    /// Creates an event without details.
+    pub fn new(kind: WebhookEventKind, message: impl Into<String>) -> Self {
+        WebhookEvent {
+            id: format!("{:032x}", rand::rng().random::<u128>()),
+            kind,
+            message: message.into(),
+            data: Map::new(),
+            timestamp: SystemTime::now(),
+        }
+    }
+
+    /// Adds a detail to the event.
+    pub fn with_data(mut self, key: &str, value: impl Into<Value>) -> Self {
+        self.data.insert(String::from(key), value.into());
+        self
+    }
+
+    /// Returns the default payload of the event.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "type": self.kind.as_str(),
+            "message": self.message,
+            "timestamp": self
+                .timestamp
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or_default(),
+            "data": self.data,
+        })
+    }
+
+    /// Renders a payload template with the fields of the event.
+    ///
+    /// The `{{placeholders}}` of the strings of the template are replaced with the fields of the
+    /// default payload, e.g. `{{message}}` or `{{data.target}}`, and `{{event}}` with the whole
+    /// payload. A string made of a single placeholder is replaced with the JSON value of the
+    /// field, other strings with its text, the missing fields being empty.
+    pub fn render(&self, template: &Value) -> Value {
+        let payload = self.to_json();
+        Self::render_value(template, &payload)
+    }
+
+    fn render_value(template: &Value, payload: &Value) -> Value {
+        match template {
+            Value::String(text) => {
+                if let Some(captures) = PLACEHOLDER.captures(text)
+                    && captures[0].len() == text.len()
+                {
+                    return Self::field(payload, &captures[1])
+                        .cloned()
+                        .unwrap_or(Value::Null);
+                }
+
+                Value::String(String::from(PLACEHOLDER.replace_all(
+                    text,
+                    |captures: &Captures| match Self::field(payload, &captures[1]) {
+                        Some(Value::String(value)) => value.clone(),
+                        Some(Value::Null) | None => String::new(),
+                        Some(value) => value.to_string(),
+                    },
+                )))
+            }
+            Value::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| Self::render_value(item, payload))
+                    .collect(),
+            ),
+            Value::Object(fields) => Value::Object(
+                fields
+                    .iter()
+                    .map(|(key, value)| (key.clone(), Self::render_value(value, payload)))
+                    .collect(),
+            ),
+            value => value.clone(),
+        }
+    }
+
+    /// Returns a field of the payload from its dotted path.
+    fn field<'a>(payload: &'a Value, path: &str) -> Option<&'a Value> {
+        if path == "event" {
+            return Some(payload);
+        }
+
+        path.split('.')
+            .try_fold(payload, |value, key| value.get(key))
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use crate::webhooks::{WEBHOOK_EVENT_KINDS, WebhookEvent, WebhookEventKind};
+    use serde_json::json;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    fn event() -> WebhookEvent {
+        WebhookEvent {
+            timestamp: UNIX_EPOCH + Duration::from_millis(1700000000123),
+            ..WebhookEvent::new(
+                WebhookEventKind::Unreachable,
+                "The RCON server 'localhost:25575' is unreachable",
+            )
+            .with_data("target", "localhost:25575")
+            .with_data("failures", 3)
+        }
+    }
+
+    #[test]
+    fn test_kinds() {
+        for kind in [
+            WebhookEventKind::Unreachable,
+            WebhookEventKind::AuthenticationFailed,
+            WebhookEventKind::DangerousCommand,
+        ] {
+            assert!(WEBHOOK_EVENT_KINDS.contains(&kind.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_to_json() {
+        let event = event();
+
+        assert_eq!(event.id.len(), 32);
+        assert_eq!(
+            event.to_json(),
+            json!({
+                "id": event.id,
+                "type": "rcon.unreachable",
+                "message": "The RCON server 'localhost:25575' is unreachable",
+                "timestamp": 1700000000123u64,
+                "data": {
+                    "target": "localhost:25575",
+                    "failures": 3,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let event = event();
+        let template = json!({
+            "text": ":warning: {{ message }} ({{type}}, {{data.failures}} failures{{data.missing}})",
+            "failures": "{{data.failures}}",
+            "missing": "{{data.missing}}",
+            "blocks": [{"type": "section", "target": "{{data.target}}"}],
+            "unchanged": true,
+        });
+
+        assert_eq!(
+            event.render(&template),
+            json!({
+                "text": ":warning: The RCON server 'localhost:25575' is unreachable (rcon.unreachable, 3 failures)",
+                "failures": 3,
+                "missing": null,
+                "blocks": [{"type": "section", "target": "localhost:25575"}],
+                "unchanged": true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_render_whole_event() {
+        let event = event();
+
+        assert_eq!(
+            event.render(&json!({"event": "{{event}}"})),
+            json!({"event": event.to_json()})
+        );
+    }
+}
@@ -0,0 +1,18 @@
+pub mod configuration;
+pub mod configuration_test;
+pub mod dispatcher;
+pub mod dispatcher_test;
+pub mod error;
+pub mod event;
+pub mod event_test;
+pub mod signature;
+pub mod signature_test;
+pub mod trigger;
+pub mod trigger_test;
+
+pub use configuration::*;
+pub use dispatcher::*;
+pub use error::*;
+pub use event::*;
+pub use signature::*;
+pub use trigger::*;
@@ -0,0 +1,110 @@
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt::Write;
+
+/// Header giving the time the body was signed at, in seconds since the Unix epoch.
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "X-Webhook-Timestamp";
+
+/// Header giving the signature of the body, as `sha256=<hex>`.
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+
+/// Prefix of the signatures, naming the hash function.
+const SIGNATURE_PREFIX: &str = "sha256=";
+
+/// Returns the HMAC-SHA256 of a message, encoded in lowercase hexadecimal.
+///
+/// # Arguments
+///
+/// * `secret` - The shared secret.
+/// * `message` - The signed message.
+pub fn hmac_sha256_hex(secret: &[u8], message: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(message);
+
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .fold(String::with_capacity(64), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+/// Returns `true` if a hexadecimal signature is the HMAC-SHA256 of a message, compared in
+/// constant time.
+///
+/// # Arguments
+///
+/// * `secret` - The shared secret.
+/// * `message` - The signed message.
+/// * `signature` - The signature to check, in hexadecimal.
+pub fn verify_hmac_sha256_hex(secret: &[u8], message: &[u8], signature: &str) -> bool {
+    let Some(signature) = decode_hex(signature.trim()) else {
+        return false;
+    };
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(message);
+
+    mac.verify_slice(&signature).is_ok()
+}
+
+/// Signs a webhook body.
+///
+/// The signature covers the timestamp and the body, joined by a dot, so that a captured request
+/// cannot be replayed with another timestamp.
+///
+/// # Returns
+///
+/// The value of the `X-Webhook-Signature` header, e.g. `sha256=5f1d...`.
+pub fn sign_webhook(secret: &str, timestamp: u64, body: &[u8]) -> String {
+    format!(
+        "{SIGNATURE_PREFIX}{}",
+        hmac_sha256_hex(secret.as_bytes(), &signed_message(timestamp, body))
+    )
+}
+
+/// Returns `true` if the value of an `X-Webhook-Signature` header is the signature of a body.
+pub fn verify_webhook(secret: &str, timestamp: u64, body: &[u8], signature: &str) -> bool {
+    signature
+        .trim()
+        .strip_prefix(SIGNATURE_PREFIX)
+        .is_some_and(|signature| {
+            verify_hmac_sha256_hex(
+                secret.as_bytes(),
+                &signed_message(timestamp, body),
+                signature,
+            )
+        })
+}
+
+/// Returns the hexadecimal digits of the value of an `X-Webhook-Signature` header, without its
+/// prefix nor the whitespaces accepted around them, in lowercase.
+///
+/// Two headers verifying the same way have the same normalized signature, allowing to recognize
+/// a replayed request.
+pub fn normalize_webhook_signature(signature: &str) -> Option<String> {
+    signature
+        .trim()
+        .strip_prefix(SIGNATURE_PREFIX)
+        .map(|signature| signature.trim().to_lowercase())
+}
+
+/// Returns the message signed for a webhook body: `<timestamp>.<body>`.
+fn signed_message(timestamp: u64, body: &[u8]) -> Vec<u8> {
+    let mut message = format!("{timestamp}.").into_bytes();
+    message.extend_from_slice(body);
+
+    message
+}
+
+/// Decodes a hexadecimal string, in lowercase or uppercase.
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&hex[index..index + 2], 16).ok())
+        .collect()
+}
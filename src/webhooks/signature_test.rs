@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod tests {
+    use crate::webhooks::{
+        hmac_sha256_hex, normalize_webhook_signature, sign_webhook, verify_hmac_sha256_hex,
+        verify_webhook,
+    };
+
+    #[test]
+    fn test_hmac_sha256_hex() {
+        // RFC 4231, test case 2
+        assert_eq!(
+            hmac_sha256_hex(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_verify_hmac_sha256_hex() {
+        let signature = hmac_sha256_hex(b"secret", b"message");
+
+        assert!(verify_hmac_sha256_hex(b"secret", b"message", &signature));
+        assert!(verify_hmac_sha256_hex(
+            b"secret",
+            b"message",
+            &signature.to_uppercase()
+        ));
+        assert!(!verify_hmac_sha256_hex(b"other", b"message", &signature));
+        assert!(!verify_hmac_sha256_hex(b"secret", b"other", &signature));
+        assert!(!verify_hmac_sha256_hex(b"secret", b"message", "not hex"));
+        assert!(!verify_hmac_sha256_hex(b"secret", b"message", "abc"));
+    }
+
+    #[test]
+    fn test_sign_webhook() {
+        let body = br#"{"type":"rcon.unreachable"}"#;
+        let signature = sign_webhook("secret", 1700000000, body);
+
+        assert_eq!(
+            signature,
+            format!(
+                "sha256={}",
+                hmac_sha256_hex(b"secret", br#"1700000000.{"type":"rcon.unreachable"}"#)
+            )
+        );
+        assert!(verify_webhook("secret", 1700000000, body, &signature));
+    }
+
+    #[test]
+    fn test_verify_webhook_rejects_replay_with_other_timestamp() {
+        let body = b"{}";
+        let signature = sign_webhook("secret", 1700000000, body);
+
+        assert!(!verify_webhook("secret", 1700000001, body, &signature));
+        assert!(!verify_webhook(
+            "secret",
+            1700000000,
+            body,
+            signature.trim_start_matches("sha256=")
+        ));
+    }
+
+    #[test]
+    fn test_normalize_webhook_signature() {
+        assert_eq!(
+            normalize_webhook_signature(" sha256=  5BDC ").as_deref(),
+            Some("5bdc")
+        );
+        assert_eq!(normalize_webhook_signature("5bdc"), None);
+    }
+}
@@ -0,0 +1,178 @@
+use super::{
+    PLACEHOLDER, WebhookConfiguration, WebhookError, normalize_webhook_signature, verify_webhook,
+};
+use crate::rcon::Secret;
+use regex::Captures;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// Predefined commands executed when a signed request is posted to `POST /api/hooks/<name>`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookTrigger {
+    /// Name of the trigger, as given in the URL.
+    pub name: String,
+    /// Secret the requests are signed with.
+    pub secret: Secret,
+    /// Commands executed in order, their `{{placeholders}}` being replaced with the parameters
+    /// given in the body of the request.
+    pub commands: Vec<String>,
+}
+
+impl WebhookTrigger {
+    /// Returns the commands of the trigger, with their placeholders replaced with parameters.
+    ///
+    /// # Arguments
+    ///
+    /// * `parameters` - The parameters, strings, numbers or booleans.
+    ///
+    /// # Returns
+    ///
+    /// The commands, or a `WebhookError::InvalidParameters` if a parameter is missing or is not
+    /// a single line of text.
+    pub fn commands(&self, parameters: &Map<String, Value>) -> Result<Vec<String>, WebhookError> {
+        self.commands
+            .iter()
+            .map(|command| {
+                let mut error = None;
+                let command = PLACEHOLDER.replace_all(command, |captures: &Captures| {
+                    let name = &captures[1];
+                    match parameters.get(name).map(parameter_text) {
+                        Some(Ok(value)) => value,
+                        Some(Err(cause)) => {
+                            error.get_or_insert(format!("The parameter '{name}' {cause}"));
+                            String::new()
+                        }
+                        None => {
+                            error.get_or_insert(format!("The parameter '{name}' is missing"));
+                            String::new()
+                        }
+                    }
+                });
+
+                match error {
+                    Some(cause) => Err(WebhookError::InvalidParameters { cause }),
+                    None => Ok(String::from(command)),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns the text of a parameter, rejecting the values that could inject other commands.
+fn parameter_text(value: &Value) -> Result<String, &'static str> {
+    match value {
+        Value::String(text) if text.chars().any(char::is_control) => {
+            Err("contains control characters")
+        }
+        Value::String(text) => Ok(text.clone()),
+        Value::Number(number) => Ok(number.to_string()),
+        Value::Bool(boolean) => Ok(boolean.to_string()),
+        _ => Err("is not a string, a number or a boolean"),
+    }
+}
+
+/// Triggers of the webhooks file, authenticating the requests invoking them.
+///
+/// A request is signed like the outgoing webhooks, with the `X-Webhook-Timestamp` and
+/// `X-Webhook-Signature` headers. It is rejected when its timestamp is out of the tolerance
+/// window, and when its signature was already used, so that a captured request cannot be
+/// replayed.
+#[derive(Debug, Clone)]
+pub struct WebhookTriggers {
+    triggers: HashMap<String, WebhookTrigger>,
+    tolerance: u64,
+    /// Signatures of the accepted requests along with their timestamp, forgotten once out of the
+    /// tolerance window.
+    used_signatures: Arc<Mutex<HashMap<String, u64>>>,
+}
+
+impl WebhookTriggers {
+    /// Creates the triggers of a configuration.
+    pub fn new(configuration: &WebhookConfiguration) -> Self {
+        WebhookTriggers {
+            triggers: configuration
+                .triggers
+                .iter()
+                .map(|trigger| (trigger.name.clone(), trigger.clone()))
+                .collect(),
+            tolerance: configuration.trigger_tolerance,
+            used_signatures: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Authenticates a request invoking a trigger.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the trigger.
+    /// * `timestamp` - The value of the `X-Webhook-Timestamp` header.
+    /// * `signature` - The value of the `X-Webhook-Signature` header.
+    /// * `body` - The body of the request.
+    /// * `now` - The current time, in seconds since the Unix epoch.
+    ///
+    /// # Returns
+    ///
+    /// The trigger, a `WebhookError::UnknownTrigger` if it does not exist, otherwise a
+    /// `WebhookError::InvalidSignature` if the request is not authentic.
+    pub fn authenticate(
+        &self,
+        name: &str,
+        timestamp: Option<&str>,
+        signature: Option<&str>,
+        body: &[u8],
+        now: u64,
+    ) -> Result<&WebhookTrigger, WebhookError> {
+        let trigger = self
+            .triggers
+            .get(name)
+            .ok_or_else(|| WebhookError::UnknownTrigger {
+                name: String::from(name),
+            })?;
+
+        let (Some(timestamp), Some(signature)) = (timestamp, signature) else {
+            return Err(WebhookError::InvalidSignature {
+                cause: String::from(
+                    "The 'X-Webhook-Timestamp' and 'X-Webhook-Signature' headers are required",
+                ),
+            });
+        };
+        let timestamp = timestamp
+            .trim()
+            .parse::<u64>()
+            .ok()
+            .filter(|timestamp| timestamp.abs_diff(now) <= self.tolerance)
+            .ok_or_else(|| WebhookError::InvalidSignature {
+                cause: format!(
+                    "The timestamp is not within {} seconds of the current time",
+                    self.tolerance
+                ),
+            })?;
+        if !verify_webhook(trigger.secret.expose(), timestamp, body, signature) {
+            return Err(WebhookError::InvalidSignature {
+                cause: String::from("The signature does not match the body"),
+            });
+        }
+
+        // A verified signature always has a prefix.
+        let signature = normalize_webhook_signature(signature).unwrap_or_default();
+        let mut used_signatures = self.lock_used_signatures();
+        used_signatures.retain(|_, used_at| used_at.abs_diff(now) <= self.tolerance);
+        if used_signatures.insert(signature, timestamp).is_some() {
+            return Err(WebhookError::InvalidSignature {
+                cause: String::from("The request was already received"),
+            });
+        }
+
+        Ok(trigger)
+    }
+
+    fn lock_used_signatures(&self) -> MutexGuard<'_, HashMap<String, u64>> {
+        self.used_signatures
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod tests {
+    use crate::{
+        rcon::Secret,
+        webhooks::{
+            WebhookConfiguration, WebhookError, WebhookTrigger, WebhookTriggers, sign_webhook,
+        },
+    };
+    use serde_json::{Map, Value, json};
+    use std::collections::HashSet;
+    use test_case::test_case;
+
+    const NOW: u64 = 1700000000;
+    const SECRET: &str = "s3cr3t";
+
+    fn trigger() -> WebhookTrigger {
+        WebhookTrigger {
+            name: String::from("deploy"),
+            secret: Secret::from(String::from(SECRET)),
+            commands: vec![
+                String::from("say Deploying {{version}} by {{ author }}"),
+                String::from("reload"),
+            ],
+        }
+    }
+
+    fn triggers() -> WebhookTriggers {
+        WebhookTriggers::new(&WebhookConfiguration {
+            endpoints: Vec::new(),
+            timeout: 1000,
+            max_attempts: 1,
+            base_delay: 0,
+            max_delay: 0,
+            dead_letter_path: None,
+            dangerous_commands: HashSet::new(),
+            triggers: vec![trigger()],
+            trigger_tolerance: 300,
+        })
+    }
+
+    fn parameters(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_commands() {
+        let commands = trigger()
+            .commands(&parameters(
+                json!({"version": "1.4.2", "author": "ci", "extra": 1}),
+            ))
+            .unwrap();
+
+        assert_eq!(commands, vec!["say Deploying 1.4.2 by ci", "reload"]);
+    }
+
+    #[test]
+    fn test_commands_scalar_parameters() {
+        let commands = trigger()
+            .commands(&parameters(json!({"version": 3, "author": true})))
+            .unwrap();
+
+        assert_eq!(commands[0], "say Deploying 3 by true");
+    }
+
+    #[test_case(json!({"version": "1.4.2"}); "missing parameter")]
+    #[test_case(json!({"version": "1.4.2\nop Steve", "author": "ci"}); "multiline parameter")]
+    #[test_case(json!({"version": ["1.4.2"], "author": "ci"}); "array parameter")]
+    #[test_case(json!({"version": null, "author": "ci"}); "null parameter")]
+    fn test_commands_invalid_parameters(value: Value) {
+        let result = trigger().commands(&parameters(value));
+
+        assert!(matches!(
+            result,
+            Err(WebhookError::InvalidParameters { .. })
+        ));
+    }
+
+    #[test]
+    fn test_authenticate() {
+        let triggers = triggers();
+        let body = br#"{"version":"1.4.2"}"#;
+        let signature = sign_webhook(SECRET, NOW - 10, body);
+
+        let trigger = triggers
+            .authenticate(
+                "deploy",
+                Some(&(NOW - 10).to_string()),
+                Some(&signature),
+                body,
+                NOW,
+            )
+            .unwrap();
+        assert_eq!(trigger.name, "deploy");
+    }
+
+    #[test]
+    fn test_authenticate_unknown_trigger() {
+        let triggers = triggers();
+        let result = triggers.authenticate("backup", None, None, b"", NOW);
+
+        assert_eq!(
+            result.unwrap_err(),
+            WebhookError::UnknownTrigger {
+                name: String::from("backup")
+            }
+        );
+    }
+
+    #[test_case(None, None; "missing headers")]
+    #[test_case(Some("soon"), Some(sign_webhook(SECRET, NOW, b"{}")); "invalid timestamp")]
+    #[test_case(Some("1699999000"), Some(sign_webhook(SECRET, 1699999000, b"{}")); "expired timestamp")]
+    #[test_case(Some("1700001000"), Some(sign_webhook(SECRET, 1700001000, b"{}")); "future timestamp")]
+    #[test_case(Some("1700000000"), Some(sign_webhook("other", NOW, b"{}")); "wrong secret")]
+    #[test_case(Some("1700000000"), Some(sign_webhook(SECRET, NOW, b"{ }")); "other body")]
+    #[test_case(Some("1700000000"), Some(String::from("sha256=zz")); "malformed signature")]
+    fn test_authenticate_invalid_signature(timestamp: Option<&str>, signature: Option<String>) {
+        let triggers = triggers();
+        let result = triggers.authenticate("deploy", timestamp, signature.as_deref(), b"{}", NOW);
+
+        assert!(matches!(result, Err(WebhookError::InvalidSignature { .. })));
+    }
+
+    #[test]
+    fn test_authenticate_replay() {
+        let triggers = triggers();
+        let signature = sign_webhook(SECRET, NOW, b"{}");
+        let timestamp = NOW.to_string();
+
+        assert!(
+            triggers
+                .authenticate("deploy", Some(&timestamp), Some(&signature), b"{}", NOW)
+                .is_ok()
+        );
+        assert!(matches!(
+            triggers.authenticate("deploy", Some(&timestamp), Some(&signature), b"{}", NOW + 1),
+            Err(WebhookError::InvalidSignature { .. })
+        ));
+
+        // The same signature padded or in uppercase is still a replay.
+        let hex = signature.strip_prefix("sha256=").unwrap();
+        for padded in [
+            format!("sha256= {hex}"),
+            format!("sha256=  {hex} "),
+            format!(" sha256={}", hex.to_uppercase()),
+        ] {
+            assert!(matches!(
+                triggers.authenticate("deploy", Some(&timestamp), Some(&padded), b"{}", NOW + 1),
+                Err(WebhookError::InvalidSignature { .. })
+            ));
+        }
+
+        // Another request signed at the same time is accepted.
+        let signature = sign_webhook(SECRET, NOW, br#"{"version":"1.4.3"}"#);
+        assert!(
+            triggers
+                .authenticate(
+                    "deploy",
+                    Some(&timestamp),
+                    Some(&signature),
+                    br#"{"version":"1.4.3"}"#,
+                    NOW + 1
+                )
+                .is_ok()
+        );
+    }
+}